@@ -1,9 +1,13 @@
 use crate::migrate;
 use crate::opt::ConnectOpts;
+use anyhow::Context;
 use console::style;
 use promptly::{prompt, ReadlineError};
 use sqlx::any::Any;
 use sqlx::migrate::MigrateDatabase;
+use sqlx::{Connection, Executor};
+use std::fs;
+use std::path::Path;
 
 pub async fn create(connect_opts: &ConnectOpts) -> anyhow::Result<()> {
     // NOTE: only retry the idempotent action.
@@ -41,11 +45,18 @@ pub async fn drop(connect_opts: &ConnectOpts, confirm: bool) -> anyhow::Result<(
 
 pub async fn reset(
     migration_source: &str,
+    seed_path: Option<&Path>,
     connect_opts: &ConnectOpts,
     confirm: bool,
 ) -> anyhow::Result<()> {
     drop(connect_opts, confirm).await?;
-    setup(migration_source, connect_opts).await
+    setup(migration_source, connect_opts).await?;
+
+    if let Some(seed_path) = seed_path {
+        seed(seed_path, connect_opts).await?;
+    }
+
+    Ok(())
 }
 
 pub async fn setup(migration_source: &str, connect_opts: &ConnectOpts) -> anyhow::Result<()> {
@@ -53,6 +64,49 @@ pub async fn setup(migration_source: &str, connect_opts: &ConnectOpts) -> anyhow
     migrate::run(migration_source, connect_opts, false, false).await
 }
 
+/// Runs a SQL file, or every SQL file in a folder, each in its own transaction.
+pub async fn seed(seed_path: &Path, connect_opts: &ConnectOpts) -> anyhow::Result<()> {
+    let files = if seed_path.is_dir() {
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(seed_path)
+            .with_context(|| format!("failed to read seed folder {}", seed_path.display()))?
+        {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map_or(false, |ext| ext == "sql") {
+                files.push(path);
+            }
+        }
+
+        files.sort();
+        files
+    } else {
+        vec![seed_path.to_path_buf()]
+    };
+
+    let mut conn = crate::connect(connect_opts).await?;
+
+    for path in files {
+        let sql = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read seed file {}", path.display()))?;
+
+        let mut tx = conn.begin().await?;
+
+        tx.execute(&*sql)
+            .await
+            .with_context(|| format!("failed to run seed file {}", path.display()))?;
+
+        tx.commit().await?;
+
+        println!("Seeded {}", style(path.display()).cyan());
+    }
+
+    let _ = conn.close().await;
+
+    Ok(())
+}
+
 fn ask_to_continue(connect_opts: &ConnectOpts) -> bool {
     loop {
         let r: Result<String, ReadlineError> = prompt(format!(
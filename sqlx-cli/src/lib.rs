@@ -56,8 +56,11 @@ pub async fn run(opt: Opt) -> Result<()> {
             DatabaseCommand::Reset {
                 confirmation,
                 source,
+                seed,
                 connect_opts,
-            } => database::reset(&source, &connect_opts, !confirmation.yes).await?,
+            } => {
+                database::reset(&source, seed.as_deref(), &connect_opts, !confirmation.yes).await?
+            }
             DatabaseCommand::Setup {
                 source,
                 connect_opts,
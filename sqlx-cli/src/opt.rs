@@ -1,4 +1,5 @@
 use std::ops::{Deref, Not};
+use std::path::PathBuf;
 
 use clap::{Args, Parser};
 #[cfg(feature = "completions")]
@@ -86,6 +87,12 @@ pub enum DatabaseCommand {
         #[clap(flatten)]
         source: Source,
 
+        /// Path to a SQL file, or a folder of SQL files, to run after the migrations.
+        ///
+        /// Files in a folder are run in lexicographic order, each in its own transaction.
+        #[clap(long)]
+        seed: Option<PathBuf>,
+
         #[clap(flatten)]
         connect_opts: ConnectOpts,
     },
@@ -223,7 +230,7 @@ pub struct ConnectOpts {
 pub struct Confirmation {
     /// Automatic confirmation. Without this option, you will be prompted before dropping
     /// your database.
-    #[clap(short)]
+    #[clap(short, long = "force")]
     pub yes: bool,
 }
 
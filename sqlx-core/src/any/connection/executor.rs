@@ -1,10 +1,13 @@
 use crate::any::{Any, AnyConnection, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
+use crate::connection::rewrite_query;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+use std::borrow::Cow;
 
 impl<'c> Executor<'c> for &'c mut AnyConnection {
     type Database = Any;
@@ -17,8 +20,19 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
         'c: 'e,
         E: Execute<'q, Any>,
     {
-        let arguments = query.take_arguments();
-        self.backend.fetch_many(query.sql(), arguments)
+        let sql = query.sql();
+        let mut arguments = query.take_arguments();
+
+        Box::pin(try_stream! {
+            let sql = rewrite_query(self.query_rewriter.as_ref(), sql, arguments.as_mut())?;
+            let mut s = self.backend.fetch_many(&sql, arguments);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(v);
+            }
+
+            Ok(())
+        })
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -29,19 +43,34 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let arguments = query.take_arguments();
-        self.backend.fetch_optional(query.sql(), arguments)
+        let sql = query.sql();
+        let mut arguments = query.take_arguments();
+
+        Box::pin(async move {
+            let sql = rewrite_query(self.query_rewriter.as_ref(), sql, arguments.as_mut())?;
+            self.backend.fetch_optional(&sql, arguments).await
+        })
     }
 
     fn prepare_with<'e, 'q: 'e>(
         self,
         sql: &'q str,
-        parameters: &[AnyTypeInfo],
+        parameters: &'e [AnyTypeInfo],
     ) -> BoxFuture<'e, Result<AnyStatement<'q>, Error>>
     where
         'c: 'e,
     {
-        self.backend.prepare_with(sql, parameters)
+        Box::pin(async move {
+            let rewritten = rewrite_query(self.query_rewriter.as_ref(), sql, None)?;
+            let statement = self.backend.prepare_with(&rewritten, parameters).await?;
+
+            Ok(AnyStatement {
+                sql: Cow::Borrowed(sql),
+                parameters: statement.parameters,
+                column_names: statement.column_names,
+                columns: statement.columns,
+            })
+        })
     }
 
     fn describe<'e, 'q: 'e>(
@@ -1,7 +1,9 @@
+use std::sync::Arc;
+
 use futures_core::future::BoxFuture;
 
 use crate::any::{Any, AnyConnectOptions};
-use crate::connection::{ConnectOptions, Connection};
+use crate::connection::{ConnectOptions, Connection, QueryRewriter};
 use crate::error::Error;
 
 use crate::database::Database;
@@ -24,6 +26,7 @@ mod executor;
 #[derive(Debug)]
 pub struct AnyConnection {
     pub(crate) backend: Box<dyn AnyConnectionBackend>,
+    pub(crate) query_rewriter: Option<Arc<dyn QueryRewriter<Any>>>,
 }
 
 impl AnyConnection {
@@ -48,12 +51,14 @@ impl AnyConnection {
             for<'a> TryFrom<&'a AnyConnectOptions, Error = Error>,
     {
         let res = TryFrom::try_from(options);
+        let query_rewriter = &options.query_rewriter;
 
         Box::pin(async {
             let options: <DB::Connection as Connection>::Options = res?;
 
             Ok(AnyConnection {
                 backend: Box::new(options.connect().await?),
+                query_rewriter: query_rewriter.clone(),
             })
         })
     }
@@ -26,6 +26,9 @@ impl Database for Any {
     const NAME: &'static str = "Any";
 
     const URL_SCHEMES: &'static [&'static str] = &[];
+
+    // the lowest limit of the drivers `Any` can connect to (SQLite)
+    const MAX_BIND_PARAMETERS: usize = 32766;
 }
 
 impl<'r> HasValueRef<'r> for Any {
@@ -1,9 +1,10 @@
-use crate::any::AnyConnection;
-use crate::connection::{ConnectOptions, LogSettings};
+use crate::any::{Any, AnyConnection};
+use crate::connection::{ConnectOptions, LogSettings, QueryEventHandler, QueryRewriter};
 use crate::error::Error;
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -19,6 +20,7 @@ use url::Url;
 pub struct AnyConnectOptions {
     pub database_url: Url,
     pub log_settings: LogSettings,
+    pub query_rewriter: Option<Arc<dyn QueryRewriter<Any>>>,
 }
 impl FromStr for AnyConnectOptions {
     type Err = Error;
@@ -29,6 +31,7 @@ impl FromStr for AnyConnectOptions {
                 .parse::<Url>()
                 .map_err(|e| Error::Configuration(e.into()))?,
             log_settings: LogSettings::default(),
+            query_rewriter: None,
         })
     }
 }
@@ -40,6 +43,7 @@ impl ConnectOptions for AnyConnectOptions {
         Ok(AnyConnectOptions {
             database_url: url.clone(),
             log_settings: LogSettings::default(),
+            query_rewriter: None,
         })
     }
 
@@ -58,4 +62,14 @@ impl ConnectOptions for AnyConnectOptions {
         self.log_settings.slow_statements_duration = duration;
         self
     }
+
+    fn event_handler(mut self, handler: Arc<dyn QueryEventHandler>) -> Self {
+        self.log_settings.event_handler = Some(handler);
+        self
+    }
+
+    fn query_rewriter(mut self, rewriter: Arc<dyn QueryRewriter<Any>>) -> Self {
+        self.query_rewriter = Some(rewriter);
+        self
+    }
 }
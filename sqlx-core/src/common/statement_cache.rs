@@ -38,6 +38,11 @@ impl<T> StatementCache<T> {
         lru_item
     }
 
+    /// Removes the statement with the given key from the cache, returning it if it existed.
+    pub fn remove(&mut self, k: &str) -> Option<T> {
+        self.inner.remove(k)
+    }
+
     /// The number of statements in the cache.
     pub fn len(&self) -> usize {
         self.inner.len()
@@ -63,6 +68,12 @@ impl<T> StatementCache<T> {
         self.inner.capacity()
     }
 
+    /// Sets the maximum number of statements the cache can hold, removing the least recently
+    /// used statements if it holds more.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.inner.set_capacity(capacity);
+    }
+
     /// Returns true if the cache capacity is more than 0.
     #[allow(dead_code)] // Only used for some `cfg`s
     pub fn is_enabled(&self) -> bool {
@@ -1,11 +1,13 @@
-use crate::database::{Database, HasStatementCache};
+use crate::database::{Database, HasArguments, HasStatementCache};
 use crate::error::Error;
 
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
-use std::fmt::Debug;
+use std::borrow::Cow;
+use std::fmt::{self, Debug};
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -42,6 +44,25 @@ pub trait Connection: Send {
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Checks if a connection to the database is still valid, failing with an I/O error of kind
+    /// [`TimedOut`](std::io::ErrorKind::TimedOut) if the database does not respond within
+    /// `timeout`.
+    ///
+    /// A connection whose peer silently went away, e.g. because a load balancer dropped it while
+    /// it was idle, may otherwise only fail once the operating system gives up on it.
+    /// The state of the connection is unknown after a timeout, so it should be closed.
+    fn ping_with_timeout(&mut self, timeout: Duration) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            match crate::rt::timeout(timeout, self.ping()).await {
+                Ok(res) => res,
+                Err(_) => Err(Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "ping timed out",
+                ))),
+            }
+        })
+    }
+
     /// Begin a new transaction or establish a savepoint within the active transaction.
     ///
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
@@ -49,6 +70,37 @@ pub trait Connection: Send {
     where
         Self: Sized;
 
+    /// Begin a new transaction with the given isolation level and access mode.
+    ///
+    /// Savepoints cannot change these options, so this fails if options are given while a
+    /// transaction is already active. It also fails if the database does not support an option.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::postgres::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::Connection;
+    /// use sqlx::{IsolationLevel, TransactionOptions};
+    ///
+    /// let mut tx = conn
+    ///     .begin_with(
+    ///         TransactionOptions::new()
+    ///             .isolation(IsolationLevel::Serializable)
+    ///             .read_only(true),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+        Self::Database: Database<Connection = Self>,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     /// Execute the function inside a transaction.
     ///
     /// If the function returns an error, the transaction will be rolled back. If it does not
@@ -126,6 +178,13 @@ pub trait Connection: Send {
     /// allow the buffers to shrink.
     fn shrink_buffers(&mut self);
 
+    /// Tests the connection before it is returned to a pool, resetting the state of the
+    /// session if the driver is configured to.
+    #[doc(hidden)]
+    fn before_release(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        self.ping()
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
@@ -155,12 +214,13 @@ pub trait Connection: Send {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 #[non_exhaustive]
 pub struct LogSettings {
     pub statements_level: LevelFilter,
     pub slow_statements_level: LevelFilter,
     pub slow_statements_duration: Duration,
+    pub event_handler: Option<Arc<dyn QueryEventHandler>>,
 }
 
 impl Default for LogSettings {
@@ -169,10 +229,22 @@ impl Default for LogSettings {
             statements_level: LevelFilter::Debug,
             slow_statements_level: LevelFilter::Warn,
             slow_statements_duration: Duration::from_secs(1),
+            event_handler: None,
         }
     }
 }
 
+impl Debug for LogSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogSettings")
+            .field("statements_level", &self.statements_level)
+            .field("slow_statements_level", &self.slow_statements_level)
+            .field("slow_statements_duration", &self.slow_statements_duration)
+            .field("event_handler", &self.event_handler.is_some())
+            .finish()
+    }
+}
+
 impl LogSettings {
     pub fn log_statements(&mut self, level: LevelFilter) {
         self.statements_level = level;
@@ -181,6 +253,113 @@ impl LogSettings {
         self.slow_statements_level = level;
         self.slow_statements_duration = duration;
     }
+    pub fn event_handler(&mut self, handler: Arc<dyn QueryEventHandler>) {
+        self.event_handler = Some(handler);
+    }
+}
+
+/// A query executed on a connection, passed to a [`QueryEventHandler`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct QueryEvent<'a> {
+    /// The SQL of the query.
+    pub sql: &'a str,
+
+    /// The number of arguments bound to the query.
+    pub arguments: usize,
+
+    /// The time from the start of the query until its results were consumed or dropped.
+    pub elapsed: Duration,
+
+    /// The number of rows inserted, updated or deleted by the query.
+    pub rows_affected: u64,
+
+    /// The number of rows returned by the query.
+    pub rows_returned: u64,
+
+    /// The error the query failed with, if any.
+    pub error: Option<&'a str>,
+}
+
+/// Receives an event for every query executed on a connection, e.g. to report queries to
+/// an application performance monitoring service, see [`ConnectOptions::event_handler()`].
+///
+/// The handler is invoked on the task or thread which executes the query, once the query
+/// completed or was dropped, and should return quickly.
+pub trait QueryEventHandler: Send + Sync + 'static {
+    fn on_query(&self, event: &QueryEvent<'_>);
+}
+
+impl<F> QueryEventHandler for F
+where
+    F: Fn(&QueryEvent<'_>) + Send + Sync + 'static,
+{
+    fn on_query(&self, event: &QueryEvent<'_>) {
+        self(event)
+    }
+}
+
+/// Inspects or modifies queries before they are executed on a connection, e.g. to add a
+/// `/* trace_id */` comment or query hints, or to refuse statements that write, see
+/// [`ConnectOptions::query_rewriter()`].
+///
+/// The rewriter is invoked for every query executed on the connection, including queries
+/// executing a prepared [`Statement`](crate::statement::Statement), and when a statement is
+/// prepared. It is not invoked for statements the driver issues itself, e.g. to begin and end
+/// transactions or to set up the session of a new connection.
+///
+/// Queries are cached as prepared statements by their rewritten SQL, so rewriting a query
+/// differently each time it is executed, e.g. with a unique trace ID, prepares it every time.
+/// Consider making such queries non-persistent.
+///
+/// ```rust,no_run
+/// # use std::borrow::Cow;
+/// # use sqlx::{postgres::PgArguments, Error, Postgres, QueryRewriter};
+/// struct TraceComment(String);
+///
+/// impl QueryRewriter<Postgres> for TraceComment {
+///     fn rewrite<'q>(
+///         &self,
+///         sql: &mut Cow<'q, str>,
+///         _arguments: Option<&mut PgArguments>,
+///     ) -> Result<(), Error> {
+///         *sql = format!("/* trace_id={} */ {}", self.0, sql).into();
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait QueryRewriter<DB: Database>: Send + Sync + 'static {
+    /// Rewrite the SQL or the arguments of a query, or return an error to refuse to execute it.
+    ///
+    /// `arguments` is `None` if no arguments were bound, when a statement is prepared and when
+    /// a statement is executed with a batch of arguments.
+    fn rewrite<'q>(
+        &self,
+        sql: &mut Cow<'q, str>,
+        arguments: Option<&mut <DB as HasArguments<'q>>::Arguments>,
+    ) -> Result<(), Error>;
+}
+
+impl<DB: Database> Debug for dyn QueryRewriter<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("QueryRewriter")
+    }
+}
+
+/// Apply the [`QueryRewriter`] of a connection, if any, to a query before executing it.
+#[doc(hidden)]
+pub fn rewrite_query<'q, DB: Database>(
+    rewriter: Option<&Arc<dyn QueryRewriter<DB>>>,
+    sql: &'q str,
+    arguments: Option<&mut <DB as HasArguments<'q>>::Arguments>,
+) -> Result<Cow<'q, str>, Error> {
+    let mut sql = Cow::Borrowed(sql);
+
+    if let Some(rewriter) = rewriter {
+        rewriter.rewrite(&mut sql, arguments)?;
+    }
+
+    Ok(sql)
 }
 
 pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug + Clone {
@@ -201,6 +380,31 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
     /// at the specified `level`.
     fn log_slow_statements(self, level: LevelFilter, duration: Duration) -> Self;
 
+    /// Invoke `handler` for every query executed on the connection, with the SQL, the number
+    /// of arguments, the duration, the number of rows and the error of the query.
+    ///
+    /// Events are delivered regardless of the levels set with
+    /// [`log_statements()`](Self::log_statements) and
+    /// [`log_slow_statements()`](Self::log_slow_statements).
+    ///
+    /// The default implementation ignores `handler`, for drivers which don't report query
+    /// events.
+    fn event_handler(self, _handler: Arc<dyn QueryEventHandler>) -> Self {
+        self
+    }
+
+    /// Pass every query executed on the connection through `rewriter` first, which can modify
+    /// its SQL and arguments or refuse to execute it.
+    ///
+    /// The default implementation ignores `rewriter`, for drivers which don't support rewriting
+    /// queries.
+    fn query_rewriter(
+        self,
+        _rewriter: Arc<dyn QueryRewriter<<Self::Connection as Connection>::Database>>,
+    ) -> Self {
+        self
+    }
+
     /// Entirely disables statement logging (both slow and regular).
     fn disable_statement_logging(self) -> Self {
         self.log_statements(LevelFilter::Off)
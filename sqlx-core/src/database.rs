@@ -105,6 +105,12 @@ pub trait Database:
 
     /// The schemes for database URLs that should match this driver.
     const URL_SCHEMES: &'static [&'static str];
+
+    /// The maximum number of bind parameters in a single query.
+    ///
+    /// See [`QueryBuilder::push_bind()`][crate::query_builder::QueryBuilder::push_bind] for
+    /// the limits of the supported databases.
+    const MAX_BIND_PARAMETERS: usize = 65535;
 }
 
 /// Associate [`Database`] with a [`ValueRef`](crate::value::ValueRef) of a generic lifetime.
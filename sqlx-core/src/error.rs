@@ -78,6 +78,11 @@ pub enum Error {
         source: BoxDynError,
     },
 
+    /// Error occurred while encoding a value, e.g. because it does not match the type of the
+    /// parameter it is bound to.
+    #[error("error occurred while encoding a value: {0}")]
+    Encode(#[source] BoxDynError),
+
     /// Error occurred while decoding a value.
     #[error("error occurred while decoding: {0}")]
     Decode(#[source] BoxDynError),
@@ -104,6 +109,12 @@ pub enum Error {
     #[error("attempted to communicate with a crashed background worker")]
     WorkerCrashed,
 
+    /// A statement was interrupted because it ran longer than the configured timeout.
+    ///
+    /// The connection remains usable.
+    #[error("statement timed out")]
+    Timeout,
+
     #[cfg(feature = "migrate")]
     #[error("{0}")]
     Migrate(#[source] Box<crate::migrate::MigrateError>),
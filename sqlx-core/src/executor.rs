@@ -6,7 +6,9 @@ use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{future, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
+use std::cmp;
 use std::fmt::Debug;
+use std::time::Duration;
 
 /// A type that contains or can provide a database
 /// connection to use for executing queries against the database.
@@ -39,6 +41,10 @@ pub trait Executor<'c>: Send + Debug + Sized {
     }
 
     /// Execute multiple queries and return the rows affected from each query, in a stream.
+    ///
+    /// One result is produced for each statement of a multi-statement batch, in order. The stream
+    /// ends with the first error, so the number of results received before an error is the index
+    /// of the statement that failed.
     fn execute_many<'e, 'q: 'e, E: 'q>(
         self,
         query: E,
@@ -104,6 +110,43 @@ pub trait Executor<'c>: Send + Debug + Sized {
         self.fetch(query).try_collect().boxed()
     }
 
+    /// Execute the query and return the generated results as a stream of batches of at most
+    /// `batch_size` rows.
+    ///
+    /// Drivers which support server-side cursors (MySQL) fetch each batch from the database
+    /// separately, so huge result sets are read with bounded memory. Other drivers stream the
+    /// rows as with [`fetch`](Self::fetch) and group them into batches.
+    fn fetch_chunked<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+        batch_size: u32,
+    ) -> BoxStream<'e, Result<Vec<<Self::Database as Database>::Row>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        let batch_size = cmp::max(batch_size, 1) as usize;
+        let mut rows = self.fetch(query);
+
+        Box::pin(try_stream! {
+            let mut batch = Vec::with_capacity(batch_size);
+
+            while let Some(row) = rows.try_next().await? {
+                batch.push(row);
+
+                if batch.len() == batch_size {
+                    r#yield!(std::mem::replace(&mut batch, Vec::with_capacity(batch_size)));
+                }
+            }
+
+            if !batch.is_empty() {
+                r#yield!(batch);
+            }
+
+            Ok(())
+        })
+    }
+
     /// Execute the query and returns exactly one row.
     fn fetch_one<'e, 'q: 'e, E: 'q>(
         self,
@@ -199,6 +242,14 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 
     /// Returns `true` if the statement should be cached.
     fn persistent(&self) -> bool;
+
+    /// Returns how long the statement may run before the database is asked to cancel it.
+    ///
+    /// See [`Query::timeout`](crate::query::Query::timeout).
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
 }
 
 // NOTE: `Execute` is explicitly not implemented for String and &String to make it slightly more
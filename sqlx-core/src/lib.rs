@@ -69,13 +69,16 @@ pub mod executor;
 pub mod from_row;
 pub mod fs;
 pub mod io;
+pub mod listener;
 pub mod logger;
+pub mod metrics;
 pub mod net;
 pub mod query_as;
 pub mod query_builder;
 pub mod query_scalar;
 pub mod row;
 pub mod rt;
+pub mod shared_connection;
 pub mod sync;
 pub mod type_info;
 pub mod value;
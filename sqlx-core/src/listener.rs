@@ -0,0 +1,45 @@
+use std::fmt::Debug;
+
+use futures_core::future::BoxFuture;
+
+use crate::error::Error;
+
+/// An asynchronous notification received by a [`Listener`].
+pub trait Notification: Debug + Send + Sync + 'static {
+    /// The channel that the notification has been raised on.
+    fn channel(&self) -> &str;
+
+    /// The payload of the notification. An empty payload is received as an empty string.
+    fn payload(&self) -> &str;
+}
+
+/// Receives change notifications from the database in a generic way.
+///
+/// This allows applications to await notifications independently of the mechanism the database
+/// uses to deliver them, e.g. `LISTEN`/`NOTIFY` in Postgres.
+///
+/// ```rust,no_run
+/// # use sqlx::error::Error;
+/// # use sqlx::listener::{Listener, Notification};
+/// async fn watch(listener: &mut impl Listener) -> Result<(), Error> {
+///     listener.listen("orders").await?;
+///
+///     loop {
+///         let notification = listener.recv().await?;
+///
+///         println!("{}: {}", notification.channel(), notification.payload());
+///     }
+/// }
+/// ```
+pub trait Listener: Send {
+    type Notification: Notification;
+
+    /// Starts listening for notifications on a channel.
+    fn listen<'a>(&'a mut self, channel: &'a str) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Stops listening for notifications on a channel.
+    fn unlisten<'a>(&'a mut self, channel: &'a str) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Receives the next notification available from any of the subscribed channels.
+    fn recv(&mut self) -> BoxFuture<'_, Result<Self::Notification, Error>>;
+}
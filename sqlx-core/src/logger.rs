@@ -1,4 +1,5 @@
-use crate::connection::LogSettings;
+use crate::connection::{LogSettings, QueryEvent};
+use crate::error::Error;
 use std::time::Instant;
 
 // Yes these look silly. `tracing` doesn't currently support dynamic levels
@@ -58,6 +59,8 @@ pub use sqlformat;
 
 pub struct QueryLogger<'q> {
     sql: &'q str,
+    arguments: usize,
+    error: Option<String>,
     rows_returned: u64,
     rows_affected: u64,
     start: Instant,
@@ -68,6 +71,8 @@ impl<'q> QueryLogger<'q> {
     pub fn new(sql: &'q str, settings: LogSettings) -> Self {
         Self {
             sql,
+            arguments: 0,
+            error: None,
             rows_returned: 0,
             rows_affected: 0,
             start: Instant::now(),
@@ -83,9 +88,38 @@ impl<'q> QueryLogger<'q> {
         self.rows_affected += n;
     }
 
+    pub fn set_arguments(&mut self, n: usize) {
+        self.arguments = n;
+    }
+
+    /// Records the error the query failed with, to be reported to the event handler, and
+    /// returns it.
+    pub fn record_error(&mut self, error: Error) -> Error {
+        crate::metrics::query_failed(&error);
+
+        if self.error.is_none() && self.settings.event_handler.is_some() {
+            self.error = Some(error.to_string());
+        }
+
+        error
+    }
+
     pub fn finish(&self) {
         let elapsed = self.start.elapsed();
 
+        crate::metrics::query_finished(elapsed);
+
+        if let Some(handler) = &self.settings.event_handler {
+            handler.on_query(&QueryEvent {
+                sql: self.sql,
+                arguments: self.arguments,
+                elapsed,
+                rows_affected: self.rows_affected,
+                rows_returned: self.rows_returned,
+                error: self.error.as_deref(),
+            });
+        }
+
         let lvl = if elapsed >= self.settings.slow_statements_duration {
             self.settings.slow_statements_level
         } else {
@@ -0,0 +1,70 @@
+//! Metrics reported through the [`metrics`](https://docs.rs/metrics) facade.
+//!
+//! Requires the `metrics` Cargo feature flag; without it, these functions do nothing. An exporter
+//! must be installed by the application for the metrics to be recorded anywhere.
+//!
+//! | Name                                  | Type      | Description                                   |
+//! |---------------------------------------|-----------|-----------------------------------------------|
+//! | `sqlx_pool_connections`               | gauge     | Connections currently open in a pool          |
+//! | `sqlx_pool_idle_connections`          | gauge     | Connections currently idle in a pool          |
+//! | `sqlx_pool_acquire_duration_seconds`  | histogram | Time spent waiting in `Pool::acquire()`       |
+//! | `sqlx_pool_acquire_errors_total`      | counter   | Calls to `Pool::acquire()` that failed        |
+//! | `sqlx_queries_total`                  | counter   | Queries executed                              |
+//! | `sqlx_query_duration_seconds`         | histogram | Time spent executing a query                  |
+//! | `sqlx_query_errors_total`             | counter   | Failed queries, labeled by `code` (SQLSTATE)  |
+//! | `sqlx_shared_connection_queued`       | gauge     | Calls waiting for a [`SharedConnection`]      |
+//! | `sqlx_shared_connection_wait_seconds` | histogram | Time a call waited for a [`SharedConnection`] |
+//!
+//! [`SharedConnection`]: crate::shared_connection::SharedConnection
+#![cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+
+use std::time::Duration;
+
+use crate::error::Error;
+
+pub(crate) fn pool_connections(size: u32, idle: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::gauge!("sqlx_pool_connections", size as f64);
+        ::metrics::gauge!("sqlx_pool_idle_connections", idle as f64);
+    }
+}
+
+pub(crate) fn pool_acquired(elapsed: Duration, success: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::histogram!("sqlx_pool_acquire_duration_seconds", elapsed);
+
+        if !success {
+            ::metrics::increment_counter!("sqlx_pool_acquire_errors_total");
+        }
+    }
+}
+
+pub(crate) fn query_finished(elapsed: Duration) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::increment_counter!("sqlx_queries_total");
+        ::metrics::histogram!("sqlx_query_duration_seconds", elapsed);
+    }
+}
+
+pub(crate) fn query_failed(error: &Error) {
+    #[cfg(feature = "metrics")]
+    {
+        let code = error
+            .as_database_error()
+            .and_then(|e| e.code())
+            .map_or_else(|| String::from("none"), |code| code.into_owned());
+
+        ::metrics::increment_counter!("sqlx_query_errors_total", "code" => code);
+    }
+}
+
+pub(crate) fn shared_connection_dequeued(waited: Duration, queued: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::histogram!("sqlx_shared_connection_wait_seconds", waited);
+        ::metrics::gauge!("sqlx_shared_connection_queued", queued as f64);
+    }
+}
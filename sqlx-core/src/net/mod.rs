@@ -1,4 +1,7 @@
 mod socket;
 pub mod tls;
 
-pub use socket::{connect_tcp, connect_uds, BufferedSocket, Socket, SocketIntoBox, WithSocket};
+pub use socket::{
+    connect_named_pipe, connect_tcp, connect_uds, BufferedSocket, Socket, SocketIntoBox,
+    StreamSocket, WithSocket,
+};
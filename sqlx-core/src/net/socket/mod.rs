@@ -8,10 +8,12 @@ use bytes::BufMut;
 use futures_core::ready;
 
 pub use buffered::{BufferedSocket, WriteBuffer};
+pub use stream::StreamSocket;
 
 use crate::io::ReadBuf;
 
 mod buffered;
+mod stream;
 
 pub trait Socket: Send + Sync + Unpin + 'static {
     fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize>;
@@ -258,3 +260,43 @@ pub async fn connect_uds<P: AsRef<Path>, Ws: WithSocket>(
         crate::rt::missing_rt((path, with_socket))
     }
 }
+
+/// Connect to a Windows named pipe at the given path (e.g. `\\.\pipe\MySQL`).
+///
+/// Returns an error if named pipes are not supported on this platform or runtime.
+pub async fn connect_named_pipe<P: AsRef<Path>, Ws: WithSocket>(
+    path: P,
+    with_socket: Ws,
+) -> crate::Result<Ws::Output> {
+    #[cfg(all(windows, feature = "_rt-tokio"))]
+    if crate::rt::rt_tokio::available() {
+        use std::time::Duration;
+        use tokio::net::windows::named_pipe::ClientOptions;
+
+        // ERROR_PIPE_BUSY
+        const PIPE_BUSY: i32 = 231;
+
+        let pipe = loop {
+            match ClientOptions::new().open(path.as_ref()) {
+                Ok(pipe) => break pipe,
+
+                // all instances of the pipe are in use, wait for the server to create a new one
+                Err(e) if e.raw_os_error() == Some(PIPE_BUSY) => {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+
+                Err(e) => return Err(e.into()),
+            }
+        };
+
+        return Ok(with_socket.with_socket(pipe));
+    }
+
+    drop((path, with_socket));
+
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "named pipes are only supported on Windows with the Tokio runtime",
+    )
+    .into())
+}
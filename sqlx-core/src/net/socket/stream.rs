@@ -0,0 +1,150 @@
+use std::cmp;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, BufMut, BytesMut};
+use futures_core::ready;
+use futures_io::{AsyncRead, AsyncWrite};
+
+use crate::io::ReadBuf;
+use crate::net::Socket;
+
+// the number of bytes read from the stream at once, and buffered for writing at most
+const BUF_SIZE: usize = 8192;
+
+/// A [`Socket`] over any [`AsyncRead`] + [`AsyncWrite`] stream, e.g. a Unix socket proxy, an
+/// SSH tunnel or an in-memory duplex stream in tests.
+///
+/// Tokio streams can be adapted with the `compat` module of `tokio-util`.
+pub struct StreamSocket<S> {
+    stream: S,
+    read_buf: BytesMut,
+    write_buf: BytesMut,
+    eof: bool,
+}
+
+impl<S> StreamSocket<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    pub fn new(stream: S) -> Self {
+        StreamSocket {
+            stream,
+            read_buf: BytesMut::new(),
+            write_buf: BytesMut::new(),
+            eof: false,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /// Writes buffered data to the stream until at most `max` bytes remain buffered.
+    fn poll_write_buffered(&mut self, cx: &mut Context<'_>, max: usize) -> Poll<io::Result<()>> {
+        while self.write_buf.len() > max {
+            let written = ready!(Pin::new(&mut self.stream).poll_write(cx, &self.write_buf))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+
+            self.write_buf.advance(written);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S> Socket for StreamSocket<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + Sync + 'static,
+{
+    fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+        if self.read_buf.is_empty() {
+            return if self.eof {
+                Ok(0)
+            } else {
+                Err(io::ErrorKind::WouldBlock.into())
+            };
+        }
+
+        let n = cmp::min(self.read_buf.len(), buf.remaining_mut());
+        buf.put_slice(&self.read_buf[..n]);
+        self.read_buf.advance(n);
+
+        Ok(n)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = cmp::min(buf.len(), BUF_SIZE - self.write_buf.len());
+
+        if n == 0 && !buf.is_empty() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        self.write_buf.extend_from_slice(&buf[..n]);
+
+        Ok(n)
+    }
+
+    fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.read_buf.is_empty() || self.eof {
+            return Poll::Ready(Ok(()));
+        }
+
+        self.read_buf.resize(BUF_SIZE, 0);
+
+        let res = Pin::new(&mut self.stream).poll_read(cx, &mut self.read_buf);
+        let n = match &res {
+            Poll::Ready(Ok(n)) => *n,
+            _ => 0,
+        };
+
+        self.read_buf.truncate(n);
+        self.eof = matches!(res, Poll::Ready(Ok(0)));
+
+        res.map_ok(|_| ())
+    }
+
+    fn poll_write_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // make room in the buffer
+        self.poll_write_buffered(cx, BUF_SIZE - 1)
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_buffered(cx, 0))?;
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_buffered(cx, 0))?;
+        Pin::new(&mut self.stream).poll_close(cx)
+    }
+}
+
+#[test]
+fn test_stream_socket_round_trip() {
+    use futures_util::io::Cursor;
+
+    let mut socket = StreamSocket::new(Cursor::new(b"hello".to_vec()));
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let mut buf = BytesMut::with_capacity(16);
+        let n = socket.read(&mut buf).await.unwrap();
+        assert_eq!(&buf[..n], b"hello");
+
+        // end of stream
+        assert_eq!(socket.read(&mut buf).await.unwrap(), 0);
+
+        socket.write(b" world").await.unwrap();
+        socket.flush().await.unwrap();
+    });
+
+    assert_eq!(socket.into_inner().into_inner(), b"hello world");
+}
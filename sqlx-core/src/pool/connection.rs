@@ -24,6 +24,8 @@ pub struct PoolConnection<DB: Database> {
 pub(super) struct Live<DB: Database> {
     pub(super) raw: DB::Connection,
     pub(super) created_at: Instant,
+    // the number of times the connection was handed out
+    pub(super) connect_count: u64,
 }
 
 pub(super) struct Idle<DB: Database> {
@@ -208,13 +210,16 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             inner: Live {
                 raw: conn,
                 created_at: Instant::now(),
+                connect_count: 0,
             },
             guard,
         }
     }
 
     pub fn reattach(self) -> PoolConnection<DB> {
-        let Floating { inner, guard } = self;
+        let Floating { mut inner, guard } = self;
+
+        inner.connect_count += 1;
 
         let pool = Arc::clone(&guard.pool);
 
@@ -264,7 +269,7 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         // returned to the pool; also of course, if it was dropped due to an error
         // this is simply a band-aid as SQLx-next connections should be able
         // to recover from cancellations
-        if let Err(error) = self.raw.ping().await {
+        if let Err(error) = self.raw.before_release().await {
             tracing::warn!(
                 %error,
                 "error occurred while testing the connection on-release",
@@ -306,6 +311,7 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         PoolConnectionMetadata {
             age: self.created_at.elapsed(),
             idle_for: Duration::ZERO,
+            connect_count: self.connect_count,
         }
     }
 }
@@ -326,6 +332,10 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
         self.live.raw.ping().await
     }
 
+    pub async fn ping_with_timeout(&mut self, timeout: Duration) -> Result<(), Error> {
+        self.live.raw.ping_with_timeout(timeout).await
+    }
+
     pub fn into_live(self) -> Floating<DB, Live<DB>> {
         Floating {
             inner: self.inner.live,
@@ -355,6 +365,7 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
             // https://github.com/launchbadge/sqlx/issues/1912
             age: now.saturating_duration_since(self.created_at),
             idle_for: now.saturating_duration_since(self.idle_since),
+            connect_count: self.connect_count,
         }
     }
 }
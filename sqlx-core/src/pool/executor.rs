@@ -36,6 +36,28 @@ where
         })
     }
 
+    fn fetch_chunked<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+        batch_size: u32,
+    ) -> BoxStream<'e, Result<Vec<DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        let pool = self.clone();
+
+        Box::pin(try_stream! {
+            let mut conn = pool.acquire().await?;
+            let mut s = conn.fetch_chunked(query, batch_size);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(v);
+            }
+
+            Ok(())
+        })
+    }
+
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
         self,
         query: E,
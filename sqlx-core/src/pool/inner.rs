@@ -3,7 +3,7 @@ use crate::connection::ConnectOptions;
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
-use crate::pool::{deadline_as_timeout, CloseEvent, Pool, PoolOptions};
+use crate::pool::{deadline_as_timeout, CloseEvent, Pool, PoolOptions, WarmUpReport};
 use crossbeam_queue::ArrayQueue;
 
 use crate::sync::{AsyncSemaphore, AsyncSemaphoreReleaser};
@@ -106,6 +106,30 @@ impl<DB: Database> PoolInner<DB> {
         }
     }
 
+    pub(super) async fn close_with_timeout(self: &Arc<Self>, timeout: Duration) -> u32 {
+        if crate::rt::timeout(timeout, self.close()).await.is_ok() {
+            return 0;
+        }
+
+        // Close any connections that were returned in the meantime but not yet closed.
+        while let Some(idle) = self.idle_conns.pop() {
+            let _ = idle.live.float((*self).clone()).close().await;
+        }
+
+        let remaining = self.size();
+
+        if remaining > 0 {
+            tracing::warn!(
+                remaining,
+                ?timeout,
+                "pool closed before all checked-out connections were returned; \
+                 they will be closed when dropped"
+            );
+        }
+
+        remaining
+    }
+
     pub(crate) fn close_event(&self) -> CloseEvent {
         CloseEvent {
             listener: (!self.is_closed()).then(|| self.on_closed.listen()),
@@ -210,6 +234,12 @@ impl<DB: Database> PoolInner<DB> {
         guard.release_permit();
 
         self.num_idle.fetch_add(1, Ordering::AcqRel);
+
+        self.record_metrics();
+    }
+
+    fn record_metrics(&self) {
+        crate::metrics::pool_connections(self.size(), self.num_idle());
     }
 
     /// Try to atomically increment the pool size for a new connection.
@@ -237,14 +267,22 @@ impl<DB: Database> PoolInner<DB> {
     }
 
     pub(super) async fn acquire(self: &Arc<Self>) -> Result<Floating<DB, Live<DB>>, Error> {
+        self.acquire_until(Instant::now() + self.options.acquire_timeout)
+            .await
+    }
+
+    pub(super) async fn acquire_until(
+        self: &Arc<Self>,
+        deadline: Instant,
+    ) -> Result<Floating<DB, Live<DB>>, Error> {
         if self.is_closed() {
             return Err(Error::PoolClosed);
         }
 
-        let deadline = Instant::now() + self.options.acquire_timeout;
+        let start = Instant::now();
 
-        crate::rt::timeout(
-            self.options.acquire_timeout,
+        let res = crate::rt::timeout(
+            deadline_as_timeout::<DB>(deadline)?,
             async {
                 loop {
                     // Handles the close-event internally
@@ -286,7 +324,13 @@ impl<DB: Database> PoolInner<DB> {
             }
         )
             .await
-            .map_err(|_| Error::PoolTimedOut)?
+            .map_err(|_| Error::PoolTimedOut)
+            .and_then(|res| res);
+
+        crate::metrics::pool_acquired(start.elapsed(), res.is_ok());
+        self.record_metrics();
+
+        res
     }
 
     pub(super) async fn connect(
@@ -321,6 +365,7 @@ impl<DB: Database> PoolInner<DB> {
                     let meta = PoolConnectionMetadata {
                         age: Duration::ZERO,
                         idle_for: Duration::ZERO,
+                        connect_count: 0,
                     };
 
                     let res = if let Some(callback) = &self.options.after_connect {
@@ -387,6 +432,49 @@ impl<DB: Database> PoolInner<DB> {
         Ok(())
     }
 
+    /// Concurrently open as many connections as are missing to reach `min_connections`,
+    /// collecting the errors instead of stopping at the first one.
+    pub(super) async fn warm_up(self: &Arc<Self>, deadline: Instant) -> WarmUpReport {
+        let mut guards = Vec::new();
+
+        while self.size() < self.options.min_connections {
+            // As in `try_min_connections()`, only use permits which are free right now.
+            let Some(permit) = self.semaphore.try_acquire(1) else {
+                break;
+            };
+
+            let Some(guard) = self.try_increment_size(permit).ok() else {
+                break;
+            };
+
+            guards.push(guard);
+        }
+
+        let results = future::join_all(
+            guards
+                .into_iter()
+                .map(|guard| self.connect(deadline, guard)),
+        )
+        .await;
+
+        let mut report = WarmUpReport {
+            opened: 0,
+            errors: Vec::new(),
+        };
+
+        for res in results {
+            match res {
+                Ok(conn) => {
+                    self.release(conn);
+                    report.opened += 1;
+                }
+                Err(error) => report.errors.push(error),
+            }
+        }
+
+        report
+    }
+
     /// Attempt to maintain `min_connections`, logging if unable.
     pub async fn min_connections_maintenance(self: &Arc<Self>, deadline: Option<Instant>) {
         let deadline = deadline.unwrap_or_else(|| {
@@ -442,7 +530,12 @@ async fn check_idle_conn<DB: Database>(
 
     if options.test_before_acquire {
         // Check that the connection is still live
-        if let Err(error) = conn.ping().await {
+        let res = match options.ping_timeout {
+            Some(timeout) => conn.ping_with_timeout(timeout).await,
+            None => conn.ping().await,
+        };
+
+        if let Err(error) = res {
             // an error here means the other end has hung up or we lost connectivity
             // either way we're fine to just discard the connection
             // the error itself here isn't necessarily unexpected so WARN is too strong
@@ -605,6 +698,8 @@ impl<DB: Database> Drop for DecrementSizeGuard<DB> {
 
             // and here we release the permit we got on construction
             self.pool.semaphore.release(1);
+
+            self.pool.record_metrics();
         }
     }
 }
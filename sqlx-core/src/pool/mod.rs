@@ -68,8 +68,9 @@ use crate::any::{Any, AnyKind};
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use event_listener::EventListener;
+use futures_core::future::BoxFuture;
 use futures_core::FusedFuture;
 use futures_util::FutureExt;
 use std::fmt;
@@ -90,7 +91,7 @@ mod inner;
 mod options;
 
 pub use self::connection::PoolConnection;
-pub use self::options::{PoolConnectionMetadata, PoolOptions};
+pub use self::options::{Fairness, PoolConnectionMetadata, PoolOptions, ReconnectPolicy};
 
 #[doc(hidden)]
 pub use self::maybe::MaybePoolConnection;
@@ -273,6 +274,32 @@ pub struct CloseEvent {
     listener: Option<EventListener>,
 }
 
+/// The outcome of [`Pool::warm_up()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct WarmUpReport {
+    /// The number of connections that were opened and added to the idle queue.
+    pub opened: u32,
+
+    /// The errors returned by the connection attempts that failed, one per connection.
+    pub errors: Vec<Error>,
+}
+
+impl WarmUpReport {
+    /// Returns `true` if every connection attempt succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the number of connections opened, or the first error if any attempt failed.
+    pub fn into_result(self) -> Result<u32, Error> {
+        match self.errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(self.opened),
+        }
+    }
+}
+
 impl<DB: Database> Pool<DB> {
     /// Create a new connection pool with a default pool configuration and
     /// the given connection URL, and immediately establish one connection.
@@ -366,6 +393,58 @@ impl<DB: Database> Pool<DB> {
         async move { shared.acquire().await.map(|conn| conn.reattach()) }
     }
 
+    /// Retrieves a connection from the pool, waiting at most `timeout` instead of
+    /// [`PoolOptions::acquire_timeout`].
+    ///
+    /// This allows bounding the wait for a connection per call, e.g. shorter for latency-sensitive
+    /// request handlers than for background jobs. If the timeout elapses, this will return
+    /// [`Error::PoolTimedOut`].
+    ///
+    /// See [`acquire`][Self::acquire] for the caveats about cancellation.
+    pub fn acquire_timeout(
+        &self,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.try_acquire_until(Instant::now() + timeout)
+    }
+
+    /// Retrieves a connection from the pool, waiting until `deadline` at the latest instead of
+    /// [`PoolOptions::acquire_timeout`].
+    ///
+    /// If the deadline passes, this will return [`Error::PoolTimedOut`], immediately if it
+    /// already passed when this is called.
+    ///
+    /// See [`acquire`][Self::acquire] for the caveats about cancellation.
+    pub fn try_acquire_until(
+        &self,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        let shared = self.0.clone();
+        async move {
+            shared
+                .acquire_until(deadline)
+                .await
+                .map(|conn| conn.reattach())
+        }
+    }
+
+    /// Open connections until the pool holds [`PoolOptions::min_connections`], instead of
+    /// waiting for the background task that maintains it.
+    ///
+    /// All missing connections are opened concurrently, and the attempts are bounded by
+    /// [`PoolOptions::acquire_timeout`]. Each attempt retries on the same errors as
+    /// [`acquire`][Self::acquire]; any other error, e.g. bad credentials, is collected in the
+    /// returned report rather than aborting the other attempts. This lets an application fail
+    /// fast on startup with [`WarmUpReport::into_result()`].
+    ///
+    /// Connections are only opened with permits that are free right now, so this returns early
+    /// if the pool is busy or closed. See also [`PoolOptions::warm_up_on_connect`].
+    pub async fn warm_up(&self) -> WarmUpReport {
+        self.0
+            .warm_up(Instant::now() + self.0.options.acquire_timeout)
+            .await
+    }
+
     /// Attempts to retrieve a connection from the pool if there is one available.
     ///
     /// Returns `None` immediately if there are no idle connections available in the pool
@@ -374,11 +453,86 @@ impl<DB: Database> Pool<DB> {
         self.0.try_acquire().map(|conn| conn.into_live().reattach())
     }
 
+    /// Runs `callback` with a live connection from the pool, reconnecting first if the
+    /// connection was lost, as configured by [`PoolOptions::reconnect_policy`].
+    ///
+    /// Before `callback` is started, the connection is checked with a ping. If acquiring or
+    /// checking it fails with [`Error::Io`], e.g. because the database server restarted, the
+    /// connection is discarded and another one is acquired after a backoff. New connections
+    /// are set up by [`PoolOptions::after_connect`], which restores the session state
+    /// configured there.
+    ///
+    /// `callback` itself runs at most once. If the connection is lost while it runs, the error
+    /// is returned, as the server may already have executed part of its work.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// let count: i64 = pool
+    ///     .with_reconnect(|conn| {
+    ///         Box::pin(async move {
+    ///             sqlx::query_scalar("SELECT COUNT(*) FROM jobs")
+    ///                 .fetch_one(conn)
+    ///                 .await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_reconnect<F, R>(&self, callback: F) -> Result<R, Error>
+    where
+        for<'c> F: FnOnce(&'c mut DB::Connection) -> BoxFuture<'c, Result<R, Error>>,
+    {
+        let policy = self.0.options.reconnect_policy;
+        let mut retries = 0;
+
+        loop {
+            let error = match self.acquire().await {
+                Ok(mut conn) => match conn.ping().await {
+                    Ok(()) => return callback(&mut *conn).await,
+                    Err(error) => {
+                        // the connection is unusable, don't return it to the pool
+                        drop(conn.detach());
+                        error
+                    }
+                },
+                Err(error) => error,
+            };
+
+            match error {
+                Error::Io(error) if matches!(policy, Some(policy) if retries < policy.max_retries) =>
+                {
+                    tracing::debug!(%error, retries, "connection lost, reconnecting");
+
+                    if let Some(policy) = policy {
+                        crate::rt::sleep(policy.delay(retries)).await;
+                    }
+
+                    retries += 1;
+                }
+
+                error => return Err(error),
+            }
+        }
+    }
+
     /// Retrieves a connection and immediately begins a new transaction.
     pub async fn begin(&self) -> Result<Transaction<'static, DB>, Error> {
         Ok(Transaction::begin(MaybePoolConnection::PoolConnection(self.acquire().await?)).await?)
     }
 
+    /// Retrieves a connection and immediately begins a new transaction with the given options.
+    pub async fn begin_with(
+        &self,
+        options: TransactionOptions,
+    ) -> Result<Transaction<'static, DB>, Error> {
+        Transaction::begin_with(
+            MaybePoolConnection::PoolConnection(self.acquire().await?),
+            options,
+        )
+        .await
+    }
+
     /// Attempts to retrieve a connection and immediately begins a new transaction if successful.
     pub async fn try_begin(&self) -> Result<Option<Transaction<'static, DB>>, Error> {
         match self.try_acquire() {
@@ -415,6 +569,23 @@ impl<DB: Database> Pool<DB> {
         self.0.close()
     }
 
+    /// Shut down the connection pool like [`.close()`][Pool::close], but wait at most `timeout`
+    /// for checked-out connections to be returned.
+    ///
+    /// New acquires fail with [`Error::PoolClosed`] immediately. Connections which are returned
+    /// before the timeout elapses are closed gracefully, giving in-flight queries the chance
+    /// to complete.
+    ///
+    /// If the timeout elapses first, any connections returned in the meantime are closed and
+    /// the number of connections still checked out is returned (and logged as a warning).
+    /// These connections are closed as soon as they are dropped, since the pool no longer
+    /// accepts them.
+    ///
+    /// Returns `0` if all connections were closed gracefully.
+    pub async fn close_with_timeout(&self, timeout: Duration) -> u32 {
+        self.0.close_with_timeout(timeout).await
+    }
+
     /// Returns `true` if [`.close()`][Pool::close] has been called on the pool, `false` otherwise.
     pub fn is_closed(&self) -> bool {
         self.0.is_closed()
@@ -4,6 +4,7 @@ use crate::error::Error;
 use crate::pool::inner::PoolInner;
 use crate::pool::Pool;
 use futures_core::future::BoxFuture;
+use std::cmp;
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -43,6 +44,7 @@ use std::time::{Duration, Instant};
 /// the perspectives of both API designer and consumer.
 pub struct PoolOptions<DB: Database> {
     pub(crate) test_before_acquire: bool,
+    pub(crate) ping_timeout: Option<Duration>,
     pub(crate) after_connect: Option<
         Arc<
             dyn Fn(&mut DB::Connection, PoolConnectionMetadata) -> BoxFuture<'_, Result<(), Error>>
@@ -76,9 +78,11 @@ pub struct PoolOptions<DB: Database> {
     pub(crate) max_connections: u32,
     pub(crate) acquire_timeout: Duration,
     pub(crate) min_connections: u32,
+    pub(crate) warm_up_on_connect: bool,
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
     pub(crate) fair: bool,
+    pub(crate) reconnect_policy: Option<ReconnectPolicy>,
 
     pub(crate) parent_pool: Option<Pool<DB>>,
 }
@@ -90,15 +94,18 @@ impl<DB: Database> Clone for PoolOptions<DB> {
     fn clone(&self) -> Self {
         PoolOptions {
             test_before_acquire: self.test_before_acquire,
+            ping_timeout: self.ping_timeout,
             after_connect: self.after_connect.clone(),
             before_acquire: self.before_acquire.clone(),
             after_release: self.after_release.clone(),
             max_connections: self.max_connections,
             acquire_timeout: self.acquire_timeout,
             min_connections: self.min_connections,
+            warm_up_on_connect: self.warm_up_on_connect,
             max_lifetime: self.max_lifetime,
             idle_timeout: self.idle_timeout,
             fair: self.fair,
+            reconnect_policy: self.reconnect_policy,
             parent_pool: self.parent_pool.as_ref().map(Pool::clone),
         }
     }
@@ -118,6 +125,78 @@ pub struct PoolConnectionMetadata {
     /// Only relevant for [`before_acquire`][PoolOptions::before_acquire].
     /// For other callbacks, this is [`Duration::ZERO`].
     pub idle_for: Duration,
+
+    /// The number of times the connection was handed out by the pool, e.g. to close connections
+    /// after a number of uses in [`before_acquire`][PoolOptions::before_acquire].
+    ///
+    /// For [`after_connect`][PoolOptions::after_connect], this is `0`. For
+    /// [`after_release`][PoolOptions::after_release], this includes the use that just ended.
+    pub connect_count: u64,
+}
+
+/// How [`Pool::with_reconnect`] reconnects after a connection was lost.
+///
+/// The delay before reconnecting starts at [`backoff`][Self::backoff] and doubles for each
+/// following attempt, up to [`max_backoff`][Self::max_backoff].
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectPolicy {
+    pub(crate) max_retries: u32,
+    pub(crate) backoff: Duration,
+    pub(crate) max_backoff: Duration,
+}
+
+impl ReconnectPolicy {
+    /// Reconnect at most `max_retries` times.
+    ///
+    /// The delay before the first reconnect defaults to 100 milliseconds, and is at most
+    /// 10 seconds.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+
+    /// Set the delay before the first reconnect.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the maximum delay before a reconnect.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Get the maximum number of retries.
+    pub fn get_max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        cmp::min(
+            self.backoff.saturating_mul(2_u32.saturating_pow(retry)),
+            self.max_backoff,
+        )
+    }
+}
+
+/// The order in which tasks waiting in [`Pool::acquire`] are handed connections.
+///
+/// See [`PoolOptions::fairness`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Fairness {
+    /// Waiters are served first-come-first-serve.
+    #[default]
+    Fifo,
+
+    /// Waiters are not queued in any particular order, and "drive-by" tasks that arrive while
+    /// a connection is available may take it ahead of tasks that have been waiting.
+    ///
+    /// Not supported by the Tokio runtime, which only has fair permits.
+    Unfair,
 }
 
 impl<DB: Database> Default for PoolOptions<DB> {
@@ -140,13 +219,16 @@ impl<DB: Database> PoolOptions<DB> {
             before_acquire: None,
             after_release: None,
             test_before_acquire: true,
+            ping_timeout: None,
             // A production application will want to set a higher limit than this.
             max_connections: 10,
             min_connections: 0,
+            warm_up_on_connect: false,
             acquire_timeout: Duration::from_secs(30),
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
             fair: true,
+            reconnect_policy: None,
             parent_pool: None,
         }
     }
@@ -198,6 +280,26 @@ impl<DB: Database> PoolOptions<DB> {
         self.min_connections
     }
 
+    /// If set to `true`, [`connect()`][Self::connect] and [`connect_with()`][Self::connect_with]
+    /// open all [`min_connections`][Self::min_connections] concurrently with
+    /// [`Pool::warm_up()`], and fail if any of them can't be opened.
+    ///
+    /// Otherwise, the connections are opened one after the other, stopping at the first error.
+    ///
+    /// This has no effect on [`connect_lazy()`][Self::connect_lazy], whose connections are
+    /// opened in the background.
+    ///
+    /// Defaults to `false`.
+    pub fn warm_up_on_connect(mut self, warm_up: bool) -> Self {
+        self.warm_up_on_connect = warm_up;
+        self
+    }
+
+    /// Get whether `min_connections` are opened concurrently when the pool is created.
+    pub fn get_warm_up_on_connect(&self) -> bool {
+        self.warm_up_on_connect
+    }
+
     /// Set the maximum amount of time to spend waiting for a connection in [`Pool::acquire()`].
     ///
     /// Caps the total amount of time `Pool::acquire()` can spend waiting across multiple phases:
@@ -274,6 +376,68 @@ impl<DB: Database> PoolOptions<DB> {
         self.test_before_acquire
     }
 
+    /// Set the maximum time to wait for the database to respond to the ping of
+    /// [`test_before_acquire`][Self::test_before_acquire], see [`Connection::ping_with_timeout`].
+    ///
+    /// A connection which fails the check is closed and `acquire()` transparently continues
+    /// with another idle connection or a new one, within the
+    /// [`acquire_timeout`][Self::acquire_timeout]. Setting a short timeout avoids waiting for
+    /// connections which died silently, e.g. because a load balancer dropped them while idle.
+    ///
+    /// Defaults to `None`, which bounds the ping only by the `acquire_timeout`.
+    pub fn ping_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.ping_timeout = timeout.into();
+        self
+    }
+
+    /// Get the maximum time to wait for the ping of `test_before_acquire`.
+    pub fn get_ping_timeout(&self) -> Option<Duration> {
+        self.ping_timeout
+    }
+
+    /// Set how [`Pool::with_reconnect`] reconnects if a connection was lost before the
+    /// operation started, e.g. because the database server restarted.
+    ///
+    /// New connections are set up by [`after_connect`][Self::after_connect] as usual, so
+    /// session state configured there is restored before the operation runs. An operation
+    /// that already started is never run again.
+    ///
+    /// Defaults to `None`, which doesn't reconnect.
+    pub fn reconnect_policy(mut self, policy: impl Into<Option<ReconnectPolicy>>) -> Self {
+        self.reconnect_policy = policy.into();
+        self
+    }
+
+    /// Get how [`Pool::with_reconnect`] reconnects if a connection was lost.
+    pub fn get_reconnect_policy(&self) -> Option<ReconnectPolicy> {
+        self.reconnect_policy
+    }
+
+    /// Set the order in which tasks waiting for a connection are served.
+    ///
+    /// Defaults to [`Fairness::Fifo`], which keeps tail latencies predictable under high
+    /// contention: tasks at the head of the waiter queue can't be repeatedly preempted by
+    /// "drive-by" tasks, which would leave tasks further back timing out.
+    ///
+    /// [`Fairness::Unfair`] may slightly reduce the time to `acquire()` at low contention.
+    ///
+    /// ### Panics
+    /// Creating a pool with [`Fairness::Unfair`] panics in debug builds when the Tokio runtime
+    /// is enabled, as Tokio only supports fair permits.
+    pub fn fairness(mut self, fairness: Fairness) -> Self {
+        self.fair = fairness == Fairness::Fifo;
+        self
+    }
+
+    /// Get the order in which tasks waiting for a connection are served.
+    pub fn get_fairness(&self) -> Fairness {
+        if self.fair {
+            Fairness::Fifo
+        } else {
+            Fairness::Unfair
+        }
+    }
+
     /// If set to `true`, calls to `acquire()` are fair and connections  are issued
     /// in first-come-first-serve order. If `false`, "drive-by" tasks may steal idle connections
     /// ahead of tasks that have been waiting.
@@ -284,8 +448,7 @@ impl<DB: Database> PoolOptions<DB> {
     /// these "drive-by" tasks and tasks further back in the queue timing out because
     /// the queue isn't moving.
     ///
-    /// Currently only exposed for benchmarking; `fair = true` seems to be the superior option
-    /// in most cases.
+    /// Equivalent to [`fairness`][Self::fairness]; kept for benchmarking.
     #[doc(hidden)]
     pub fn __fair(mut self, fair: bool) -> Self {
         self.fair = fair;
@@ -459,7 +622,7 @@ impl<DB: Database> PoolOptions<DB> {
     ///
     /// ### Panics
     /// If `self.max_connections` is greater than the setting the given pool was created with,
-    /// or `self.fair` differs from the setting the given pool was created with.
+    /// or `self.fairness` differs from the setting the given pool was created with.
     #[doc(hidden)]
     pub fn parent(mut self, pool: Pool<DB>) -> Self {
         self.parent_pool = Some(pool);
@@ -496,7 +659,13 @@ impl<DB: Database> PoolOptions<DB> {
 
         let inner = PoolInner::new_arc(self, options);
 
-        if inner.options.min_connections > 0 {
+        if inner.options.warm_up_on_connect {
+            if let Err(error) = inner.warm_up(deadline).await.into_result() {
+                // Don't leave the connections that were opened to the reaper.
+                inner.close().await;
+                return Err(error);
+            }
+        } else if inner.options.min_connections > 0 {
             // If the idle reaper is spawned then this will race with the call from that task
             // and may not report any connection errors.
             inner.try_min_connections(deadline).await?;
@@ -540,10 +709,14 @@ impl<DB: Database> Debug for PoolOptions<DB> {
         f.debug_struct("PoolOptions")
             .field("max_connections", &self.max_connections)
             .field("min_connections", &self.min_connections)
+            .field("warm_up_on_connect", &self.warm_up_on_connect)
             .field("connect_timeout", &self.acquire_timeout)
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field("ping_timeout", &self.ping_timeout)
+            .field("fairness", &self.get_fairness())
+            .field("reconnect_policy", &self.reconnect_policy)
             .finish()
     }
 }
@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use either::Either;
 use futures_core::stream::BoxStream;
@@ -7,9 +8,10 @@ use futures_util::{future, StreamExt, TryFutureExt, TryStreamExt};
 use crate::arguments::{Arguments, IntoArguments};
 use crate::database::{Database, HasArguments, HasStatement, HasStatementCache};
 use crate::encode::Encode;
-use crate::error::Error;
+use crate::error::{mismatched_types, Error};
 use crate::executor::{Execute, Executor};
 use crate::statement::Statement;
+use crate::type_info::TypeInfo;
 use crate::types::Type;
 
 /// Raw SQL query with bind parameters. Returned by [`query`][crate::query::query].
@@ -17,8 +19,11 @@ use crate::types::Type;
 pub struct Query<'q, DB: Database, A> {
     pub(crate) statement: Either<&'q str, &'q <DB as HasStatement<'q>>::Statement>,
     pub(crate) arguments: Option<A>,
+    /// The number of values bound so far, unknown if the arguments were given up front.
+    pub(crate) bound: Option<usize>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) timeout: Option<Duration>,
 }
 
 /// SQL query that will map its results to owned Rust types.
@@ -65,6 +70,11 @@ where
     fn persistent(&self) -> bool {
         self.persistent
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
 }
 
 impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
@@ -79,10 +89,78 @@ impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
     pub fn bind<T: 'q + Send + Encode<'q, DB> + Type<DB>>(mut self, value: T) -> Self {
         if let Some(arguments) = &mut self.arguments {
             arguments.add(value);
+            self.bound = self.bound.map(|bound| bound + 1);
         }
 
         self
     }
+
+    /// Bind a value for use with this SQL query, after checking that it is compatible with the
+    /// type of the parameter it is bound to.
+    ///
+    /// This returns [`Error::Encode`] instead of an error from the database, or an implicit
+    /// conversion, when a value of the wrong type is bound. It also fails if there is no
+    /// parameter left to bind the value to.
+    ///
+    /// The parameters can only be checked if the query was created from a prepared
+    /// [`Statement`] (and not given its arguments up front), and only as far as the driver
+    /// reports them: PostgreSQL reports their types, SQLite only their number. Otherwise, this
+    /// is equivalent to [`bind()`](Self::bind).
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::{Executor, Statement};
+    ///
+    /// let statement = conn.prepare("SELECT * FROM users WHERE id = $1").await?;
+    ///
+    /// // fails before the query is sent if `id` is not a BIGINT
+    /// let user = statement.query().bind_checked(1_i64)?.fetch_optional(conn).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind_checked<T: 'q + Send + Encode<'q, DB> + Type<DB>>(
+        self,
+        value: T,
+    ) -> Result<Self, Error> {
+        if let (Either::Right(statement), Some(index)) = (self.statement, self.bound) {
+            let len = match statement.parameters() {
+                Some(Either::Left(parameters)) => {
+                    if let Some(ty) = parameters.get(index) {
+                        if !ty.is_null() && !T::compatible(ty) {
+                            return Err(Error::Encode(
+                                format!(
+                                    "parameter {}: {}",
+                                    index + 1,
+                                    mismatched_types::<DB, T>(ty)
+                                )
+                                .into(),
+                            ));
+                        }
+                    }
+
+                    Some(parameters.len())
+                }
+
+                Some(Either::Right(len)) => Some(len),
+
+                None => None,
+            };
+
+            if let Some(len) = len.filter(|len| index >= *len) {
+                return Err(Error::Encode(
+                    format!("too many values bound: expected {}, got {}", len, index + 1).into(),
+                ));
+            }
+        }
+
+        Ok(self.bind(value))
+    }
+
+    /// Mutable access to the arguments of this query, used by database-specific extensions.
+    #[doc(hidden)]
+    pub fn arguments_mut(&mut self) -> Option<&mut <DB as HasArguments<'q>>::Arguments> {
+        self.arguments.as_mut()
+    }
 }
 
 impl<'q, DB, A> Query<'q, DB, A>
@@ -103,6 +181,33 @@ where
     }
 }
 
+impl<'q, DB: Database, A> Query<'q, DB, A> {
+    /// Sets how long the statement may run before the database is asked to cancel it. The
+    /// statement then fails with an error.
+    ///
+    /// Unlike dropping the future of the query (e.g. with a timeout around it), this stops the
+    /// work in the database:
+    ///
+    /// * Postgres sends a cancel request on a separate connection.
+    /// * MySQL interrupts the statement with `KILL QUERY` on a separate connection, see
+    ///   `MySqlConnection::cancel_handle()`. This overrides the `query_timeout` of the connection.
+    /// * SQLite interrupts the statement, which fails with [`Error::Timeout`]. This overrides the
+    ///   `statement_timeout` of the connection.
+    ///
+    /// The timeout is enforced by the client, not with a limit enforced by the server such as
+    /// `statement_timeout` of Postgres or the `MAX_EXECUTION_TIME` hint of MySQL. Cancelling a
+    /// statement on Postgres and MySQL opens a new connection, which costs a full handshake and
+    /// fails if the server does not accept more connections.
+    ///
+    /// The `Any` driver ignores the timeout.
+    ///
+    /// Default: `None`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
 impl<'q, DB, A: Send> Query<'q, DB, A>
 where
     DB: Database,
@@ -178,6 +283,22 @@ where
         executor.fetch(self)
     }
 
+    /// Execute the query and return the generated results as a stream of batches of at most
+    /// `batch_size` rows, see [`Executor::fetch_chunked`].
+    #[inline]
+    pub fn fetch_chunked<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        batch_size: u32,
+    ) -> BoxStream<'e, Result<Vec<DB::Row>, Error>>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        executor.fetch_chunked(self, batch_size)
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     #[inline]
@@ -251,6 +372,21 @@ where
     fn persistent(&self) -> bool {
         self.inner.arguments.is_some()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
+    }
+}
+
+impl<'q, DB: Database, F, A> Map<'q, DB, F, A> {
+    /// Sets how long the statement may run before the database is asked to cancel it.
+    ///
+    /// See [`Query::timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
 }
 
 impl<'q, DB, F, O, A> Map<'q, DB, F, A>
@@ -404,8 +540,10 @@ where
     Query {
         database: PhantomData,
         arguments: Some(Default::default()),
+        bound: Some(0),
         statement: Either::Right(statement),
         persistent: true,
+        timeout: None,
     }
 }
 
@@ -421,8 +559,10 @@ where
     Query {
         database: PhantomData,
         arguments: Some(arguments),
+        bound: None,
         statement: Either::Right(statement),
         persistent: true,
+        timeout: None,
     }
 }
 
@@ -434,8 +574,10 @@ where
     Query {
         database: PhantomData,
         arguments: Some(Default::default()),
+        bound: Some(0),
         statement: Either::Left(sql),
         persistent: true,
+        timeout: None,
     }
 }
 
@@ -448,7 +590,9 @@ where
     Query {
         database: PhantomData,
         arguments: Some(arguments),
+        bound: None,
         statement: Either::Left(sql),
         persistent: true,
+        timeout: None,
     }
 }
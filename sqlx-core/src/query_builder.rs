@@ -1,12 +1,16 @@
 //! Runtime query-builder API.
 
+use std::cmp;
 use std::fmt::Display;
 use std::fmt::Write;
 use std::marker::PhantomData;
 
+use crate::acquire::Acquire;
 use crate::arguments::{Arguments, IntoArguments};
 use crate::database::{Database, HasArguments};
 use crate::encode::Encode;
+use crate::error::Error;
+use crate::executor::Executor;
 use crate::from_row::FromRow;
 use crate::query::Query;
 use crate::query_as::QueryAs;
@@ -28,6 +32,8 @@ where
     query: String,
     init_len: usize,
     arguments: Option<<DB as HasArguments<'args>>::Arguments>,
+    // the number of values bound since `new()` or the last `reset()`
+    num_binds: usize,
 }
 
 impl<'args, DB: Database> Default for QueryBuilder<'args, DB> {
@@ -36,6 +42,7 @@ impl<'args, DB: Database> Default for QueryBuilder<'args, DB> {
             init_len: 0,
             query: String::default(),
             arguments: Some(Default::default()),
+            num_binds: 0,
         }
     }
 }
@@ -57,6 +64,7 @@ where
             init_len: init.len(),
             query: init,
             arguments: Some(Default::default()),
+            num_binds: 0,
         }
     }
 
@@ -75,6 +83,7 @@ where
             init_len: init.len(),
             query: init,
             arguments: Some(arguments.into_arguments()),
+            num_binds: 0,
         }
     }
 
@@ -161,6 +170,8 @@ where
             .format_placeholder(&mut self.query)
             .expect("error in format_placeholder");
 
+        self.num_binds += 1;
+
         self
     }
 
@@ -326,6 +337,94 @@ where
         separated.query_builder
     }
 
+    /// Execute a bulk `INSERT` of `tuples`, split into as many statements as needed to stay within
+    /// the bind parameter limit of the database.
+    ///
+    /// Each statement is the initial fragment passed to [`new()`][Self::new] followed by a
+    /// `VALUES` clause built like [`.push_values()`][Self::push_values]. The number of tuples per
+    /// statement is [`Database::MAX_BIND_PARAMETERS`] divided by the number of values bound for
+    /// the first tuple. All statements are executed in one transaction, so either all or none
+    /// of `tuples` are inserted, and the combined result is returned.
+    ///
+    /// The builder is [reset][Self::reset] before each statement, so anything pushed after
+    /// `new()` is discarded, and it is left reset when this returns.
+    ///
+    /// ### Example (Postgres)
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: &sqlx::PgPool) -> sqlx::Result<()> {
+    /// use sqlx::{Postgres, QueryBuilder};
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> =
+    ///     QueryBuilder::new("INSERT INTO users(id, username) ");
+    ///
+    /// // 100,000 tuples would need 200,000 parameters in a single statement
+    /// let result = query_builder
+    ///     .push_values_with(pool, 0..100_000, |mut b, id| {
+    ///         b.push_bind(id).push_bind(format!("test_user_{id}"));
+    ///     })
+    ///     .await?;
+    ///
+    /// assert_eq!(result.rows_affected(), 100_000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn push_values_with<'c, A, I, F>(
+        &mut self,
+        conn: A,
+        tuples: I,
+        mut push_tuple: F,
+    ) -> Result<DB::QueryResult, Error>
+    where
+        A: Acquire<'c, Database = DB>,
+        I: IntoIterator,
+        F: FnMut(Separated<'_, 'args, DB, &'static str>, I::Item),
+        for<'q> <DB as HasArguments<'args>>::Arguments: IntoArguments<'q, DB>,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+    {
+        let mut tuples = tuples.into_iter();
+        let mut next = tuples.next();
+        let mut tuples_per_query = None;
+
+        let mut tx = conn.begin().await?;
+        let mut result = DB::QueryResult::default();
+
+        while next.is_some() {
+            self.reset();
+            self.push("VALUES ");
+
+            let mut num_tuples = 0;
+
+            while let Some(tuple) = next.take() {
+                if num_tuples > 0 {
+                    self.push(", ");
+                }
+
+                self.push("(");
+                push_tuple(self.separated(", "), tuple);
+                self.push(")");
+
+                num_tuples += 1;
+                next = tuples.next();
+
+                let max = *tuples_per_query.get_or_insert_with(|| {
+                    cmp::max(DB::MAX_BIND_PARAMETERS / cmp::max(self.num_binds, 1), 1)
+                });
+
+                if num_tuples >= max {
+                    break;
+                }
+            }
+
+            result.extend(Some(self.build().execute(&mut *tx).await?));
+        }
+
+        self.reset();
+        tx.commit().await?;
+
+        Ok(result)
+    }
+
     /// Creates `((a, b), (..)` statements, from `tuples`.
     ///
     /// This can be used to construct a bulk `SELECT` statement like this:
@@ -451,8 +550,10 @@ where
         Query {
             statement: Either::Left(&self.query),
             arguments: self.arguments.take(),
+            bound: Some(self.num_binds),
             database: PhantomData,
             persistent: true,
+            timeout: None,
         }
     }
 
@@ -508,6 +609,7 @@ where
     pub fn reset(&mut self) -> &mut Self {
         self.query.truncate(self.init_len);
         self.arguments = Some(Default::default());
+        self.num_binds = 0;
 
         self
     }
@@ -0,0 +1,192 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use futures_channel::{mpsc, oneshot};
+use futures_core::future::BoxFuture;
+use futures_util::StreamExt;
+
+use crate::connection::Connection;
+use crate::database::Database;
+use crate::error::Error;
+
+/// A single connection that can be shared by many tasks without a [`Pool`].
+///
+/// The connection is owned by a background task; calls to [`run()`][Self::run] are queued and
+/// executed against it one at a time, in the order they were made. This suits applications with
+/// little database traffic, where a pool would mostly hold idle connections.
+///
+/// Cloning a `SharedConnection` is cheap and yields another handle to the same connection.
+/// The connection is closed when [`close()`][Self::close] is called or all handles are dropped.
+///
+/// Because every call waits for the ones queued before it, a long-running call delays all others.
+/// The time each call spends queued is reported through [`metrics`](crate::metrics).
+///
+/// ```rust,no_run
+/// # async fn example(conn: sqlx::postgres::PgConnection) -> sqlx::Result<()> {
+/// use futures_core::future::BoxFuture;
+/// use sqlx::postgres::{PgConnection, Postgres};
+/// use sqlx::SharedConnection;
+///
+/// let shared = SharedConnection::<Postgres>::new(conn);
+///
+/// // the closure must be annotated so that it borrows the connection for any lifetime
+/// let count: i64 = shared
+///     .run(|conn: &mut PgConnection| -> BoxFuture<'_, sqlx::Result<i64>> {
+///         Box::pin(async move {
+///             sqlx::query_scalar("SELECT COUNT(*) FROM users")
+///                 .fetch_one(conn)
+///                 .await
+///         })
+///     })
+///     .await??;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Pool`]: crate::pool::Pool
+pub struct SharedConnection<DB: Database> {
+    command_tx: mpsc::UnboundedSender<Command<DB>>,
+    queued: Arc<AtomicUsize>,
+}
+
+type Job<DB> = Box<
+    dyn for<'c> FnOnce(&'c mut <DB as Database>::Connection) -> BoxFuture<'c, ()> + Send + 'static,
+>;
+
+enum Command<DB: Database> {
+    Run {
+        job: Job<DB>,
+        queued_at: Instant,
+    },
+    Close {
+        tx: oneshot::Sender<Result<(), Error>>,
+    },
+}
+
+impl<DB: Database> SharedConnection<DB> {
+    /// Share an established connection.
+    ///
+    /// This spawns the background task which owns the connection, so it must be called
+    /// from within an async runtime.
+    pub fn new(conn: DB::Connection) -> Self {
+        let (command_tx, command_rx) = mpsc::unbounded();
+        let queued = Arc::new(AtomicUsize::new(0));
+
+        crate::rt::spawn(worker::<DB>(conn, command_rx, Arc::clone(&queued)));
+
+        Self { command_tx, queued }
+    }
+
+    /// Establish a new connection from a URL and share it.
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        Ok(Self::new(DB::Connection::connect(url).await?))
+    }
+
+    /// Run `f` with exclusive access to the connection once all previously queued calls
+    /// have finished.
+    ///
+    /// Returns [`Error::WorkerCrashed`] if the connection was closed before `f` could run,
+    /// or if a previous call panicked.
+    pub async fn run<F, R>(&self, f: F) -> Result<R, Error>
+    where
+        F: for<'c> FnOnce(&'c mut DB::Connection) -> BoxFuture<'c, R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+
+        let job = job::<DB, _>(move |conn| {
+            Box::pin(async move {
+                // the caller may have stopped waiting; the result is dropped in that case
+                let _ = tx.send(f(conn).await);
+            })
+        });
+
+        self.queued.fetch_add(1, Ordering::AcqRel);
+
+        if self
+            .command_tx
+            .unbounded_send(Command::Run {
+                job,
+                queued_at: Instant::now(),
+            })
+            .is_err()
+        {
+            self.queued.fetch_sub(1, Ordering::AcqRel);
+            return Err(Error::WorkerCrashed);
+        }
+
+        rx.await.map_err(|_| Error::WorkerCrashed)
+    }
+
+    /// The number of calls to [`run()`][Self::run] waiting for the connection.
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Acquire)
+    }
+
+    /// Close the connection once all previously queued calls have finished.
+    ///
+    /// Calls made afterwards through other handles fail with [`Error::WorkerCrashed`].
+    pub async fn close(self) -> Result<(), Error> {
+        let (tx, rx) = oneshot::channel();
+
+        self.command_tx
+            .unbounded_send(Command::Close { tx })
+            .map_err(|_| Error::WorkerCrashed)?;
+
+        rx.await.map_err(|_| Error::WorkerCrashed)?
+    }
+}
+
+// pins down the higher-ranked signature of the closure in `run()`
+fn job<DB, F>(f: F) -> Job<DB>
+where
+    DB: Database,
+    F: for<'c> FnOnce(&'c mut DB::Connection) -> BoxFuture<'c, ()> + Send + 'static,
+{
+    Box::new(f)
+}
+
+async fn worker<DB: Database>(
+    mut conn: DB::Connection,
+    mut command_rx: mpsc::UnboundedReceiver<Command<DB>>,
+    queued: Arc<AtomicUsize>,
+) {
+    while let Some(command) = command_rx.next().await {
+        match command {
+            Command::Run { job, queued_at } => {
+                let queued = queued.fetch_sub(1, Ordering::AcqRel) - 1;
+                crate::metrics::shared_connection_dequeued(queued_at.elapsed(), queued);
+
+                job(&mut conn).await;
+            }
+
+            Command::Close { tx } => {
+                let _ = tx.send(conn.close().await);
+                return;
+            }
+        }
+    }
+
+    // every handle was dropped without calling `close()`
+    let _ = conn.close().await;
+}
+
+impl<DB: Database> Clone for SharedConnection<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            command_tx: self.command_tx.clone(),
+            queued: Arc::clone(&self.queued),
+        }
+    }
+}
+
+impl<DB: Database> Debug for SharedConnection<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedConnection")
+            .field("queued", &self.queued())
+            .field("closed", &self.command_tx.is_closed())
+            .finish()
+    }
+}
@@ -1,4 +1,8 @@
+use crate::database::HasArguments;
 use crate::encode::{Encode, IsNull};
+use crate::query::Query;
+use crate::query_as::QueryAs;
+use crate::query_scalar::QueryScalar;
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo};
 pub(crate) use sqlx_core::arguments::*;
@@ -9,6 +13,15 @@ pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: Vec<u8>,
+    pub(crate) attributes: Vec<MySqlQueryAttribute>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MySqlQueryAttribute {
+    pub(crate) name: String,
+    pub(crate) type_info: MySqlTypeInfo,
+    // `None` if the value is NULL
+    pub(crate) value: Option<Vec<u8>>,
 }
 
 impl MySqlArguments {
@@ -27,6 +40,30 @@ impl MySqlArguments {
         }
     }
 
+    /// Attach a query attribute to the statement, which is available on the server through
+    /// `mysql_query_attribute_string()` (e.g. for audit plugins).
+    ///
+    /// Query attributes require MySQL 8.0.23 or newer and are ignored by older servers
+    /// and MariaDB.
+    pub fn add_attribute<'q, T>(&mut self, name: &str, value: T)
+    where
+        T: Encode<'q, MySql> + Type<MySql>,
+    {
+        let type_info = value.produces().unwrap_or_else(T::type_info);
+        let mut buf = Vec::new();
+
+        let value = match value.encode(&mut buf) {
+            IsNull::No => Some(buf),
+            IsNull::Yes => None,
+        };
+
+        self.attributes.push(MySqlQueryAttribute {
+            name: name.to_owned(),
+            type_info,
+            value,
+        });
+    }
+
     #[doc(hidden)]
     pub fn len(&self) -> usize {
         self.types.len()
@@ -48,3 +85,72 @@ impl<'q> Arguments<'q> for MySqlArguments {
         self.add(value)
     }
 }
+
+/// Extension trait to attach [query attributes] to a MySQL query.
+///
+/// Query attributes are key/value metadata sent along with a statement that do not affect
+/// its result, such as trace ids for audit plugins. They require MySQL 8.0.23 or newer and are
+/// ignored by older servers and MariaDB.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mysql::MySqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mysql::MySqlQueryAttributes;
+///
+/// sqlx::query("DELETE FROM sessions WHERE expires_at < NOW()")
+///     .attribute("trace_id", "3f2c1a")
+///     .execute(conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [query attributes]: https://dev.mysql.com/doc/refman/8.0/en/query-attributes.html
+pub trait MySqlQueryAttributes<'q>: Sized {
+    /// Attach a query attribute with the given name and value.
+    fn attribute<T>(self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, MySql> + Type<MySql>;
+}
+
+impl<'q> MySqlQueryAttributes<'q> for Query<'q, MySql, <MySql as HasArguments<'q>>::Arguments> {
+    fn attribute<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        if let Some(arguments) = self.arguments_mut() {
+            arguments.add_attribute(name, value);
+        }
+
+        self
+    }
+}
+
+impl<'q, O> MySqlQueryAttributes<'q>
+    for QueryAs<'q, MySql, O, <MySql as HasArguments<'q>>::Arguments>
+{
+    fn attribute<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        if let Some(arguments) = self.arguments_mut() {
+            arguments.add_attribute(name, value);
+        }
+
+        self
+    }
+}
+
+impl<'q, O> MySqlQueryAttributes<'q>
+    for QueryScalar<'q, MySql, O, <MySql as HasArguments<'q>>::Arguments>
+{
+    fn attribute<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        if let Some(arguments) = self.arguments_mut() {
+            arguments.add_attribute(name, value);
+        }
+
+        self
+    }
+}
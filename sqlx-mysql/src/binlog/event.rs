@@ -0,0 +1,470 @@
+use std::sync::Arc;
+
+use bytes::{Buf, Bytes};
+use crc::{Crc, CRC_32_ISO_HDLC};
+
+use crate::binlog::value::{column_type, MySqlBinlogValue};
+use crate::error::Error;
+use crate::io::{BufExt, MySqlBufExt};
+use crate::HashMap;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/binlog__event_8h.html
+const QUERY_EVENT: u8 = 0x02;
+const ROTATE_EVENT: u8 = 0x04;
+const XID_EVENT: u8 = 0x10;
+const TABLE_MAP_EVENT: u8 = 0x13;
+const WRITE_ROWS_EVENT_V1: u8 = 0x17;
+const UPDATE_ROWS_EVENT_V1: u8 = 0x18;
+const DELETE_ROWS_EVENT_V1: u8 = 0x19;
+const HEARTBEAT_EVENT: u8 = 0x1b;
+const WRITE_ROWS_EVENT: u8 = 0x1e;
+const UPDATE_ROWS_EVENT: u8 = 0x1f;
+const DELETE_ROWS_EVENT: u8 = 0x20;
+const GTID_EVENT: u8 = 0x21;
+
+const EVENT_HEADER_LEN: usize = 19;
+
+const CRC_32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+/// An event read from the binary log of the server by a
+/// [`MySqlBinlogStream`](crate::MySqlBinlogStream).
+#[derive(Debug, Clone)]
+pub struct MySqlBinlogEvent {
+    timestamp: u32,
+    server_id: u32,
+    log_position: u32,
+    data: MySqlBinlogEventData,
+}
+
+impl MySqlBinlogEvent {
+    /// The time the statement which caused this event started executing, in seconds since the
+    /// Unix epoch.
+    pub fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    /// The id of the server that originally wrote this event.
+    pub fn server_id(&self) -> u32 {
+        self.server_id
+    }
+
+    /// The position of the next event in the current binlog file.
+    pub fn log_position(&self) -> u32 {
+        self.log_position
+    }
+
+    pub fn data(&self) -> &MySqlBinlogEventData {
+        &self.data
+    }
+
+    pub fn into_data(self) -> MySqlBinlogEventData {
+        self.data
+    }
+}
+
+/// The decoded payload of a [`MySqlBinlogEvent`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum MySqlBinlogEventData {
+    /// The server continues with the next binlog file.
+    Rotate { file: String, position: u64 },
+
+    /// The start of a transaction, identified by its GTID (`source_uuid:transaction_id`).
+    Gtid { gtid: String },
+
+    /// A statement logged in statement format, including DDL and `BEGIN`.
+    Query { schema: String, query: String },
+
+    /// The commit of a transaction.
+    Xid { xid: u64 },
+
+    /// The definition of a table used by the rows events that follow.
+    TableMap(Arc<MySqlBinlogTable>),
+
+    /// Rows inserted, updated or deleted by a statement.
+    Rows(MySqlBinlogRows),
+
+    /// Sent by the server when there were no events for the heartbeat period.
+    Heartbeat,
+
+    /// Any other event, identified by its type code.
+    Other { event_type: u8 },
+}
+
+/// A table as described by a `TABLE_MAP_EVENT`.
+#[derive(Debug)]
+pub struct MySqlBinlogTable {
+    id: u64,
+    schema: String,
+    name: String,
+    column_types: Vec<u8>,
+    column_meta: Vec<u16>,
+}
+
+impl MySqlBinlogTable {
+    /// The id of the table, which is only stable for as long as the table definition is cached
+    /// by the server.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The number of columns in the table.
+    pub fn columns(&self) -> usize {
+        self.column_types.len()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MySqlBinlogRowsKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// The rows changed by a single statement.
+#[derive(Debug, Clone)]
+pub struct MySqlBinlogRows {
+    kind: MySqlBinlogRowsKind,
+    table: Arc<MySqlBinlogTable>,
+    rows: Vec<MySqlBinlogRow>,
+}
+
+impl MySqlBinlogRows {
+    pub fn kind(&self) -> MySqlBinlogRowsKind {
+        self.kind
+    }
+
+    pub fn table(&self) -> &MySqlBinlogTable {
+        &self.table
+    }
+
+    pub fn rows(&self) -> &[MySqlBinlogRow] {
+        &self.rows
+    }
+}
+
+/// The images of a single changed row.
+///
+/// Each image has a value for every column of the table in order. A column which is not part of
+/// an image (depending on `binlog_row_image`) is `None`.
+#[derive(Debug, Clone)]
+pub struct MySqlBinlogRow {
+    before: Option<Vec<Option<MySqlBinlogValue>>>,
+    after: Option<Vec<Option<MySqlBinlogValue>>>,
+}
+
+impl MySqlBinlogRow {
+    /// The row before the change, for updates and deletes.
+    pub fn before(&self) -> Option<&[Option<MySqlBinlogValue>]> {
+        self.before.as_deref()
+    }
+
+    /// The row after the change, for inserts and updates.
+    pub fn after(&self) -> Option<&[Option<MySqlBinlogValue>]> {
+        self.after.as_deref()
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct EventDecoder {
+    // whether events are followed by a CRC32 checksum
+    pub(super) checksum: bool,
+
+    // tables of the TABLE_MAP_EVENTs seen so far by their id
+    tables: HashMap<u64, Arc<MySqlBinlogTable>>,
+}
+
+impl EventDecoder {
+    pub(super) fn decode(&mut self, mut buf: Bytes) -> Result<MySqlBinlogEvent, Error> {
+        if self.checksum {
+            if buf.len() < EVENT_HEADER_LEN + 4 {
+                return Err(err_protocol!("binlog event too short: {} bytes", buf.len()));
+            }
+
+            let checksum = buf.split_off(buf.len() - 4).get_u32_le();
+
+            if CRC_32.checksum(&buf) != checksum {
+                return Err(err_protocol!("binlog event checksum mismatch"));
+            }
+        }
+
+        if buf.len() < EVENT_HEADER_LEN {
+            return Err(err_protocol!("binlog event too short: {} bytes", buf.len()));
+        }
+
+        // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_replication_binlog_event.html#sect_protocol_replication_binlog_event_header
+        let timestamp = buf.get_u32_le();
+        let event_type = buf.get_u8();
+        let server_id = buf.get_u32_le();
+        let _event_size = buf.get_u32_le();
+        let log_position = buf.get_u32_le();
+        let _flags = buf.get_u16_le();
+
+        let data = match event_type {
+            ROTATE_EVENT => MySqlBinlogEventData::Rotate {
+                position: buf.get_u64_le(),
+                file: String::from_utf8_lossy(&buf).into_owned(),
+            },
+
+            GTID_EVENT => {
+                let _flags = buf.get_u8();
+                let sid = buf.get_bytes(16);
+                let gno = buf.get_i64_le();
+
+                let sid = hex::encode(sid);
+
+                MySqlBinlogEventData::Gtid {
+                    gtid: format!(
+                        "{}-{}-{}-{}-{}:{}",
+                        &sid[..8],
+                        &sid[8..12],
+                        &sid[12..16],
+                        &sid[16..20],
+                        &sid[20..],
+                        gno
+                    ),
+                }
+            }
+
+            QUERY_EVENT => {
+                let _thread_id = buf.get_u32_le();
+                let _exec_time = buf.get_u32_le();
+                let schema_len = buf.get_u8() as usize;
+                let _error_code = buf.get_u16_le();
+                let status_vars_len = buf.get_u16_le() as usize;
+
+                buf.advance(status_vars_len);
+
+                let schema = buf.get_str(schema_len)?;
+                buf.advance(1); // NUL-terminator
+
+                MySqlBinlogEventData::Query {
+                    schema,
+                    query: String::from_utf8_lossy(&buf).into_owned(),
+                }
+            }
+
+            XID_EVENT => MySqlBinlogEventData::Xid {
+                xid: buf.get_u64_le(),
+            },
+
+            TABLE_MAP_EVENT => {
+                let table = Arc::new(decode_table_map(buf)?);
+                self.tables.insert(table.id, Arc::clone(&table));
+
+                MySqlBinlogEventData::TableMap(table)
+            }
+
+            WRITE_ROWS_EVENT | WRITE_ROWS_EVENT_V1 | UPDATE_ROWS_EVENT | UPDATE_ROWS_EVENT_V1
+            | DELETE_ROWS_EVENT | DELETE_ROWS_EVENT_V1 => {
+                MySqlBinlogEventData::Rows(self.decode_rows(buf, event_type)?)
+            }
+
+            HEARTBEAT_EVENT => MySqlBinlogEventData::Heartbeat,
+
+            _ => MySqlBinlogEventData::Other { event_type },
+        };
+
+        Ok(MySqlBinlogEvent {
+            timestamp,
+            server_id,
+            log_position,
+            data,
+        })
+    }
+
+    fn decode_rows(&self, mut buf: Bytes, event_type: u8) -> Result<MySqlBinlogRows, Error> {
+        // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/classbinary__log_1_1Rows__event.html
+        let table_id = buf.get_uint_le(6);
+        let _flags = buf.get_u16_le();
+
+        if event_type >= WRITE_ROWS_EVENT {
+            // the length of the extra data includes the length itself
+            let extra_len = buf.get_u16_le() as usize;
+            buf.advance(extra_len.saturating_sub(2));
+        }
+
+        let table =
+            self.tables.get(&table_id).cloned().ok_or_else(|| {
+                err_protocol!("binlog rows event for unknown table id {}", table_id)
+            })?;
+
+        let kind = match event_type {
+            WRITE_ROWS_EVENT | WRITE_ROWS_EVENT_V1 => MySqlBinlogRowsKind::Insert,
+            UPDATE_ROWS_EVENT | UPDATE_ROWS_EVENT_V1 => MySqlBinlogRowsKind::Update,
+            _ => MySqlBinlogRowsKind::Delete,
+        };
+
+        let num_columns = buf.get_uint_lenenc() as usize;
+        let bitmap_len = num_columns.div_ceil(8);
+
+        let present = buf.get_bytes(bitmap_len);
+        let present_after = if kind == MySqlBinlogRowsKind::Update {
+            buf.get_bytes(bitmap_len)
+        } else {
+            present.clone()
+        };
+
+        let mut rows = Vec::new();
+
+        while buf.has_remaining() {
+            let row = match kind {
+                MySqlBinlogRowsKind::Insert => MySqlBinlogRow {
+                    before: None,
+                    after: Some(decode_row_image(&mut buf, &table, &present)?),
+                },
+
+                MySqlBinlogRowsKind::Update => MySqlBinlogRow {
+                    before: Some(decode_row_image(&mut buf, &table, &present)?),
+                    after: Some(decode_row_image(&mut buf, &table, &present_after)?),
+                },
+
+                MySqlBinlogRowsKind::Delete => MySqlBinlogRow {
+                    before: Some(decode_row_image(&mut buf, &table, &present)?),
+                    after: None,
+                },
+            };
+
+            rows.push(row);
+        }
+
+        Ok(MySqlBinlogRows { kind, table, rows })
+    }
+}
+
+fn is_bit_set(bitmap: &[u8], i: usize) -> bool {
+    bitmap[i / 8] & (1 << (i % 8)) != 0
+}
+
+fn decode_table_map(mut buf: Bytes) -> Result<MySqlBinlogTable, Error> {
+    // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/classbinary__log_1_1Table__map__event.html
+    let id = buf.get_uint_le(6);
+    let _flags = buf.get_u16_le();
+
+    let schema_len = buf.get_u8() as usize;
+    let schema = buf.get_str(schema_len)?;
+    buf.advance(1); // NUL-terminator
+
+    let name_len = buf.get_u8() as usize;
+    let name = buf.get_str(name_len)?;
+    buf.advance(1); // NUL-terminator
+
+    let num_columns = buf.get_uint_lenenc() as usize;
+    let column_types = buf.get_bytes(num_columns).to_vec();
+
+    let mut meta = buf.get_bytes_lenenc();
+    let mut column_meta = Vec::with_capacity(num_columns);
+
+    for &r#type in &column_types {
+        use column_type::*;
+
+        column_meta.push(match r#type {
+            FLOAT | DOUBLE | BLOB | TINY_BLOB | MEDIUM_BLOB | LONG_BLOB | GEOMETRY | JSON
+            | TIME2 | DATETIME2 | TIMESTAMP2 => u16::from(meta.get_u8()),
+
+            VARCHAR | VAR_STRING => meta.get_u16_le(),
+
+            // stored as two separate bytes, e.g. (precision, scale) or (real type, length)
+            BIT | NEWDECIMAL | STRING | ENUM | SET => meta.get_u16(),
+
+            _ => 0,
+        });
+    }
+
+    Ok(MySqlBinlogTable {
+        id,
+        schema,
+        name,
+        column_types,
+        column_meta,
+    })
+}
+
+fn decode_row_image(
+    buf: &mut Bytes,
+    table: &MySqlBinlogTable,
+    present: &[u8],
+) -> Result<Vec<Option<MySqlBinlogValue>>, Error> {
+    let num_columns = table.columns();
+    let num_present = (0..num_columns).filter(|&i| is_bit_set(present, i)).count();
+
+    // the NULL bitmap only covers the columns present in the image
+    let nulls = buf.get_bytes(num_present.div_ceil(8));
+
+    let mut values = Vec::with_capacity(num_columns);
+    let mut index = 0;
+
+    for i in 0..num_columns {
+        if !is_bit_set(present, i) {
+            values.push(None);
+            continue;
+        }
+
+        let value = if is_bit_set(&nulls, index) {
+            MySqlBinlogValue::Null
+        } else {
+            MySqlBinlogValue::decode(buf, table.column_types[i], table.column_meta[i])?
+        };
+
+        values.push(Some(value));
+        index += 1;
+    }
+
+    Ok(values)
+}
+
+#[test]
+fn test_decode_write_rows_event() {
+    let mut decoder = EventDecoder::default();
+
+    // TABLE_MAP_EVENT for `test`.`t` (id INT NOT NULL, name VARCHAR(50) NULL)
+    let table_map: &[u8] =
+        b"\x00\x00\x00\x00\x13\x01\x00\x00\x00\x31\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+        \x2a\x00\x00\x00\x00\x00\x01\x00\
+        \x04test\x00\x01t\x00\
+        \x02\x03\x0f\x02\xc8\x00\x02";
+
+    let event = decoder.decode(Bytes::from_static(table_map)).unwrap();
+    assert!(
+        matches!(event.data(), MySqlBinlogEventData::TableMap(t) if t.name() == "t" && t.columns() == 2)
+    );
+
+    // WRITE_ROWS_EVENT (v2) with (1, 'a') and (2, NULL)
+    let write_rows: &[u8] =
+        b"\x00\x00\x00\x00\x1e\x01\x00\x00\x00\x2d\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+        \x2a\x00\x00\x00\x00\x00\x01\x00\x02\x00\
+        \x02\xff\
+        \x00\x01\x00\x00\x00\x01a\
+        \x02\x02\x00\x00\x00";
+
+    let event = decoder.decode(Bytes::from_static(write_rows)).unwrap();
+    let rows = match event.into_data() {
+        MySqlBinlogEventData::Rows(rows) => rows,
+        data => panic!("unexpected event {:?}", data),
+    };
+
+    assert_eq!(rows.kind(), MySqlBinlogRowsKind::Insert);
+    assert_eq!(rows.table().schema(), "test");
+    assert_eq!(rows.rows().len(), 2);
+
+    assert_eq!(
+        rows.rows()[0].after().unwrap(),
+        &[
+            Some(MySqlBinlogValue::Int(1)),
+            Some(MySqlBinlogValue::Bytes(Bytes::from_static(b"a")))
+        ][..]
+    );
+
+    assert_eq!(
+        rows.rows()[1].after().unwrap(),
+        &[Some(MySqlBinlogValue::Int(2)), Some(MySqlBinlogValue::Null)][..]
+    );
+}
@@ -0,0 +1,521 @@
+use std::fmt::{self, Display, Formatter, Write};
+use std::str::from_utf8;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+
+use super::value::{column_type, decode_decimal};
+use crate::error::Error;
+
+// https://github.com/mysql/mysql-server/blob/8.0/sql-common/json_binary.h
+
+const SMALL_OBJECT: u8 = 0x00;
+const LARGE_OBJECT: u8 = 0x01;
+const SMALL_ARRAY: u8 = 0x02;
+const LARGE_ARRAY: u8 = 0x03;
+const LITERAL: u8 = 0x04;
+const INT16: u8 = 0x05;
+const UINT16: u8 = 0x06;
+const INT32: u8 = 0x07;
+const UINT32: u8 = 0x08;
+const INT64: u8 = 0x09;
+const UINT64: u8 = 0x0a;
+const DOUBLE: u8 = 0x0b;
+const STRING: u8 = 0x0c;
+const OPAQUE: u8 = 0x0f;
+
+const LITERAL_NULL: u8 = 0x00;
+const LITERAL_TRUE: u8 = 0x01;
+const LITERAL_FALSE: u8 = 0x02;
+
+// documents nested deeper than this are rejected by the server
+const MAX_DEPTH: usize = 100;
+
+/// A `JSON` value of a binlog row image in the binary JSON format of MySQL.
+///
+/// Members and elements are looked up directly in the binary representation,
+/// without materializing the whole document. The [`Display`] implementation formats the
+/// value as JSON text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MySqlBinlogJson {
+    r#type: u8,
+
+    // the encoded value, starting at the element count for objects and arrays
+    data: Bytes,
+}
+
+impl MySqlBinlogJson {
+    pub(super) fn decode(mut buf: Bytes) -> Result<Self, Error> {
+        // an empty value is written for JSON columns set to a JSON null
+        // by a partial update
+        if buf.is_empty() {
+            return Ok(Self {
+                r#type: LITERAL,
+                data: Bytes::from_static(&[LITERAL_NULL]),
+            });
+        }
+
+        let r#type = buf[0];
+        let json = Self {
+            r#type,
+            data: buf.split_off(1),
+        };
+
+        if !json.validate(0) {
+            return Err(err_protocol!(
+                "invalid binary JSON value in binlog row image"
+            ));
+        }
+
+        Ok(json)
+    }
+
+    /// Returns `true` if the value is the JSON literal `null`.
+    pub fn is_null(&self) -> bool {
+        self.r#type == LITERAL && self.data.first() == Some(&LITERAL_NULL)
+    }
+
+    /// Returns `true` if the value is a JSON object.
+    pub fn is_object(&self) -> bool {
+        matches!(self.r#type, SMALL_OBJECT | LARGE_OBJECT)
+    }
+
+    /// Returns `true` if the value is a JSON array.
+    pub fn is_array(&self) -> bool {
+        matches!(self.r#type, SMALL_ARRAY | LARGE_ARRAY)
+    }
+
+    /// Returns the value if it is a JSON boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match (self.r#type, self.data.first()) {
+            (LITERAL, Some(&LITERAL_TRUE)) => Some(true),
+            (LITERAL, Some(&LITERAL_FALSE)) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is an integer which fits into an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.r#type {
+            INT16 => Some(i64::from(i16::from_le_bytes(self.array()?))),
+            UINT16 => Some(i64::from(u16::from_le_bytes(self.array()?))),
+            INT32 => Some(i64::from(i32::from_le_bytes(self.array()?))),
+            UINT32 => Some(i64::from(u32::from_le_bytes(self.array()?))),
+            INT64 => Some(i64::from_le_bytes(self.array()?)),
+            UINT64 => u64::from_le_bytes(self.array()?).try_into().ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value if it is an integer which fits into a `u64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.r#type {
+            UINT64 => Some(u64::from_le_bytes(self.array()?)),
+            _ => self.as_i64()?.try_into().ok(),
+        }
+    }
+
+    /// Returns the value if it is a number, converted to an `f64` if it is an integer.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.r#type {
+            DOUBLE => Some(f64::from_le_bytes(self.array()?)),
+            UINT64 => Some(u64::from_le_bytes(self.array()?) as f64),
+            _ => Some(self.as_i64()? as f64),
+        }
+    }
+
+    /// Returns the value if it is a JSON string.
+    pub fn as_str(&self) -> Option<&str> {
+        if self.r#type != STRING {
+            return None;
+        }
+
+        let (len, n) = read_variable_length(&self.data)?;
+
+        from_utf8(self.data.get(n..n + len)?).ok()
+    }
+
+    /// Looks up the member of an object by its key.
+    pub fn get(&self, key: &str) -> Option<MySqlBinlogJson> {
+        let (large, true, count) = self.container()? else {
+            return None;
+        };
+
+        // keys are sorted by length and then by their bytes, so that they can be searched
+        // without looking at every key
+        let (mut lo, mut hi) = (0, count);
+
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let k = self.key(large, count, mid)?;
+
+            match (k.len(), k).cmp(&(key.len(), key)) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => return self.entry(large, true, count, mid),
+            }
+        }
+
+        None
+    }
+
+    /// Returns the element of an array at the given index.
+    pub fn get_index(&self, index: usize) -> Option<MySqlBinlogJson> {
+        match self.container()? {
+            (large, false, count) if index < count => self.entry(large, false, count, index),
+            _ => None,
+        }
+    }
+
+    /// Looks up a value by a [JSON Pointer](https://tools.ietf.org/html/rfc6901),
+    /// e.g. `/address/lines/0`.
+    ///
+    /// Returns `None` if the pointer is malformed or does not resolve to a value.
+    pub fn pointer(&self, pointer: &str) -> Option<MySqlBinlogJson> {
+        if pointer.is_empty() {
+            return Some(self.clone());
+        }
+
+        let mut value = self.clone();
+
+        for token in pointer.strip_prefix('/')?.split('/') {
+            let token = token.replace("~1", "/").replace("~0", "~");
+
+            value = if value.is_array() {
+                value.get_index(token.parse().ok()?)?
+            } else {
+                value.get(&token)?
+            };
+        }
+
+        Some(value)
+    }
+
+    /// Returns an iterator over the keys and values of an object, or the indices and values
+    /// of an array.
+    pub fn entries(&self) -> impl Iterator<Item = (Option<&str>, MySqlBinlogJson)> + '_ {
+        let (large, object, count) = self.container().unwrap_or((false, false, 0));
+
+        (0..count).filter_map(move |i| {
+            let key = if object {
+                Some(self.key(large, count, i)?)
+            } else {
+                None
+            };
+
+            Some((key, self.entry(large, object, count, i)?))
+        })
+    }
+
+    /// Converts the value into a [`JsonValue`](crate::types::JsonValue).
+    #[cfg(feature = "json")]
+    pub fn to_value(&self) -> Result<crate::types::JsonValue, crate::error::BoxDynError> {
+        crate::types::Json::decode_from_string(&self.to_string()).map(|json| json.0)
+    }
+
+    fn array<const N: usize>(&self) -> Option<[u8; N]> {
+        self.data.get(..N)?.try_into().ok()
+    }
+
+    // (large, object, count)
+    fn container(&self) -> Option<(bool, bool, usize)> {
+        let (large, object) = match self.r#type {
+            SMALL_OBJECT => (false, true),
+            LARGE_OBJECT => (true, true),
+            SMALL_ARRAY => (false, false),
+            LARGE_ARRAY => (true, false),
+            _ => return None,
+        };
+
+        Some((large, object, read_offset(&self.data, 0, large)?))
+    }
+
+    fn key(&self, large: bool, count: usize, index: usize) -> Option<&str> {
+        if index >= count {
+            return None;
+        }
+
+        let offset_size = offset_size(large);
+        let pos = 2 * offset_size + index * (offset_size + 2);
+
+        let offset = read_offset(&self.data, pos, large)?;
+        let len = read_offset(&self.data, pos + offset_size, false)?;
+
+        from_utf8(self.data.get(offset..offset + len)?).ok()
+    }
+
+    fn entry(&self, large: bool, object: bool, count: usize, index: usize) -> Option<Self> {
+        let offset_size = offset_size(large);
+        let key_entries = if object { count * (offset_size + 2) } else { 0 };
+
+        let pos = 2 * offset_size + key_entries + index * (1 + offset_size);
+        let r#type = *self.data.get(pos)?;
+
+        let inlined = match r#type {
+            LITERAL | INT16 | UINT16 => true,
+            INT32 | UINT32 => large,
+            _ => false,
+        };
+
+        let data = if inlined {
+            if pos + 1 + offset_size > self.data.len() {
+                return None;
+            }
+
+            self.data.slice(pos + 1..pos + 1 + offset_size)
+        } else {
+            let offset = read_offset(&self.data, pos + 1, large)?;
+
+            if offset > self.data.len() {
+                return None;
+            }
+
+            self.data.slice(offset..)
+        };
+
+        Some(Self { r#type, data })
+    }
+
+    fn validate(&self, depth: usize) -> bool {
+        if depth > MAX_DEPTH {
+            return false;
+        }
+
+        match self.r#type {
+            SMALL_OBJECT | LARGE_OBJECT | SMALL_ARRAY | LARGE_ARRAY => {
+                let Some((large, object, count)) = self.container() else {
+                    return false;
+                };
+
+                (0..count).all(|i| {
+                    (!object || self.key(large, count, i).is_some())
+                        && self
+                            .entry(large, object, count, i)
+                            .is_some_and(|entry| entry.validate(depth + 1))
+                })
+            }
+
+            LITERAL => matches!(
+                self.data.first(),
+                Some(&(LITERAL_NULL | LITERAL_TRUE | LITERAL_FALSE))
+            ),
+
+            INT16 | UINT16 => self.data.len() >= 2,
+            INT32 | UINT32 => self.data.len() >= 4,
+            INT64 | UINT64 | DOUBLE => self.data.len() >= 8,
+
+            STRING => self.as_str().is_some(),
+            OPAQUE => self.opaque().is_some(),
+
+            _ => false,
+        }
+    }
+
+    // (field type, data)
+    fn opaque(&self) -> Option<(u8, &[u8])> {
+        let field_type = *self.data.first()?;
+        let (len, n) = read_variable_length(&self.data[1..])?;
+
+        Some((field_type, self.data.get(1 + n..1 + n + len)?))
+    }
+}
+
+impl Display for MySqlBinlogJson {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.r#type {
+            SMALL_OBJECT | LARGE_OBJECT | SMALL_ARRAY | LARGE_ARRAY => {
+                let object = self.is_object();
+                f.write_char(if object { '{' } else { '[' })?;
+
+                for (i, (key, value)) in self.entries().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    if let Some(key) = key {
+                        write_json_string(f, key)?;
+                        f.write_str(": ")?;
+                    }
+
+                    Display::fmt(&value, f)?;
+                }
+
+                f.write_char(if object { '}' } else { ']' })
+            }
+
+            LITERAL => f.write_str(match self.as_bool() {
+                Some(true) => "true",
+                Some(false) => "false",
+                None => "null",
+            }),
+
+            INT16 | UINT16 | INT32 | UINT32 | INT64 => {
+                write!(f, "{}", self.as_i64().ok_or(fmt::Error)?)
+            }
+
+            UINT64 => write!(f, "{}", self.as_u64().ok_or(fmt::Error)?),
+
+            // `Debug` always includes a decimal point or exponent, like MySQL
+            DOUBLE => write!(f, "{:?}", self.as_f64().ok_or(fmt::Error)?),
+
+            STRING => write_json_string(f, self.as_str().ok_or(fmt::Error)?),
+
+            OPAQUE => {
+                let (field_type, data) = self.opaque().ok_or(fmt::Error)?;
+
+                write_opaque(f, field_type, data)
+            }
+
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+fn offset_size(large: bool) -> usize {
+    if large {
+        4
+    } else {
+        2
+    }
+}
+
+fn read_offset(data: &[u8], pos: usize, large: bool) -> Option<usize> {
+    if large {
+        let bytes = data.get(pos..pos + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?) as usize)
+    } else {
+        let bytes = data.get(pos..pos + 2)?;
+        Some(usize::from(u16::from_le_bytes(bytes.try_into().ok()?)))
+    }
+}
+
+// lengths are stored in 7 bits per byte, the high bit is set if more bytes follow
+fn read_variable_length(data: &[u8]) -> Option<(usize, usize)> {
+    let mut len = 0;
+
+    for i in 0..5 {
+        let b = *data.get(i)?;
+        len |= usize::from(b & 0x7f) << (7 * i);
+
+        if b & 0x80 == 0 {
+            return Some((len, i + 1));
+        }
+    }
+
+    None
+}
+
+fn write_json_string(f: &mut Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\u{08}' => f.write_str("\\b")?,
+            '\u{0c}' => f.write_str("\\f")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+
+    f.write_char('"')
+}
+
+// opaque values are MySQL values without a JSON representation, e.g. DECIMAL or DATETIME
+fn write_opaque(f: &mut Formatter<'_>, field_type: u8, data: &[u8]) -> fmt::Result {
+    match field_type {
+        column_type::NEWDECIMAL if data.len() >= 2 => {
+            let (precision, scale) = (usize::from(data[0]), usize::from(data[1]));
+            let mut buf = Bytes::copy_from_slice(&data[2..]);
+
+            let decimal = decode_decimal(&mut buf, precision, scale).map_err(|_| fmt::Error)?;
+
+            f.write_str(&decimal)
+        }
+
+        column_type::DATE | column_type::DATETIME | column_type::TIMESTAMP | column_type::TIME
+            if data.len() >= 8 =>
+        {
+            let packed = i64::from_le_bytes(data[..8].try_into().unwrap());
+
+            let (negative, packed) = (packed < 0, packed.unsigned_abs());
+            let (int_part, microsecond) = (packed >> 24, packed % (1 << 24));
+
+            let time = int_part % (1 << 17);
+            let (hour, minute, second) = (time >> 12, (time >> 6) % (1 << 6), time % (1 << 6));
+
+            let s = if field_type == column_type::TIME {
+                format!(
+                    "{}{:02}:{:02}:{:02}.{:06}",
+                    if negative { "-" } else { "" },
+                    (int_part >> 12) % (1 << 10),
+                    minute,
+                    second,
+                    microsecond
+                )
+            } else {
+                let ymd = int_part >> 17;
+                let ym = ymd >> 5;
+                let date = format!("{:04}-{:02}-{:02}", ym / 13, ym % 13, ymd % (1 << 5));
+
+                if field_type == column_type::DATE {
+                    date
+                } else {
+                    format!("{date} {hour:02}:{minute:02}:{second:02}.{microsecond:06}")
+                }
+            };
+
+            write_json_string(f, &s)
+        }
+
+        // like MySQL, format other opaque values as base64
+        _ => write_json_string(
+            f,
+            &format!("base64:type{}:{}", field_type, STANDARD.encode(data)),
+        ),
+    }
+}
+
+#[test]
+fn test_decode_binary_json() {
+    // {"a": 1, "bb": [true, "x", null, 2.5], "c": 1.5}
+    //
+    // the keys are sorted by length, so "c" is stored before "bb"
+    let data = Bytes::from_static(
+        b"\x00\x03\x00\x3f\x00\x19\x00\x01\x00\x1a\x00\x01\x00\x1b\x00\x02\x00\x05\x01\x00\x0b\x1d\x00\x02\x25\x00acbb\x00\x00\x00\x00\x00\x00\xf8\x3f\x04\x00\x1a\x00\x04\x01\x00\x0c\x10\x00\x04\x00\x00\x0b\x12\x00\x01x\x00\x00\x00\x00\x00\x00\x04\x40",
+    );
+
+    let json = MySqlBinlogJson::decode(data).unwrap();
+
+    assert!(json.is_object());
+    assert_eq!(json.entries().count(), 3);
+    assert_eq!(json.get("a").and_then(|v| v.as_i64()), Some(1));
+    assert_eq!(json.get("c").and_then(|v| v.as_f64()), Some(1.5));
+    assert_eq!(json.pointer("/bb/0").and_then(|v| v.as_bool()), Some(true));
+    assert_eq!(
+        json.pointer("/bb/1").as_ref().and_then(|v| v.as_str()),
+        Some("x")
+    );
+    assert!(json.pointer("/bb/2").unwrap().is_null());
+    assert_eq!(json.pointer("/bb/3").and_then(|v| v.as_f64()), Some(2.5));
+    assert!(json.get("d").is_none());
+
+    assert_eq!(
+        json.to_string(),
+        r#"{"a": 1, "c": 1.5, "bb": [true, "x", null, 2.5]}"#
+    );
+}
+
+#[test]
+fn test_decode_binary_json_opaque() {
+    // CAST(1234.5678 AS DECIMAL(10, 4)) as a JSON scalar
+    let data = Bytes::from_static(b"\x0f\xf6\x07\x0a\x04\x80\x04\xd2\x16\x2e");
+
+    let json = MySqlBinlogJson::decode(data).unwrap();
+
+    assert_eq!(json.to_string(), "1234.5678");
+}
@@ -0,0 +1,440 @@
+use bytes::{Buf, Bytes};
+
+use super::json::MySqlBinlogJson;
+use crate::error::Error;
+use crate::io::BufExt;
+
+// column types as they appear in a TABLE_MAP_EVENT
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/field__types_8h.html
+pub(super) mod column_type {
+    pub(crate) const DECIMAL: u8 = 0x00;
+    pub(crate) const TINY: u8 = 0x01;
+    pub(crate) const SHORT: u8 = 0x02;
+    pub(crate) const LONG: u8 = 0x03;
+    pub(crate) const FLOAT: u8 = 0x04;
+    pub(crate) const DOUBLE: u8 = 0x05;
+    pub(crate) const NULL: u8 = 0x06;
+    pub(crate) const TIMESTAMP: u8 = 0x07;
+    pub(crate) const LONGLONG: u8 = 0x08;
+    pub(crate) const INT24: u8 = 0x09;
+    pub(crate) const DATE: u8 = 0x0a;
+    pub(crate) const TIME: u8 = 0x0b;
+    pub(crate) const DATETIME: u8 = 0x0c;
+    pub(crate) const YEAR: u8 = 0x0d;
+    pub(crate) const VARCHAR: u8 = 0x0f;
+    pub(crate) const BIT: u8 = 0x10;
+    pub(crate) const TIMESTAMP2: u8 = 0x11;
+    pub(crate) const DATETIME2: u8 = 0x12;
+    pub(crate) const TIME2: u8 = 0x13;
+    pub(crate) const JSON: u8 = 0xf5;
+    pub(crate) const NEWDECIMAL: u8 = 0xf6;
+    pub(crate) const ENUM: u8 = 0xf7;
+    pub(crate) const SET: u8 = 0xf8;
+    pub(crate) const TINY_BLOB: u8 = 0xf9;
+    pub(crate) const MEDIUM_BLOB: u8 = 0xfa;
+    pub(crate) const LONG_BLOB: u8 = 0xfb;
+    pub(crate) const BLOB: u8 = 0xfc;
+    pub(crate) const VAR_STRING: u8 = 0xfd;
+    pub(crate) const STRING: u8 = 0xfe;
+    pub(crate) const GEOMETRY: u8 = 0xff;
+}
+
+/// A single column value of a row image in a binlog rows event.
+///
+/// Row images only carry the storage type of a column. Integers are always decoded as signed;
+/// the value of an `UNSIGNED` column can be recovered by casting to the unsigned type of the
+/// same width (e.g. `v as u8` for a `TINYINT UNSIGNED`).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum MySqlBinlogValue {
+    Null,
+
+    /// `TINYINT`, `SMALLINT`, `MEDIUMINT`, `INT` and `BIGINT`.
+    Int(i64),
+
+    Float(f32),
+
+    Double(f64),
+
+    /// `DECIMAL`, formatted as a string to preserve its exact value.
+    Decimal(String),
+
+    /// `CHAR`, `VARCHAR`, `BINARY`, `BLOB` and `TEXT` types, `BIT` and `GEOMETRY` (in the internal
+    /// SRID + WKB format).
+    Bytes(Bytes),
+
+    Json(MySqlBinlogJson),
+
+    Year(u16),
+
+    Date {
+        year: u16,
+        month: u8,
+        day: u8,
+    },
+
+    Time {
+        negative: bool,
+        hours: u32,
+        minutes: u8,
+        seconds: u8,
+        microseconds: u32,
+    },
+
+    DateTime {
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        microsecond: u32,
+    },
+
+    /// `TIMESTAMP` as seconds and microseconds since the Unix epoch.
+    Timestamp {
+        seconds: u32,
+        microseconds: u32,
+    },
+
+    /// The 1-based index of an `ENUM` value.
+    Enum(u16),
+
+    /// The bitmap of the members of a `SET` value.
+    Set(u64),
+}
+
+impl MySqlBinlogValue {
+    pub(super) fn decode(buf: &mut Bytes, r#type: u8, meta: u16) -> Result<Self, Error> {
+        use column_type::*;
+
+        Ok(match r#type {
+            TINY => Self::Int(i64::from(buf.get_i8())),
+            SHORT => Self::Int(i64::from(buf.get_i16_le())),
+            INT24 => Self::Int(buf.get_int_le(3)),
+            LONG => Self::Int(i64::from(buf.get_i32_le())),
+            LONGLONG => Self::Int(buf.get_i64_le()),
+
+            FLOAT => Self::Float(buf.get_f32_le()),
+            DOUBLE => Self::Double(buf.get_f64_le()),
+
+            NULL => Self::Null,
+
+            YEAR => match buf.get_u8() {
+                0 => Self::Year(0),
+                v => Self::Year(1900 + u16::from(v)),
+            },
+
+            NEWDECIMAL => {
+                let precision = (meta >> 8) as usize;
+                let scale = (meta & 0xff) as usize;
+
+                Self::Decimal(decode_decimal(buf, precision, scale)?)
+            }
+
+            VARCHAR | VAR_STRING => {
+                let len = if meta < 256 {
+                    usize::from(buf.get_u8())
+                } else {
+                    usize::from(buf.get_u16_le())
+                };
+
+                Self::Bytes(buf.get_bytes(len))
+            }
+
+            STRING => {
+                let (real_type, len) = ((meta >> 8) as u8, meta & 0xff);
+
+                match real_type {
+                    ENUM => Self::Enum(buf.get_uint_le(len as usize) as u16),
+                    SET => Self::Set(buf.get_uint_le(len as usize)),
+
+                    _ => {
+                        // the upper bits of the max. length are stored XOR'd in the real type
+                        let max_len = (((u16::from(real_type) << 4) & 0x300) ^ 0x300) + len;
+
+                        let len = if max_len < 256 {
+                            usize::from(buf.get_u8())
+                        } else {
+                            usize::from(buf.get_u16_le())
+                        };
+
+                        Self::Bytes(buf.get_bytes(len))
+                    }
+                }
+            }
+
+            BLOB | TINY_BLOB | MEDIUM_BLOB | LONG_BLOB | GEOMETRY => {
+                let len = buf.get_uint_le(meta as usize) as usize;
+
+                Self::Bytes(buf.get_bytes(len))
+            }
+
+            JSON => {
+                let len = buf.get_uint_le(meta as usize) as usize;
+
+                Self::Json(MySqlBinlogJson::decode(buf.get_bytes(len))?)
+            }
+
+            BIT => {
+                let (bits, bytes) = ((meta >> 8) as usize, (meta & 0xff) as usize);
+                let len = bytes + usize::from(bits > 0);
+
+                Self::Bytes(buf.get_bytes(len))
+            }
+
+            DATE => {
+                let v = buf.get_uint_le(3);
+
+                Self::Date {
+                    year: (v >> 9) as u16,
+                    month: ((v >> 5) & 0x0f) as u8,
+                    day: (v & 0x1f) as u8,
+                }
+            }
+
+            TIMESTAMP => Self::Timestamp {
+                seconds: buf.get_u32_le(),
+                microseconds: 0,
+            },
+
+            TIMESTAMP2 => Self::Timestamp {
+                seconds: buf.get_u32(),
+                microseconds: decode_fraction(buf, meta),
+            },
+
+            DATETIME => {
+                // YYYYMMDDhhmmss as a decimal number
+                let v = buf.get_u64_le();
+                let (date, time) = (v / 1_000_000, v % 1_000_000);
+
+                Self::DateTime {
+                    year: (date / 10_000) as u16,
+                    month: (date / 100 % 100) as u8,
+                    day: (date % 100) as u8,
+                    hour: (time / 10_000) as u8,
+                    minute: (time / 100 % 100) as u8,
+                    second: (time % 100) as u8,
+                    microsecond: 0,
+                }
+            }
+
+            DATETIME2 => {
+                // https://github.com/mysql/mysql-server/blob/8.0/mysys/my_time.cc (my_datetime_packed_from_binary)
+                let int_part = (buf.get_uint(5) as i64) - 0x80_0000_0000;
+                let microsecond = decode_fraction(buf, meta);
+
+                let ymd = int_part >> 17;
+                let ym = ymd >> 5;
+                let hms = int_part % (1 << 17);
+
+                Self::DateTime {
+                    year: (ym / 13) as u16,
+                    month: (ym % 13) as u8,
+                    day: (ymd % (1 << 5)) as u8,
+                    hour: (hms >> 12) as u8,
+                    minute: ((hms >> 6) % (1 << 6)) as u8,
+                    second: (hms % (1 << 6)) as u8,
+                    microsecond,
+                }
+            }
+
+            TIME => {
+                // hhmmss as a (signed) decimal number
+                let v = buf.get_int_le(3);
+                let negative = v < 0;
+                let v = v.unsigned_abs();
+
+                Self::Time {
+                    negative,
+                    hours: (v / 10_000) as u32,
+                    minutes: (v / 100 % 100) as u8,
+                    seconds: (v % 100) as u8,
+                    microseconds: 0,
+                }
+            }
+
+            TIME2 => decode_time2(buf, meta),
+
+            // the pre-5.0 DECIMAL type is not written by any supported server version
+            DECIMAL | ENUM | SET => {
+                return Err(err_protocol!(
+                    "unexpected column type 0x{:02x} in binlog row image",
+                    r#type
+                ));
+            }
+
+            _ => {
+                return Err(err_protocol!(
+                    "unknown column type 0x{:02x} in binlog row image",
+                    r#type
+                ));
+            }
+        })
+    }
+}
+
+// reads the fractional seconds of a TIMESTAMP2, DATETIME2 or TIME2 in microseconds
+fn decode_fraction(buf: &mut Bytes, fsp: u16) -> u32 {
+    match fsp {
+        1 | 2 => u32::from(buf.get_u8()) * 10_000,
+        3 | 4 => u32::from(buf.get_u16()) * 100,
+        5 | 6 => buf.get_uint(3) as u32,
+        _ => 0,
+    }
+}
+
+fn decode_time2(buf: &mut Bytes, fsp: u16) -> MySqlBinlogValue {
+    // https://github.com/mysql/mysql-server/blob/8.0/mysys/my_time.cc (my_time_packed_from_binary)
+    const TIMEF_INT_OFS: i64 = 0x80_0000;
+
+    let packed = match fsp {
+        1 | 2 => {
+            let mut int_part = buf.get_uint(3) as i64 - TIMEF_INT_OFS;
+            let mut frac = i64::from(buf.get_u8());
+
+            if int_part < 0 && frac != 0 {
+                int_part += 1;
+                frac -= 0x100;
+            }
+
+            (int_part << 24) + frac * 10_000
+        }
+
+        3 | 4 => {
+            let mut int_part = buf.get_uint(3) as i64 - TIMEF_INT_OFS;
+            let mut frac = i64::from(buf.get_u16());
+
+            if int_part < 0 && frac != 0 {
+                int_part += 1;
+                frac -= 0x10000;
+            }
+
+            (int_part << 24) + frac * 100
+        }
+
+        5 | 6 => buf.get_uint(6) as i64 - 0x8000_0000_0000,
+
+        _ => (buf.get_uint(3) as i64 - TIMEF_INT_OFS) << 24,
+    };
+
+    let negative = packed < 0;
+    let packed = packed.abs();
+
+    let hms = packed >> 24;
+
+    MySqlBinlogValue::Time {
+        negative,
+        hours: ((hms >> 12) % (1 << 10)) as u32,
+        minutes: ((hms >> 6) % (1 << 6)) as u8,
+        seconds: (hms % (1 << 6)) as u8,
+        microseconds: (packed % (1 << 24)) as u32,
+    }
+}
+
+// number of bytes used to store the leftover digits of a decimal
+const DIG2BYTES: [usize; 10] = [0, 1, 1, 2, 2, 3, 3, 4, 4, 4];
+
+const DIGITS_PER_INT: usize = 9;
+
+pub(super) fn decode_decimal(
+    buf: &mut Bytes,
+    precision: usize,
+    scale: usize,
+) -> Result<String, Error> {
+    // https://github.com/mysql/mysql-server/blob/8.0/strings/decimal.cc (bin2decimal)
+    let integral = precision.saturating_sub(scale);
+
+    let (integral_ints, integral_rest) = (integral / DIGITS_PER_INT, integral % DIGITS_PER_INT);
+    let (scale_ints, scale_rest) = (scale / DIGITS_PER_INT, scale % DIGITS_PER_INT);
+
+    let size =
+        integral_ints * 4 + DIG2BYTES[integral_rest] + scale_ints * 4 + DIG2BYTES[scale_rest];
+
+    if buf.len() < size || size == 0 {
+        return Err(err_protocol!(
+            "expected {} bytes for DECIMAL({}, {}) but found {}",
+            size,
+            precision,
+            scale,
+            buf.len()
+        ));
+    }
+
+    let mut bin = buf.split_to(size).to_vec();
+
+    // the sign is stored in the (inverted) high bit, negative values have all their bits flipped
+    let negative = bin[0] & 0x80 == 0;
+    bin[0] ^= 0x80;
+
+    if negative {
+        bin.iter_mut().for_each(|b| *b = !*b);
+    }
+
+    let mut bin = Bytes::from(bin);
+    let mut s = String::with_capacity(precision + 2);
+
+    if negative {
+        s.push('-');
+    }
+
+    let mut integral_digits = String::with_capacity(integral);
+
+    if integral_rest > 0 {
+        let v = bin.get_uint(DIG2BYTES[integral_rest]);
+        integral_digits.push_str(&format!("{:0width$}", v, width = integral_rest));
+    }
+
+    for _ in 0..integral_ints {
+        integral_digits.push_str(&format!("{:09}", bin.get_u32()));
+    }
+
+    match integral_digits.trim_start_matches('0') {
+        "" => s.push('0'),
+        digits => s.push_str(digits),
+    }
+
+    if scale > 0 {
+        s.push('.');
+
+        for _ in 0..scale_ints {
+            s.push_str(&format!("{:09}", bin.get_u32()));
+        }
+
+        if scale_rest > 0 {
+            let v = bin.get_uint(DIG2BYTES[scale_rest]);
+            s.push_str(&format!("{:0width$}", v, width = scale_rest));
+        }
+    }
+
+    Ok(s)
+}
+
+#[test]
+fn test_decode_decimal() {
+    // DECIMAL(10, 4): 1234.5678 and -1234.5678
+    let mut buf = Bytes::from_static(b"\x80\x04\xd2\x16\x2e");
+    assert_eq!(decode_decimal(&mut buf, 10, 4).unwrap(), "1234.5678");
+
+    let mut buf = Bytes::from_static(b"\x7f\xfb\x2d\xe9\xd1");
+    assert_eq!(decode_decimal(&mut buf, 10, 4).unwrap(), "-1234.5678");
+}
+
+#[test]
+fn test_decode_datetime2() {
+    // DATETIME(0): 2023-07-21 13:45:08
+    let mut buf = Bytes::from_static(b"\x99\xb0\xaa\xdb\x48");
+
+    assert_eq!(
+        MySqlBinlogValue::decode(&mut buf, column_type::DATETIME2, 0).unwrap(),
+        MySqlBinlogValue::DateTime {
+            year: 2023,
+            month: 7,
+            day: 21,
+            hour: 13,
+            minute: 45,
+            second: 8,
+            microsecond: 0,
+        }
+    );
+}
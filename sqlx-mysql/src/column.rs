@@ -14,6 +14,28 @@ pub struct MySqlColumn {
     pub(crate) flags: Option<ColumnFlags>,
 }
 
+impl MySqlColumn {
+    /// Returns `true` if the column is part of the primary key of its table.
+    pub fn is_primary_key(&self) -> bool {
+        self.has_flag(ColumnFlags::PRIMARY_KEY)
+    }
+
+    /// Returns `true` if the column is part of a unique key of its table.
+    pub fn is_unique_key(&self) -> bool {
+        self.has_flag(ColumnFlags::UNIQUE_KEY)
+    }
+
+    /// Returns `true` if the column is an `AUTO_INCREMENT` column.
+    pub fn is_auto_increment(&self) -> bool {
+        self.has_flag(ColumnFlags::AUTO_INCREMENT)
+    }
+
+    // the flags are not available for columns loaded from offline query data
+    fn has_flag(&self, flag: ColumnFlags) -> bool {
+        self.flags.is_some_and(|flags| flags.contains(flag))
+    }
+}
+
 impl Column for MySqlColumn {
     type Database = MySql;
 
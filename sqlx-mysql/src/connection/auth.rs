@@ -1,11 +1,13 @@
 use bytes::buf::Chain;
 use bytes::Bytes;
 use digest::{Digest, OutputSizeUser};
+use ed25519_dalek::hazmat::{raw_sign, ExpandedSecretKey};
+use ed25519_dalek::VerifyingKey;
 use generic_array::GenericArray;
 use rand::thread_rng;
 use rsa::{pkcs8::DecodePublicKey, Oaep, RsaPublicKey};
 use sha1::Sha1;
-use sha2::Sha256;
+use sha2::{Sha256, Sha512};
 
 use crate::connection::stream::MySqlStream;
 use crate::error::Error;
@@ -20,13 +22,41 @@ impl AuthPlugin {
         nonce: &Chain<Bytes, Bytes>,
     ) -> Result<Vec<u8>, Error> {
         match self {
+            // https://mariadb.com/kb/en/authentication-plugin-ed25519/
+            // the password derives the signing key so it is used even when empty
+            AuthPlugin::Ed25519 => Ok(scramble_ed25519(password, nonce).to_vec()),
+
+            // https://dev.mysql.com/doc/refman/8.0/en/socket-pluggable-authentication.html
+            // the server authenticates the OS user of the peer of the Unix domain socket,
+            // the client sends no credentials
+            AuthPlugin::AuthSocket | AuthPlugin::UnixSocket => Ok(Vec::new()),
+
+            // https://dev.mysql.com/doc/refman/8.0/en/cleartext-pluggable-authentication.html
+            // the password is sent as is to be checked by the server (e.g. with PAM or LDAP),
+            // which is only permitted on a secure transport
+            AuthPlugin::ClearPassword if stream.is_tls || stream.is_socket => {
+                Ok(to_asciz(password))
+            }
+
+            AuthPlugin::ClearPassword => Err(Error::Tls(
+                "the mysql_clear_password authentication plugin requires TLS or a local socket"
+                    .into(),
+            )),
+
+            // an empty password is always sent as an empty auth response
+            _ if password.is_empty() => Ok(Vec::new()),
+
             // https://mariadb.com/kb/en/caching_sha2_password-authentication-plugin/
             AuthPlugin::CachingSha2Password => Ok(scramble_sha256(password, nonce).to_vec()),
 
             AuthPlugin::MySqlNativePassword => Ok(scramble_sha1(password, nonce).to_vec()),
 
             // https://mariadb.com/kb/en/sha256_password-plugin/
-            AuthPlugin::Sha256Password => encrypt_rsa(stream, 0x01, password, nonce).await,
+            AuthPlugin::Sha256Password if stream.is_tls => Ok(to_asciz(password)),
+
+            // request the public key of the server; the encrypted password is sent
+            // once the key arrives in an AuthMoreData packet (see `handle`)
+            AuthPlugin::Sha256Password => Ok(vec![0x01]),
         }
     }
 
@@ -38,16 +68,22 @@ impl AuthPlugin {
         nonce: &Chain<Bytes, Bytes>,
     ) -> Result<bool, Error> {
         match self {
-            AuthPlugin::CachingSha2Password if packet[0] == 0x01 => {
+            // fast authentication result: AuthMoreData (0x01) followed by a single status byte
+            AuthPlugin::CachingSha2Password if packet[0] == 0x01 && packet.len() == 2 => {
                 match packet[1] {
                     // AUTH_OK
                     0x03 => Ok(true),
 
                     // AUTH_CONTINUE
                     0x04 => {
-                        let payload = encrypt_rsa(stream, 0x02, password, nonce).await?;
+                        if stream.is_tls {
+                            // If in a TLS stream, send the password directly in clear text
+                            stream.write_packet(&*to_asciz(password));
+                        } else {
+                            // client sends a public key request
+                            stream.write_packet(&[0x02_u8][..]);
+                        }
 
-                        stream.write_packet(&*payload);
                         stream.flush().await?;
 
                         Ok(false)
@@ -59,6 +95,17 @@ impl AuthPlugin {
                 }
             }
 
+            // server sends a public key response
+            AuthPlugin::CachingSha2Password | AuthPlugin::Sha256Password if packet[0] == 0x01 => {
+                let payload = encrypt_rsa(&packet[1..], password, nonce)?;
+
+                // client sends an RSA encrypted password
+                stream.write_packet(&*payload);
+                stream.flush().await?;
+
+                Ok(false)
+            }
+
             _ => Err(err_protocol!(
                 "unexpected packet 0x{:02x} for auth plugin '{}' during authentication",
                 packet[0],
@@ -123,26 +170,30 @@ fn scramble_sha256(
     pw_hash
 }
 
-async fn encrypt_rsa<'s>(
-    stream: &'s mut MySqlStream,
-    public_key_request_id: u8,
-    password: &'s str,
-    nonce: &'s Chain<Bytes, Bytes>,
-) -> Result<Vec<u8>, Error> {
-    // https://mariadb.com/kb/en/caching_sha2_password-authentication-plugin/
+fn scramble_ed25519(password: &str, nonce: &Chain<Bytes, Bytes>) -> [u8; 64] {
+    // MariaDB does not use the password as an Ed25519 seed directly, instead
+    // the expanded secret key is SHA512( password ) which is then clamped
+    // https://github.com/MariaDB/server/blob/10.11/plugin/auth_ed25519/ref10/sign.c
+    let mut az = [0u8; 64];
+    az.copy_from_slice(&Sha512::digest(password));
 
-    if stream.is_tls {
-        // If in a TLS stream, send the password directly in clear text
-        return Ok(to_asciz(password));
-    }
+    let secret = ExpandedSecretKey::from_bytes(&az);
+    let public = VerifyingKey::from(&secret);
+
+    let (a, b) = (nonce.first_ref(), nonce.last_ref());
+    let mut message = Vec::with_capacity(a.len() + b.len());
+    message.extend_from_slice(a);
+    message.extend_from_slice(b);
 
-    // client sends a public key request
-    stream.write_packet(&[public_key_request_id][..]);
-    stream.flush().await?;
+    raw_sign::<Sha512>(&secret, &message, &public).to_bytes()
+}
 
-    // server sends a public key response
-    let packet = stream.recv_packet().await?;
-    let rsa_pub_key = &packet[1..];
+fn encrypt_rsa(
+    rsa_pub_key: &[u8],
+    password: &str,
+    nonce: &Chain<Bytes, Bytes>,
+) -> Result<Vec<u8>, Error> {
+    // https://mariadb.com/kb/en/caching_sha2_password-authentication-plugin/
 
     // xor the password with the given nonce
     let mut pass = to_asciz(password);
@@ -154,7 +205,6 @@ async fn encrypt_rsa<'s>(
 
     xor_eq(&mut pass, &*nonce);
 
-    // client sends an RSA encrypted password
     let pkey = parse_rsa_pub_key(rsa_pub_key)?;
     let padding = Oaep::new::<sha1::Sha1>();
     pkey.encrypt(&mut thread_rng(), padding, &pass[..])
@@ -0,0 +1,62 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::{MySqlConnectOptions, MySqlConnection};
+
+use super::Connection;
+
+/// A handle to cancel the statement executed by a connection from another task,
+/// see [`MySqlConnection::cancel_handle`].
+#[derive(Debug, Clone)]
+pub struct MySqlCancelHandle {
+    pub(super) options: Arc<MySqlConnectOptions>,
+    pub(super) connection_id: u32,
+}
+
+impl MySqlCancelHandle {
+    /// The id of the connection on the server, see `SHOW PROCESSLIST`.
+    pub fn connection_id(&self) -> u32 {
+        self.connection_id
+    }
+
+    /// Interrupts the statement currently executed by the connection with `KILL QUERY`, sent
+    /// on a new connection established with the same options.
+    ///
+    /// The interrupted statement fails with an error (`ER_QUERY_INTERRUPTED`) and the
+    /// connection remains usable. This has no effect if the connection is idle, but may
+    /// interrupt a statement started after the one meant to be cancelled.
+    pub async fn cancel(&self) -> Result<(), Error> {
+        let mut conn = MySqlConnection::establish(&self.options).await?;
+
+        conn.execute_internal(&format!("KILL QUERY {}", self.connection_id))
+            .await?;
+
+        conn.close().await
+    }
+}
+
+// marks the statement of a query to be cancelled before the connection is used again,
+// if the query is dropped before all of its results were received
+pub(crate) struct CancelOnDrop(Option<Arc<AtomicBool>>);
+
+impl CancelOnDrop {
+    pub(crate) fn new(conn: &MySqlConnection) -> Self {
+        Self(
+            conn.cancel_on_drop
+                .then(|| Arc::clone(&conn.cancel_pending)),
+        )
+    }
+
+    pub(crate) fn disarm(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(cancel_pending) = &self.0 {
+            cancel_pending.store(true, Ordering::Release);
+        }
+    }
+}
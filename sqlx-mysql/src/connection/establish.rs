@@ -1,4 +1,6 @@
-use bytes::buf::Buf;
+use std::sync::Arc;
+
+use bytes::buf::{Buf, Chain};
 use bytes::Bytes;
 use futures_core::future::BoxFuture;
 
@@ -7,6 +9,8 @@ use crate::common::StatementCache;
 use crate::connection::{tls, MySqlStream, MAX_PACKET_SIZE};
 use crate::error::Error;
 use crate::net::{Socket, WithSocket};
+use crate::options::TimeZoneRules;
+use crate::protocol::auth::AuthPlugin;
 use crate::protocol::connect::{
     AuthSwitchRequest, AuthSwitchResponse, Handshake, HandshakeResponse,
 };
@@ -17,19 +21,42 @@ impl MySqlConnection {
     pub(crate) async fn establish(options: &MySqlConnectOptions) -> Result<Self, Error> {
         let do_handshake = DoHandshake::new(options)?;
 
-        let handshake = match &options.socket {
-            Some(path) => crate::net::connect_uds(path, do_handshake).await?,
-            None => crate::net::connect_tcp(&options.host, options.port, do_handshake).await?,
+        let handshake = match (&options.socket, &options.pipe) {
+            (Some(path), _) => crate::net::connect_uds(path, do_handshake).await?,
+            (None, Some(pipe)) => crate::net::connect_named_pipe(pipe, do_handshake).await?,
+            (None, None) => {
+                crate::net::connect_tcp(&options.host, options.port, do_handshake).await?
+            }
         };
 
         let stream = handshake.await?;
 
-        Ok(Self {
+        Ok(Self::from_stream(options, stream))
+    }
+
+    pub(crate) async fn establish_with_socket(
+        options: &MySqlConnectOptions,
+        socket: Box<dyn Socket>,
+    ) -> Result<Self, Error> {
+        let stream = DoHandshake::new(options)?.with_socket(socket).await?;
+
+        Ok(Self::from_stream(options, stream))
+    }
+
+    fn from_stream(options: &MySqlConnectOptions, stream: MySqlStream) -> Self {
+        Self {
             stream,
             transaction_depth: 0,
             cache_statement: StatementCache::new(options.statement_cache_capacity),
             log_settings: options.log_settings.clone(),
-        })
+            init_sql: String::new(),
+            reset_on_release: options.reset_on_release,
+            options: Arc::new(options.clone()),
+            query_timeout: options.query_timeout,
+            cancel_on_drop: options.cancel_on_drop,
+            cancel_pending: Arc::default(),
+            time_zone: TimeZoneRules::new(&options.time_zone, options.datetime_mode),
+        }
     }
 }
 
@@ -70,7 +97,7 @@ impl<'a> DoHandshake<'a> {
 
         let handshake: Handshake = stream.recv_packet().await?.decode()?;
 
-        let mut plugin = handshake.auth_plugin;
+        let plugin = handshake.auth_plugin;
         let nonce = handshake.auth_plugin_data;
 
         // FIXME: server version parse is a bit ugly
@@ -102,6 +129,7 @@ impl<'a> DoHandshake<'a> {
             server_version_patch,
         );
 
+        stream.connection_id = handshake.connection_id;
         stream.capabilities &= handshake.server_capabilities;
         stream.capabilities |= Capabilities::PROTOCOL_41;
 
@@ -120,58 +148,103 @@ impl<'a> DoHandshake<'a> {
             database: options.database.as_deref(),
             auth_plugin: plugin,
             auth_response: auth_response.as_deref(),
+            connect_attrs: &connect_attrs(options),
         });
 
         stream.flush().await?;
 
-        loop {
-            let packet = stream.recv_packet().await?;
-            match packet[0] {
-                0x00 => {
-                    let _ok = packet.ok()?;
+        let password = options.password.as_deref().unwrap_or_default();
+        authenticate(&mut stream, plugin, nonce, password).await?;
 
-                    break;
-                }
+        Ok(stream)
+    }
+}
+
+// the default attributes (as sent by libmysqlclient) followed by those of the user
+fn connect_attrs(options: &MySqlConnectOptions) -> Vec<(String, String)> {
+    let mut attrs = vec![
+        ("_client_name".to_owned(), "sqlx".to_owned()),
+        (
+            "_client_version".to_owned(),
+            env!("CARGO_PKG_VERSION").to_owned(),
+        ),
+        ("_os".to_owned(), std::env::consts::OS.to_owned()),
+        ("_platform".to_owned(), std::env::consts::ARCH.to_owned()),
+        ("_pid".to_owned(), std::process::id().to_string()),
+    ];
+
+    let program_name = std::env::current_exe().ok().and_then(|path| {
+        path.file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+    });
+
+    if let Some(program_name) = program_name {
+        attrs.push(("program_name".to_owned(), program_name));
+    }
 
-                0xfe => {
-                    let switch: AuthSwitchRequest = packet.decode()?;
+    let host = std::env::var("HOSTNAME").or_else(|_| std::env::var("COMPUTERNAME"));
 
-                    plugin = Some(switch.plugin);
-                    let nonce = switch.data.chain(Bytes::new());
+    if let Ok(host) = host {
+        attrs.push(("_host".to_owned(), host));
+    }
 
-                    let response = switch
-                        .plugin
-                        .scramble(
-                            &mut stream,
-                            options.password.as_deref().unwrap_or_default(),
-                            &nonce,
-                        )
-                        .await?;
+    attrs.extend(options.connect_attrs.iter().cloned());
+    attrs
+}
 
-                    stream.write_packet(AuthSwitchResponse(response));
-                    stream.flush().await?;
-                }
+// exchanges packets with the server until it accepts or rejects the authentication,
+// following requests to switch the authentication method
+pub(super) async fn authenticate(
+    stream: &mut MySqlStream,
+    mut plugin: Option<AuthPlugin>,
+    mut nonce: Chain<Bytes, Bytes>,
+    password: &str,
+) -> Result<(), Error> {
+    loop {
+        let packet = stream.recv_packet().await?;
+        match packet[0] {
+            0x00 => {
+                let ok = packet.ok(stream.capabilities)?;
+                stream.session_state.apply(ok.session_state_changes);
+
+                break;
+            }
+
+            0xfe => {
+                let switch: AuthSwitchRequest = packet.decode()?;
+
+                // the server sends a fresh nonce along with the switch request
+                plugin = Some(switch.plugin);
+                nonce = switch.data.chain(Bytes::new());
+
+                let response = switch.plugin.scramble(stream, password, &nonce).await?;
 
-                id => {
-                    if let (Some(plugin), Some(password)) = (plugin, &options.password) {
-                        if plugin.handle(&mut stream, packet, password, &nonce).await? {
-                            // plugin signaled authentication is ok
-                            break;
-                        }
-
-                        // plugin signaled to continue authentication
-                    } else {
-                        return Err(err_protocol!(
-                            "unexpected packet 0x{:02x} during authentication",
-                            id
-                        ));
+                stream.write_packet(AuthSwitchResponse(response));
+                stream.flush().await?;
+            }
+
+            id => {
+                if let Some(plugin) = plugin {
+                    if plugin.handle(stream, packet, password, &nonce).await? {
+                        // plugin signaled authentication is ok
+                        break;
                     }
+
+                    // plugin signaled to continue authentication
+                } else {
+                    return Err(err_protocol!(
+                        "unexpected packet 0x{:02x} during authentication",
+                        id
+                    ));
                 }
             }
         }
-
-        Ok(stream)
     }
+
+    stream.auth_plugin = plugin;
+    stream.auth_nonce = nonce;
+
+    Ok(())
 }
 
 impl<'a> WithSocket for DoHandshake<'a> {
@@ -1,28 +1,31 @@
 use super::MySqlStream;
 use crate::connection::stream::Waiting;
+use crate::connection::{rewrite_query, CancelOnDrop};
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::ext::ustr::UStr;
 use crate::io::MySqlBufExt;
 use crate::logger::QueryLogger;
-use crate::protocol::response::Status;
+use crate::protocol::response::{EofPacket, Status};
 use crate::protocol::statement::{
-    BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
+    BinaryRow, BulkExecute, Execute as StatementExecute, Prepare, PrepareOk, StmtClose, StmtFetch,
 };
 use crate::protocol::text::{ColumnDefinition, ColumnFlags, Query, TextRow};
+use crate::protocol::{Capabilities, Packet};
 use crate::statement::{MySqlStatement, MySqlStatementMetadata};
 use crate::HashMap;
 use crate::{
-    MySql, MySqlArguments, MySqlColumn, MySqlConnection, MySqlQueryResult, MySqlRow, MySqlTypeInfo,
-    MySqlValueFormat,
+    MySql, MySqlArguments, MySqlColumn, MySqlConnection, MySqlQueryResult, MySqlResultSet,
+    MySqlRow, MySqlTypeInfo, MySqlValueFormat,
 };
+use bytes::Bytes;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, cmp, sync::Arc, time::Duration};
 
 impl MySqlConnection {
     async fn get_or_prepare<'c>(
@@ -60,7 +63,9 @@ impl MySqlConnection {
         let mut columns = Vec::new();
 
         let column_names = if ok.columns > 0 {
-            recv_result_metadata(&mut self.stream, ok.columns as usize, &mut columns).await?
+            recv_result_metadata(&mut self.stream, ok.columns as usize, &mut columns)
+                .await?
+                .0
         } else {
             Default::default()
         };
@@ -82,17 +87,40 @@ impl MySqlConnection {
         Ok((id, metadata))
     }
 
+    /// Execute statements issued by the driver itself, without passing them through the
+    /// [`QueryRewriter`](crate::connection::QueryRewriter) of the connection.
+    //
+    // boxed, as cancelling a statement in `run()` executes `KILL QUERY` with it
+    pub(crate) fn execute_internal<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            let s = self.run(sql, None, false, None).await?;
+            pin_mut!(s);
+
+            while s.try_next().await?.is_some() {}
+
+            Ok(())
+        })
+    }
+
     #[allow(clippy::needless_lifetimes)]
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         sql: &'q str,
         arguments: Option<MySqlArguments>,
         persistent: bool,
+        timeout: Option<Duration>,
     ) -> Result<impl Stream<Item = Result<Either<MySqlQueryResult, MySqlRow>, Error>> + 'e, Error>
     {
         let mut logger = QueryLogger::new(sql, self.log_settings.clone());
 
-        self.stream.wait_until_ready().await?;
+        if let Some(arguments) = &arguments {
+            self.time_zone.check_arguments(arguments)?;
+        }
+
+        self.wait_until_ready().await?;
         self.stream.waiting.push_back(Waiting::Result);
 
         Ok(Box::pin(try_stream! {
@@ -101,18 +129,27 @@ impl MySqlConnection {
             // to re-use this memory freely between result sets
             let mut columns = Arc::new(Vec::new());
 
+            let mut cancel_on_drop = CancelOnDrop::new(self);
+
+            // the timeout bounds the whole statement, including receiving all of its rows
+            let mut deadline = self.statement_deadline(timeout);
+
             let (mut column_names, format, mut needs_metadata) = if let Some(arguments) = arguments {
+                logger.set_arguments(arguments.types.len());
+
                 let (id, metadata) = self.get_or_prepare(
                     sql,
                     persistent,
                 )
-                .await?;
+                .await
+                .map_err(|e| logger.record_error(e))?;
 
                 // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
                 self.stream
                     .send_packet(StatementExecute {
                         statement: id,
                         arguments: &arguments,
+                        cursor: false,
                     })
                     .await?;
 
@@ -127,18 +164,23 @@ impl MySqlConnection {
             loop {
                 // query response is a meta-packet which may be one of:
                 //  Ok, Err, ResultSet, or (unhandled) LocalInfileRequest
-                let mut packet = self.stream.recv_packet().await?;
+                let mut packet = self
+                    .recv_response(&mut deadline)
+                    .await
+                    .map_err(|e| logger.record_error(e))?;
 
                 if packet[0] == 0x00 || packet[0] == 0xff {
                     // first packet in a query response is OK or ERR
                     // this indicates either a successful query with no rows at all or a failed query
-                    let ok = packet.ok()?;
+                    let ok = packet.ok(self.stream.capabilities)?;
+                    self.stream.session_state.apply(ok.session_state_changes);
 
                     let rows_affected = ok.affected_rows;
                     logger.increase_rows_affected(rows_affected);
                     let done = MySqlQueryResult {
                         rows_affected,
                         last_insert_id: ok.last_insert_id,
+                        out_params: None,
                     };
 
                     r#yield!(Either::Left(done));
@@ -149,6 +191,7 @@ impl MySqlConnection {
                     }
 
                     self.stream.waiting.pop_front();
+                    cancel_on_drop.disarm();
                     return Ok(());
                 }
 
@@ -157,26 +200,56 @@ impl MySqlConnection {
 
                 let num_columns = packet.get_uint_lenenc() as usize; // column count
 
-                if needs_metadata {
-                    column_names = Arc::new(recv_result_metadata(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?);
+                let metadata_status = if needs_metadata {
+                    let (names, status) = recv_result_metadata(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?;
+                    column_names = Arc::new(names);
+                    status
                 } else {
                     // next time we hit here, it'll be a new result set and we'll need the
                     // full metadata
                     needs_metadata = true;
 
-                    recv_result_columns(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?;
-                }
+                    recv_result_columns(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?
+                };
+
+                // the output parameters of a `CALL` are sent as an additional result set of a
+                // single row, flagged by the EOF packet ending its metadata; if `DEPRECATE_EOF`
+                // was negotiated, only the packet ending the result set carries the flag, so the
+                // last row is kept undecoded until then
+                let capture_out_params = matches!(format, MySqlValueFormat::Binary)
+                    && (metadata_status.contains(Status::SERVER_PS_OUT_PARAMS)
+                        || self.stream.capabilities.contains(Capabilities::DEPRECATE_EOF));
+
+                let mut last_packet: Option<Bytes> = None;
 
                 // finally, there will be none or many result-rows
                 loop {
-                    let packet = self.stream.recv_packet().await?;
+                    let packet = self.recv_response(&mut deadline).await?;
 
                     if packet[0] == 0xfe && packet.len() < 9 {
                         let eof = packet.eof(self.stream.capabilities)?;
+                        self.stream.session_state.apply(eof.session_state_changes);
+
+                        let out_params = match last_packet.take() {
+                            Some(packet)
+                                if (metadata_status | eof.status)
+                                    .contains(Status::SERVER_PS_OUT_PARAMS) =>
+                            {
+                                Some(MySqlRow {
+                                    row: Packet(packet).decode_with::<BinaryRow, _>(&columns)?.0,
+                                    format,
+                                    columns: Arc::clone(&columns),
+                                    column_names: Arc::clone(&column_names),
+                                    time_zone: self.time_zone,
+                                })
+                            }
+                            _ => None,
+                        };
 
                         r#yield!(Either::Left(MySqlQueryResult {
                             rows_affected: 0,
                             last_insert_id: 0,
+                            out_params,
                         }));
 
                         if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
@@ -186,9 +259,15 @@ impl MySqlConnection {
                         }
 
                         self.stream.waiting.pop_front();
+                        cancel_on_drop.disarm();
                         return Ok(());
                     }
 
+                    if capture_out_params {
+                        // only a reference to the packet buffer
+                        last_packet = Some(packet.0.clone());
+                    }
+
                     let row = match format {
                         MySqlValueFormat::Binary => packet.decode_with::<BinaryRow, _>(&columns)?.0,
                         MySqlValueFormat::Text => packet.decode_with::<TextRow, _>(&columns)?.0,
@@ -199,6 +278,7 @@ impl MySqlConnection {
                         format,
                         columns: Arc::clone(&columns),
                         column_names: Arc::clone(&column_names),
+                        time_zone: self.time_zone,
                     });
 
                     logger.increment_rows_returned();
@@ -210,6 +290,361 @@ impl MySqlConnection {
     }
 }
 
+impl MySqlConnection {
+    /// Execute a query string containing several `;`-separated statements, yielding the
+    /// rows and outcome of each statement as a separate [`MySqlResultSet`].
+    ///
+    /// This requires [`MySqlConnectOptions::multi_statements`] (enabled by default).
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mysql::MySqlConnection) -> sqlx::Result<()> {
+    /// use futures::TryStreamExt;
+    ///
+    /// let mut results = conn.fetch_result_sets("CREATE TABLE t (id INT); SELECT 1; SELECT 2");
+    ///
+    /// while let Some(result) = results.try_next().await? {
+    ///     println!("{} rows, {} affected", result.rows().len(), result.rows_affected());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`MySqlConnectOptions::multi_statements`]: crate::MySqlConnectOptions::multi_statements
+    pub fn fetch_result_sets<'e, 'q: 'e>(
+        &'e mut self,
+        sql: &'q str,
+    ) -> BoxStream<'e, Result<MySqlResultSet, Error>> {
+        Box::pin(try_stream! {
+            let s = self.run(sql, None, false, None).await?;
+            pin_mut!(s);
+
+            let mut rows = Vec::new();
+
+            while let Some(v) = s.try_next().await? {
+                match v {
+                    Either::Left(result) => {
+                        r#yield!(MySqlResultSet {
+                            rows: std::mem::take(&mut rows),
+                            result,
+                        });
+                    }
+
+                    Either::Right(row) => rows.push(row),
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl MySqlConnection {
+    /// Execute a query as a prepared statement with a server-side cursor, fetching its rows
+    /// from the server in batches of `batch_size` rows.
+    ///
+    /// Without a cursor, the server sends the whole result set at once, which has to be read
+    /// before the connection can be used again. With a cursor, the server materializes the
+    /// result set and only sends the next batch when it is requested, so the memory used by the
+    /// client stays bounded by the batch size however large the result set is.
+    ///
+    /// Rows which are not read before the stream is dropped stay in the cursor on the server
+    /// until the statement is executed again or closed.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mysql::MySqlConnection) -> sqlx::Result<()> {
+    /// use futures::TryStreamExt;
+    /// use sqlx::Row;
+    ///
+    /// let mut rows = conn.fetch_with_cursor(sqlx::query("SELECT id FROM events"), 1000);
+    ///
+    /// while let Some(row) = rows.try_next().await? {
+    ///     let id: i64 = row.try_get("id")?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fetch_with_cursor<'e, 'q: 'e, E: 'q>(
+        &'e mut self,
+        mut query: E,
+        batch_size: u32,
+    ) -> BoxStream<'e, Result<MySqlRow, Error>>
+    where
+        E: Execute<'q, MySql>,
+    {
+        let sql = query.sql();
+        let mut arguments = query.take_arguments();
+        let persistent = query.persistent();
+        let timeout = query.timeout();
+
+        Box::pin(try_stream! {
+            let rewriter = self.options.query_rewriter.as_ref();
+            let sql = rewrite_query(rewriter, sql, arguments.as_mut())?;
+            let arguments = arguments.unwrap_or_default();
+
+            let mut logger = QueryLogger::new(&sql, self.log_settings.clone());
+            logger.set_arguments(arguments.types.len());
+
+            self.time_zone.check_arguments(&arguments)?;
+            self.wait_until_ready().await?;
+
+            let (id, metadata) = self
+                .get_or_prepare(&sql, persistent)
+                .await
+                .map_err(|e| logger.record_error(e))?;
+
+            self.stream.waiting.push_back(Waiting::Result);
+            self.stream
+                .send_packet(StatementExecute {
+                    statement: id,
+                    arguments: &arguments,
+                    cursor: true,
+                })
+                .await?;
+
+            let mut deadline = self.statement_deadline(timeout);
+
+            let mut packet = self
+                .recv_response(&mut deadline)
+                .await
+                .map_err(|e| logger.record_error(e))?;
+
+            if packet[0] == 0x00 || packet[0] == 0xff {
+                // the statement has no result set, no cursor is opened
+                let ok = packet.ok(self.stream.capabilities)?;
+                self.stream.session_state.apply(ok.session_state_changes);
+
+                self.stream.waiting.pop_front();
+                return Ok(());
+            }
+
+            *self.stream.waiting.front_mut().unwrap() = Waiting::Row;
+
+            let num_columns = packet.get_uint_lenenc() as usize;
+            let mut columns = Vec::with_capacity(num_columns);
+
+            for ordinal in 0..num_columns {
+                columns.push(recv_next_result_column(&self.stream.recv().await?, ordinal)?);
+            }
+
+            let columns = Arc::new(columns);
+            let column_names = metadata.column_names;
+
+            // the metadata is terminated by an EOF packet which tells if a cursor was opened,
+            // even if `DEPRECATE_EOF` was negotiated
+            let packet = self.recv_response(&mut deadline).await?;
+            let mut first_row = None;
+
+            let status = if is_terminator(&packet) {
+                terminator_status(packet, self)?
+            } else {
+                first_row = Some(packet);
+                Status::empty()
+            };
+
+            if status.contains(Status::SERVER_STATUS_CURSOR_EXISTS) {
+                // the server waits for us to fetch the rows
+                self.stream.waiting.pop_front();
+
+                loop {
+                    self.stream.waiting.push_back(Waiting::Row);
+                    self.stream
+                        .send_packet(StmtFetch {
+                            statement: id,
+                            rows: batch_size,
+                        })
+                        .await?;
+
+                    loop {
+                        let packet = self.recv_response(&mut deadline).await?;
+
+                        if is_terminator(&packet) {
+                            let status = terminator_status(packet, self)?;
+                            self.stream.waiting.pop_front();
+
+                            if status.contains(Status::SERVER_STATUS_LAST_ROW_SENT)
+                                || !status.contains(Status::SERVER_STATUS_CURSOR_EXISTS)
+                            {
+                                return Ok(());
+                            }
+
+                            break;
+                        }
+
+                        logger.increment_rows_returned();
+
+                        r#yield!(MySqlRow {
+                            row: packet.decode_with::<BinaryRow, _>(&columns)?.0,
+                            format: MySqlValueFormat::Binary,
+                            columns: Arc::clone(&columns),
+                            column_names: Arc::clone(&column_names),
+                            time_zone: self.time_zone,
+                        });
+                    }
+                }
+            }
+
+            // the server did not open a cursor and sends all rows at once
+            let mut packet = match first_row {
+                Some(packet) => packet,
+
+                // the terminator of the (empty) result set
+                None if self.stream.capabilities.contains(Capabilities::DEPRECATE_EOF) => {
+                    if status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+                        *self.stream.waiting.front_mut().unwrap() = Waiting::Result;
+                    } else {
+                        self.stream.waiting.pop_front();
+                    }
+
+                    return Ok(());
+                }
+
+                // the terminator of the metadata, the rows follow
+                None => self.recv_response(&mut deadline).await?,
+            };
+
+            loop {
+                if is_terminator(&packet) {
+                    let status = terminator_status(packet, self)?;
+
+                    if status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+                        *self.stream.waiting.front_mut().unwrap() = Waiting::Result;
+                    } else {
+                        self.stream.waiting.pop_front();
+                    }
+
+                    return Ok(());
+                }
+
+                logger.increment_rows_returned();
+
+                r#yield!(MySqlRow {
+                    row: packet.decode_with::<BinaryRow, _>(&columns)?.0,
+                    format: MySqlValueFormat::Binary,
+                    columns: Arc::clone(&columns),
+                    column_names: Arc::clone(&column_names),
+                    time_zone: self.time_zone,
+                });
+
+                packet = self.recv_response(&mut deadline).await?;
+            }
+        })
+    }
+}
+
+impl MySqlConnection {
+    /// Executes a statement once for each set of arguments, e.g. to insert many rows.
+    ///
+    /// On MariaDB 10.2 or newer all sets of arguments are sent in a single round trip
+    /// (`COM_STMT_BULK_EXECUTE`), if they have the same parameter types. Otherwise the
+    /// statement is executed once per set of arguments. Rows returned by the statement
+    /// are discarded.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mysql::MySqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::Arguments;
+    /// use sqlx::mysql::MySqlArguments;
+    ///
+    /// let arguments = (1..=3).map(|id| {
+    ///     let mut arguments = MySqlArguments::default();
+    ///     arguments.add(id);
+    ///     arguments.add(format!("event {id}"));
+    ///     arguments
+    /// });
+    ///
+    /// let result = conn
+    ///     .execute_batch("INSERT INTO events (id, name) VALUES (?, ?)", arguments)
+    ///     .await?;
+    ///
+    /// assert_eq!(result.rows_affected(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_batch<I>(
+        &mut self,
+        sql: &str,
+        arguments: I,
+    ) -> Result<MySqlQueryResult, Error>
+    where
+        I: IntoIterator<Item = MySqlArguments>,
+    {
+        let arguments: Vec<MySqlArguments> = arguments.into_iter().collect();
+
+        // every set of arguments is executed with the same statement
+        let sql = rewrite_query(self.options.query_rewriter.as_ref(), sql, None)?;
+        let sql = &*sql;
+
+        for arguments in &arguments {
+            self.time_zone.check_arguments(arguments)?;
+        }
+
+        if self
+            .stream
+            .capabilities
+            .contains(Capabilities::MARIADB_STMT_BULK_OPERATIONS)
+            && BulkExecute::supports(&arguments)
+        {
+            let mut logger = QueryLogger::new(sql, self.log_settings.clone());
+
+            self.wait_until_ready().await?;
+
+            let (id, _) = self.get_or_prepare(sql, true).await?;
+
+            self.stream
+                .send_packet(BulkExecute {
+                    statement: id,
+                    arguments: &arguments,
+                })
+                .await?;
+
+            let ok = self.stream.recv_ok().await?;
+            logger.increase_rows_affected(ok.affected_rows);
+
+            return Ok(MySqlQueryResult {
+                rows_affected: ok.affected_rows,
+                last_insert_id: ok.last_insert_id,
+                out_params: None,
+            });
+        }
+
+        let mut result = MySqlQueryResult::default();
+
+        for arguments in arguments {
+            let s = self.run(sql, Some(arguments), true, None).await?;
+            pin_mut!(s);
+
+            while let Some(v) = s.try_next().await? {
+                if let Either::Left(r) = v {
+                    result.extend(Some(r));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn is_terminator(packet: &Packet<Bytes>) -> bool {
+    packet[0] == 0xfe && packet.len() < 9
+}
+
+// the terminators of the metadata and the batches of a cursor are EOF packets, or OK packets
+// if `DEPRECATE_EOF` was negotiated (depending on the server)
+fn terminator_status(packet: Packet<Bytes>, conn: &mut MySqlConnection) -> Result<Status, Error> {
+    let capabilities = conn.stream.capabilities;
+
+    let (status, session_state_changes) = if packet.len() == 5 {
+        let eof: EofPacket = packet.decode_with(capabilities)?;
+        (eof.status, eof.session_state_changes)
+    } else {
+        let ok = packet.ok(capabilities)?;
+        (ok.status, ok.session_state_changes)
+    };
+
+    conn.stream.session_state.apply(session_state_changes);
+
+    Ok(status)
+}
+
 impl<'c> Executor<'c> for &'c mut MySqlConnection {
     type Database = MySql;
 
@@ -222,11 +657,14 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         E: Execute<'q, Self::Database>,
     {
         let sql = query.sql();
-        let arguments = query.take_arguments();
+        let mut arguments = query.take_arguments();
         let persistent = query.persistent();
+        let timeout = query.timeout();
 
         Box::pin(try_stream! {
-            let s = self.run(sql, arguments, persistent).await?;
+            let rewriter = self.options.query_rewriter.as_ref();
+            let sql = rewrite_query(rewriter, sql, arguments.as_mut())?;
+            let s = self.run(&sql, arguments, persistent, timeout).await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -237,6 +675,38 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         })
     }
 
+    fn fetch_chunked<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+        batch_size: u32,
+    ) -> BoxStream<'e, Result<Vec<MySqlRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        // each batch is fetched from a server-side cursor
+        let batch_size = cmp::max(batch_size, 1);
+        let mut rows = self.fetch_with_cursor(query, batch_size);
+
+        Box::pin(try_stream! {
+            let mut batch = Vec::with_capacity(batch_size as usize);
+
+            while let Some(row) = rows.try_next().await? {
+                batch.push(row);
+
+                if batch.len() == batch_size as usize {
+                    r#yield!(std::mem::replace(&mut batch, Vec::with_capacity(batch_size as usize)));
+                }
+            }
+
+            if !batch.is_empty() {
+                r#yield!(batch);
+            }
+
+            Ok(())
+        })
+    }
+
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
         self,
         query: E,
@@ -267,9 +737,10 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         'c: 'e,
     {
         Box::pin(async move {
-            self.stream.wait_until_ready().await?;
+            self.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, true).await?;
+            let rewritten = rewrite_query(self.options.query_rewriter.as_ref(), sql, None)?;
+            let (_, metadata) = self.get_or_prepare(&rewritten, true).await?;
 
             Ok(MySqlStatement {
                 sql: Cow::Borrowed(sql),
@@ -285,7 +756,7 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         'c: 'e,
     {
         Box::pin(async move {
-            self.stream.wait_until_ready().await?;
+            self.wait_until_ready().await?;
 
             let (_, metadata) = self.get_or_prepare(sql, false).await?;
 
@@ -308,11 +779,12 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
     }
 }
 
+// returns the status of the EOF packet ending the metadata, if one was sent
 async fn recv_result_columns(
     stream: &mut MySqlStream,
     num_columns: usize,
     columns: &mut Vec<MySqlColumn>,
-) -> Result<(), Error> {
+) -> Result<Status, Error> {
     columns.clear();
     columns.reserve(num_columns);
 
@@ -321,10 +793,10 @@ async fn recv_result_columns(
     }
 
     if num_columns > 0 {
-        stream.maybe_recv_eof().await?;
+        return Ok(eof_status(stream.maybe_recv_eof().await?));
     }
 
-    Ok(())
+    Ok(Status::empty())
 }
 
 fn recv_next_result_column(def: &ColumnDefinition, ordinal: usize) -> Result<MySqlColumn, Error> {
@@ -349,7 +821,7 @@ async fn recv_result_metadata(
     stream: &mut MySqlStream,
     num_columns: usize,
     columns: &mut Vec<MySqlColumn>,
-) -> Result<HashMap<UStr, usize>, Error> {
+) -> Result<(HashMap<UStr, usize>, Status), Error> {
     // the result-set metadata is primarily a listing of each output
     // column in the result-set
 
@@ -367,7 +839,11 @@ async fn recv_result_metadata(
         columns.push(column);
     }
 
-    stream.maybe_recv_eof().await?;
+    let status = eof_status(stream.maybe_recv_eof().await?);
+
+    Ok((column_names, status))
+}
 
-    Ok(column_names)
+fn eof_status(eof: Option<EofPacket>) -> Status {
+    eof.map_or(Status::empty(), |eof| eof.status)
 }
@@ -1,19 +1,30 @@
 use std::fmt::{self, Debug, Formatter};
 
+use bytes::{Buf, Bytes};
+pub(crate) use cancel::CancelOnDrop;
+pub use cancel::MySqlCancelHandle;
 use futures_core::future::BoxFuture;
-use futures_util::FutureExt;
+use futures_util::pin_mut;
 pub(crate) use sqlx_core::connection::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 pub(crate) use stream::{MySqlStream, Waiting};
 
 use crate::common::StatementCache;
 use crate::error::Error;
+use crate::options::TimeZoneRules;
+use crate::protocol::connect::ChangeUser;
 use crate::protocol::statement::StmtClose;
-use crate::protocol::text::{Ping, Quit};
+use crate::protocol::text::{Ping, Quit, ResetConnection};
+use crate::protocol::Packet;
+use crate::rt;
 use crate::statement::MySqlStatementMetadata;
 use crate::transaction::Transaction;
-use crate::{MySql, MySqlConnectOptions};
+use crate::{MySql, MySqlConnectOptions, MySqlSessionState};
 
 mod auth;
+mod cancel;
 mod establish;
 mod executor;
 mod stream;
@@ -35,6 +46,201 @@ pub struct MySqlConnection {
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
     log_settings: LogSettings,
+
+    // the statements which configure the session after it is established or reset
+    pub(crate) init_sql: String,
+
+    // reset the session when the connection is returned to a pool
+    reset_on_release: bool,
+
+    // the options to establish a connection to cancel statements on
+    options: Arc<MySqlConnectOptions>,
+
+    // cancel statements which take longer than this
+    query_timeout: Option<Duration>,
+
+    // cancel the statement of a query which is dropped before completion
+    cancel_on_drop: bool,
+
+    // set by a dropped query (see `CancelOnDrop`)
+    cancel_pending: Arc<AtomicBool>,
+
+    // how temporal values are converted between UTC and the session time zone
+    pub(crate) time_zone: TimeZoneRules,
+}
+
+impl MySqlConnection {
+    /// The state of the session as reported by the server.
+    ///
+    /// See [`MySqlConnectOptions::track_session_state`].
+    pub fn session_state(&self) -> &MySqlSessionState {
+        &self.stream.session_state
+    }
+
+    /// The id of the connection on the server (`CONNECTION_ID()`).
+    pub fn connection_id(&self) -> u32 {
+        self.stream.connection_id
+    }
+
+    /// Returns a handle to cancel the statement executed by this connection from another task.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mysql::MySqlConnection) -> sqlx::Result<()> {
+    /// use std::time::Duration;
+    /// use sqlx::Executor;
+    ///
+    /// let handle = conn.cancel_handle();
+    ///
+    /// tokio::spawn(async move {
+    ///     tokio::time::sleep(Duration::from_secs(1)).await;
+    ///     handle.cancel().await
+    /// });
+    ///
+    /// // `SLEEP()` returns 1 when interrupted, most statements fail with an error instead
+    /// let interrupted: i64 = sqlx::query_scalar("SELECT SLEEP(10)").fetch_one(conn).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cancel_handle(&self) -> MySqlCancelHandle {
+        MySqlCancelHandle {
+            options: Arc::clone(&self.options),
+            connection_id: self.stream.connection_id,
+        }
+    }
+
+    /// Sets how long a statement may run before it is cancelled with `KILL QUERY`, overriding
+    /// [`MySqlConnectOptions::query_timeout`] for subsequent statements.
+    pub fn set_query_timeout(&mut self, timeout: Option<Duration>) {
+        self.query_timeout = timeout;
+    }
+
+    /// Resets the state of the session without re-authenticating (`COM_RESET_CONNECTION`).
+    ///
+    /// This rolls back an active transaction, drops temporary tables, releases locks and
+    /// clears user-defined variables and prepared statements. The session settings made
+    /// when the connection was established (e.g. the `sql_mode` and time zone) are restored.
+    ///
+    /// Requires MySQL 5.7.3 or MariaDB 10.2.4 or newer.
+    pub async fn reset(&mut self) -> Result<(), Error> {
+        self.wait_until_ready().await?;
+        self.stream.send_packet(ResetConnection).await?;
+        self.stream.recv_ok().await?;
+
+        // the server deallocated the prepared statements
+        self.cache_statement.clear();
+        self.transaction_depth = 0;
+
+        self.initialize().await
+    }
+
+    /// Re-authenticates the connection as another user and switches to the given default
+    /// database (`COM_CHANGE_USER`), without establishing a new connection.
+    ///
+    /// The state of the session is reset as by [`reset`](Self::reset). If the server rejects
+    /// the credentials, the connection is closed by the server and must be discarded.
+    pub async fn change_user(
+        &mut self,
+        username: &str,
+        password: Option<&str>,
+        database: Option<&str>,
+    ) -> Result<(), Error> {
+        self.wait_until_ready().await?;
+
+        let plugin = self.stream.auth_plugin;
+        let nonce = self
+            .stream
+            .auth_nonce
+            .first_ref()
+            .clone()
+            .chain(self.stream.auth_nonce.last_ref().clone());
+
+        let auth_response = if let (Some(plugin), Some(password)) = (plugin, password) {
+            Some(plugin.scramble(&mut self.stream, password, &nonce).await?)
+        } else {
+            None
+        };
+
+        self.stream
+            .send_packet(ChangeUser {
+                username,
+                auth_response: auth_response.as_deref(),
+                database,
+                collation: self.stream.collation as u8,
+                auth_plugin: plugin,
+            })
+            .await?;
+
+        establish::authenticate(
+            &mut self.stream,
+            plugin,
+            nonce,
+            password.unwrap_or_default(),
+        )
+        .await?;
+
+        // the server deallocated the prepared statements
+        self.cache_statement.clear();
+        self.transaction_depth = 0;
+
+        self.initialize().await
+    }
+
+    pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
+        if self.cancel_pending.swap(false, Ordering::AcqRel) && !self.stream.waiting.is_empty() {
+            // the statement of a dropped query is still running, interrupt it instead of
+            // waiting for all of its results
+            self.cancel_handle().cancel().await?;
+        }
+
+        self.stream.wait_until_ready().await
+    }
+
+    // the point in time at which a statement starting now is cancelled, given its timeout
+    // (or the query timeout of the connection if none is given)
+    pub(crate) fn statement_deadline(&self, timeout: Option<Duration>) -> Option<Instant> {
+        timeout
+            .or(self.query_timeout)
+            .map(|timeout| Instant::now() + timeout)
+    }
+
+    // waits for the next packet of the response to a statement, cancelling the statement
+    // once the deadline passes; the deadline is cleared then so it is only cancelled once
+    pub(crate) async fn recv_response(
+        &mut self,
+        deadline: &mut Option<Instant>,
+    ) -> Result<Packet<Bytes>, Error> {
+        let Some(until) = *deadline else {
+            return self.stream.recv_packet().await;
+        };
+
+        let handle = self.cancel_handle();
+        let recv = self.stream.recv_packet();
+        pin_mut!(recv);
+
+        match rt::timeout(until.saturating_duration_since(Instant::now()), &mut recv).await {
+            Ok(packet) => packet,
+
+            Err(_) => {
+                *deadline = None;
+                handle.cancel().await?;
+
+                // the server responds with an error once the statement is interrupted
+                recv.await
+            }
+        }
+    }
+
+    pub(crate) async fn initialize(&mut self) -> Result<(), Error> {
+        let init_sql = std::mem::take(&mut self.init_sql);
+        let result = self.execute_internal(&init_sql).await;
+        self.init_sql = init_sql;
+        result?;
+
+        // the changes made above are not considered a change of the session state
+        self.stream.session_state.reset_changed();
+
+        Ok(())
+    }
 }
 
 impl Debug for MySqlConnection {
@@ -66,7 +272,7 @@ impl Connection for MySqlConnection {
 
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
-            self.stream.wait_until_ready().await?;
+            self.wait_until_ready().await?;
             self.stream.send_packet(Ping).await?;
             self.stream.recv_ok().await?;
 
@@ -74,9 +280,18 @@ impl Connection for MySqlConnection {
         })
     }
 
+    #[doc(hidden)]
+    fn before_release(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        if self.reset_on_release {
+            Box::pin(self.reset())
+        } else {
+            self.ping()
+        }
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
-        self.stream.wait_until_ready().boxed()
+        Box::pin(self.wait_until_ready())
     }
 
     fn cached_statements_size(&self) -> usize {
@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 use std::ops::{Deref, DerefMut};
 
+use bytes::buf::Chain;
 use bytes::{Buf, Bytes};
 
 use crate::collation::{CharSet, Collation};
@@ -8,20 +9,30 @@ use crate::error::Error;
 use crate::io::MySqlBufExt;
 use crate::io::{Decode, Encode};
 use crate::net::{BufferedSocket, Socket};
+use crate::protocol::auth::AuthPlugin;
 use crate::protocol::response::{EofPacket, ErrPacket, OkPacket, Status};
 use crate::protocol::{Capabilities, Packet};
-use crate::{MySqlConnectOptions, MySqlDatabaseError};
+use crate::{MySqlConnectOptions, MySqlDatabaseError, MySqlSessionState};
 
 pub struct MySqlStream<S = Box<dyn Socket>> {
     // Wrapping the socket in `Box` allows us to unsize in-place.
     pub(crate) socket: BufferedSocket<S>,
     pub(crate) server_version: (u16, u16, u16),
+    pub(crate) connection_id: u32,
     pub(super) capabilities: Capabilities,
     pub(crate) sequence_id: u8,
     pub(crate) waiting: VecDeque<Waiting>,
     pub(crate) charset: CharSet,
     pub(crate) collation: Collation,
     pub(crate) is_tls: bool,
+    // connected through a Unix domain socket or a named pipe
+    pub(crate) is_socket: bool,
+    pub(crate) session_state: MySqlSessionState,
+
+    // the authentication method and nonce negotiated when the connection was established,
+    // used to re-authenticate with `COM_CHANGE_USER`
+    pub(crate) auth_plugin: Option<AuthPlugin>,
+    pub(crate) auth_nonce: Chain<Bytes, Bytes>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -47,25 +58,40 @@ impl<S: Socket> MySqlStream<S> {
             | Capabilities::TRANSACTIONS
             | Capabilities::SECURE_CONNECTION
             | Capabilities::PLUGIN_AUTH_LENENC_DATA
-            | Capabilities::MULTI_STATEMENTS
             | Capabilities::MULTI_RESULTS
             | Capabilities::PLUGIN_AUTH
             | Capabilities::PS_MULTI_RESULTS
+            | Capabilities::QUERY_ATTRIBUTES
+            | Capabilities::CONNECT_ATTRS
+            | Capabilities::MARIADB_STMT_BULK_OPERATIONS
             | Capabilities::SSL;
 
         if options.database.is_some() {
             capabilities |= Capabilities::CONNECT_WITH_DB;
         }
 
+        if options.multi_statements {
+            capabilities |= Capabilities::MULTI_STATEMENTS;
+        }
+
+        if options.track_session_state {
+            capabilities |= Capabilities::SESSION_TRACK;
+        }
+
         Self {
             waiting: VecDeque::new(),
             capabilities,
             server_version: (0, 0, 0),
+            connection_id: 0,
             sequence_id: 0,
             collation,
             charset,
             socket: BufferedSocket::new(socket),
             is_tls: false,
+            is_socket: options.socket.is_some() || options.pipe.is_some(),
+            session_state: MySqlSessionState::default(),
+            auth_plugin: None,
+            auth_nonce: Bytes::new().chain(Bytes::new()),
         }
     }
 
@@ -80,6 +106,7 @@ impl<S: Socket> MySqlStream<S> {
 
                 if !packet.is_empty() && packet[0] == 0xfe && packet.len() < 9 {
                     let eof = packet.eof(self.capabilities)?;
+                    self.session_state.apply(eof.session_state_changes);
 
                     if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         *self.waiting.front_mut().unwrap() = Waiting::Result;
@@ -93,7 +120,8 @@ impl<S: Socket> MySqlStream<S> {
                 let packet = self.recv_packet().await?;
 
                 if !packet.is_empty() && (packet[0] == 0x00 || packet[0] == 0xff) {
-                    let ok = packet.ok()?;
+                    let ok = packet.ok(self.capabilities)?;
+                    self.session_state.apply(ok.session_state_changes);
 
                     if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         self.waiting.pop_front();
@@ -169,7 +197,11 @@ impl<S: Socket> MySqlStream<S> {
     }
 
     pub(crate) async fn recv_ok(&mut self) -> Result<OkPacket, Error> {
-        self.recv_packet().await?.ok()
+        let mut ok = self.recv_packet().await?.ok(self.capabilities)?;
+        self.session_state
+            .apply(std::mem::take(&mut ok.session_state_changes));
+
+        Ok(ok)
     }
 
     pub(crate) async fn maybe_recv_eof(&mut self) -> Result<Option<EofPacket>, Error> {
@@ -196,12 +228,17 @@ impl<S: Socket> MySqlStream<S> {
         MySqlStream {
             socket: self.socket.boxed(),
             server_version: self.server_version,
+            connection_id: self.connection_id,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
             charset: self.charset,
             collation: self.collation,
             is_tls: self.is_tls,
+            is_socket: self.is_socket,
+            session_state: self.session_state,
+            auth_plugin: self.auth_plugin,
+            auth_nonce: self.auth_nonce,
         }
     }
 }
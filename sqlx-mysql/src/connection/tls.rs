@@ -5,16 +5,20 @@ use crate::net::tls::TlsConfig;
 use crate::net::{tls, BufferedSocket, Socket, WithSocket};
 use crate::protocol::connect::SslRequest;
 use crate::protocol::Capabilities;
-use crate::{MySqlConnectOptions, MySqlSslMode};
+use crate::{MySqlConnectOptions, MySqlSessionState, MySqlSslMode};
+use bytes::{Buf, Bytes};
 use std::collections::VecDeque;
 
 struct MapStream {
     server_version: (u16, u16, u16),
+    connection_id: u32,
     capabilities: Capabilities,
     sequence_id: u8,
     waiting: VecDeque<Waiting>,
     charset: CharSet,
     collation: Collation,
+    is_socket: bool,
+    session_state: MySqlSessionState,
 }
 
 pub(super) async fn maybe_upgrade<S: Socket>(
@@ -81,11 +85,14 @@ pub(super) async fn maybe_upgrade<S: Socket>(
         tls_config,
         MapStream {
             server_version: stream.server_version,
+            connection_id: stream.connection_id,
             capabilities: stream.capabilities,
             sequence_id: stream.sequence_id,
             waiting: stream.waiting,
             charset: stream.charset,
             collation: stream.collation,
+            is_socket: stream.is_socket,
+            session_state: stream.session_state,
         },
     )
     .await
@@ -98,12 +105,18 @@ impl WithSocket for MapStream {
         MySqlStream {
             socket: BufferedSocket::new(Box::new(socket)),
             server_version: self.server_version,
+            connection_id: self.connection_id,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
             charset: self.charset,
             collation: self.collation,
             is_tls: true,
+            is_socket: self.is_socket,
+            session_state: self.session_state,
+            // authentication starts after the upgrade
+            auth_plugin: None,
+            auth_nonce: Bytes::new().chain(Bytes::new()),
         }
     }
 }
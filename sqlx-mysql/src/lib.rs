@@ -11,6 +11,7 @@ pub(crate) use sqlx_core::driver_prelude::*;
 pub mod any;
 
 mod arguments;
+mod binlog;
 mod collation;
 mod column;
 mod connection;
@@ -20,7 +21,10 @@ mod io;
 mod options;
 mod protocol;
 mod query_result;
+mod result_set;
+mod router;
 mod row;
+mod session_state;
 mod statement;
 mod transaction;
 mod type_info;
@@ -33,14 +37,21 @@ mod migrate;
 #[cfg(feature = "migrate")]
 mod testing;
 
-pub use arguments::MySqlArguments;
+pub use arguments::{MySqlArguments, MySqlQueryAttributes};
+pub use binlog::{
+    MySqlBinlogEvent, MySqlBinlogEventData, MySqlBinlogJson, MySqlBinlogOptions, MySqlBinlogRow,
+    MySqlBinlogRows, MySqlBinlogRowsKind, MySqlBinlogStream, MySqlBinlogTable, MySqlBinlogValue,
+};
 pub use column::MySqlColumn;
-pub use connection::MySqlConnection;
+pub use connection::{MySqlCancelHandle, MySqlConnection};
 pub use database::MySql;
 pub use error::MySqlDatabaseError;
-pub use options::{MySqlConnectOptions, MySqlSslMode};
+pub use options::{MySqlConnectOptions, MySqlDatetimeMode, MySqlSslMode};
 pub use query_result::MySqlQueryResult;
+pub use result_set::MySqlResultSet;
+pub use router::MySqlRouter;
 pub use row::MySqlRow;
+pub use session_state::MySqlSessionState;
 pub use statement::MySqlStatement;
 pub use transaction::MySqlTransactionManager;
 pub use type_info::MySqlTypeInfo;
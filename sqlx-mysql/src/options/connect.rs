@@ -1,10 +1,12 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, QueryEventHandler, QueryRewriter};
 use crate::error::Error;
-use crate::executor::Executor;
-use crate::{MySqlConnectOptions, MySqlConnection};
+use crate::{MySql, MySqlConnectOptions, MySqlConnection};
 use futures_core::future::BoxFuture;
+use futures_io::{AsyncRead, AsyncWrite};
 use log::LevelFilter;
+use sqlx_core::net::StreamSocket;
 use sqlx_core::Url;
+use std::sync::Arc;
 use std::time::Duration;
 
 impl ConnectOptions for MySqlConnectOptions {
@@ -19,51 +21,9 @@ impl ConnectOptions for MySqlConnectOptions {
         Self::Connection: Sized,
     {
         Box::pin(async move {
-            let mut conn = MySqlConnection::establish(self).await?;
+            let conn = MySqlConnection::establish(self).await?;
 
-            // After the connection is established, we initialize by configuring a few
-            // connection parameters
-
-            // https://mariadb.com/kb/en/sql-mode/
-
-            // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
-            //                   This means that "A" || "B" can be used in place of CONCAT("A", "B").
-
-            // NO_ENGINE_SUBSTITUTION - If not set, if the available storage engine specified by a CREATE TABLE is
-            //                          not available, a warning is given and the default storage
-            //                          engine is used instead.
-
-            // NO_ZERO_DATE - Don't allow '0000-00-00'. This is invalid in Rust.
-
-            // NO_ZERO_IN_DATE - Don't allow 'YYYY-00-00'. This is invalid in Rust.
-
-            // --
-
-            // Setting the time zone allows us to assume that the output
-            // from a TIMESTAMP field is UTC
-
-            // --
-
-            // https://mathiasbynens.be/notes/mysql-utf8mb4
-
-            let mut options = String::new();
-            if self.pipes_as_concat {
-                options.push_str(r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',PIPES_AS_CONCAT,NO_ENGINE_SUBSTITUTION')),"#);
-            } else {
-                options.push_str(
-                    r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',NO_ENGINE_SUBSTITUTION')),"#,
-                );
-            }
-            options.push_str(r#"time_zone='+00:00',"#);
-            options.push_str(&format!(
-                r#"NAMES {} COLLATE {};"#,
-                conn.stream.charset.as_str(),
-                conn.stream.collation.as_str()
-            ));
-
-            conn.execute(&*options).await?;
-
-            Ok(conn)
+            self.init_connection(conn).await
         })
     }
 
@@ -76,4 +36,94 @@ impl ConnectOptions for MySqlConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn event_handler(mut self, handler: Arc<dyn QueryEventHandler>) -> Self {
+        self.log_settings.event_handler(handler);
+        self
+    }
+
+    fn query_rewriter(mut self, rewriter: Arc<dyn QueryRewriter<MySql>>) -> Self {
+        self.query_rewriter = Some(rewriter);
+        self
+    }
+}
+
+impl MySqlConnectOptions {
+    /// Establish a connection over an already-connected stream instead of opening a socket to
+    /// [`host`][Self::host] and [`port`][Self::port], [`socket`][Self::socket] or
+    /// [`pipe`][Self::pipe].
+    ///
+    /// This allows connecting through custom transports (e.g. an SSH tunnel or a proxy), or
+    /// over an in-memory duplex stream in tests. TLS is still negotiated according to
+    /// [`ssl_mode`][Self::ssl_mode].
+    ///
+    /// Note that [`MySqlConnection::cancel`] still connects to the configured address to send
+    /// `KILL QUERY`.
+    pub async fn connect_with_stream<S>(&self, stream: S) -> Result<MySqlConnection, Error>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + Sync + 'static,
+    {
+        let conn =
+            MySqlConnection::establish_with_socket(self, Box::new(StreamSocket::new(stream)))
+                .await?;
+
+        self.init_connection(conn).await
+    }
+
+    async fn init_connection(&self, mut conn: MySqlConnection) -> Result<MySqlConnection, Error> {
+        // After the connection is established, we initialize by configuring a few
+        // connection parameters
+
+        // https://mariadb.com/kb/en/sql-mode/
+
+        // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
+        //                   This means that "A" || "B" can be used in place of CONCAT("A", "B").
+
+        // NO_ENGINE_SUBSTITUTION - If not set, if the available storage engine specified by a CREATE TABLE is
+        //                          not available, a warning is given and the default storage
+        //                          engine is used instead.
+
+        // NO_ZERO_DATE - Don't allow '0000-00-00'. This is invalid in Rust.
+
+        // NO_ZERO_IN_DATE - Don't allow 'YYYY-00-00'. This is invalid in Rust.
+
+        // --
+
+        // Setting the time zone determines how the output from a TIMESTAMP field is
+        // converted, by default it is UTC (see `TimeZoneRules`)
+
+        // --
+
+        // https://mathiasbynens.be/notes/mysql-utf8mb4
+
+        let mut options = String::new();
+        if self.pipes_as_concat {
+            options.push_str(r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',PIPES_AS_CONCAT,NO_ENGINE_SUBSTITUTION')),"#);
+        } else {
+            options.push_str(
+                r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',NO_ENGINE_SUBSTITUTION')),"#,
+            );
+        }
+        options.push_str(&format!(
+            r#"time_zone='{}',"#,
+            self.time_zone.replace('\'', "''")
+        ));
+        if self.track_session_state {
+            options.push_str(r#"session_track_state_change=ON,"#);
+            options.push_str(r#"session_track_transaction_info='CHARACTERISTICS',"#);
+        }
+        if self.track_gtids {
+            options.push_str(r#"session_track_gtids='OWN_GTID',"#);
+        }
+        options.push_str(&format!(
+            r#"NAMES {} COLLATE {};"#,
+            conn.stream.charset.as_str(),
+            conn.stream.collation.as_str()
+        ));
+
+        conn.init_sql = options;
+        conn.initialize().await?;
+
+        Ok(conn)
+    }
 }
@@ -0,0 +1,149 @@
+use crate::error::{BoxDynError, Error};
+use crate::protocol::text::ColumnType;
+use crate::MySqlArguments;
+use std::str::FromStr;
+
+/// How `DATETIME` values, which are stored without a time zone, are decoded as date-times
+/// with a time zone (`chrono::DateTime<Utc>`, `chrono::DateTime<Local>` and
+/// `time::OffsetDateTime`).
+///
+/// It is used by the [`datetime_mode`](super::MySqlConnectOptions::datetime_mode) method.
+/// `DATETIME` values can always be decoded as naive date-times (`chrono::NaiveDateTime` and
+/// `time::PrimitiveDateTime`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MySqlDatetimeMode {
+    /// `DATETIME` values can only be decoded as naive date-times.
+    Naive,
+
+    /// `DATETIME` values are date-times in UTC.
+    ///
+    /// This is the default if `datetime_mode` is not specified.
+    #[default]
+    Utc,
+
+    /// `DATETIME` values are date-times in the time zone of the session (like `TIMESTAMP`
+    /// values), which must be a fixed offset (see
+    /// [`time_zone`](super::MySqlConnectOptions::time_zone)).
+    Session,
+}
+
+impl FromStr for MySqlDatetimeMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "naive" => MySqlDatetimeMode::Naive,
+            "utc" => MySqlDatetimeMode::Utc,
+            "session" => MySqlDatetimeMode::Session,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {:?} for `datetime_mode`", s).into(),
+                ));
+            }
+        })
+    }
+}
+
+// the rules to convert the temporal values of a connection between UTC and the
+// session time zone
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TimeZoneRules {
+    // the offset of the session time zone in seconds east of UTC, if it is fixed
+    session_offset: Option<i32>,
+    datetime_mode: MySqlDatetimeMode,
+}
+
+impl Default for TimeZoneRules {
+    fn default() -> Self {
+        Self {
+            session_offset: Some(0),
+            datetime_mode: MySqlDatetimeMode::Utc,
+        }
+    }
+}
+
+impl TimeZoneRules {
+    pub(crate) fn new(time_zone: &str, datetime_mode: MySqlDatetimeMode) -> Self {
+        Self {
+            session_offset: parse_offset(time_zone),
+            datetime_mode,
+        }
+    }
+
+    /// The offset in seconds east of UTC of the values of a column of the given type,
+    /// when decoded as a date-time with a time zone.
+    #[cfg_attr(not(any(feature = "chrono", feature = "time")), allow(unused))]
+    pub(crate) fn utc_offset(&self, ty: ColumnType) -> Result<i32, BoxDynError> {
+        let session_offset = || {
+            self.session_offset.ok_or_else(|| {
+                BoxDynError::from(
+                    "the session time zone is not a fixed offset, decode as a naive date-time \
+                     or convert with `CONVERT_TZ()`",
+                )
+            })
+        };
+
+        match (ty, self.datetime_mode) {
+            (ColumnType::Timestamp, _) => session_offset(),
+            (ColumnType::Datetime, MySqlDatetimeMode::Session) => session_offset(),
+            (ColumnType::Datetime, MySqlDatetimeMode::Utc) => Ok(0),
+
+            (ColumnType::Datetime, MySqlDatetimeMode::Naive) => {
+                Err("DATETIME values can only be decoded as naive date-times \
+                 (see `MySqlConnectOptions::datetime_mode`)"
+                    .into())
+            }
+
+            _ => Ok(0),
+        }
+    }
+
+    /// Date-times with a time zone are sent as UTC date-times, which are interpreted by the
+    /// server in the session time zone.
+    pub(crate) fn check_arguments(&self, arguments: &MySqlArguments) -> Result<(), Error> {
+        let zoned = arguments
+            .types
+            .iter()
+            .any(|ty| ty.r#type == ColumnType::Timestamp);
+
+        if zoned && self.session_offset != Some(0) {
+            return Err(Error::Encode(
+                "date-times with a time zone can only be bound if the session time zone is UTC, \
+                 bind a naive date-time instead"
+                    .into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// `+HH:MM` or `-HH:MM`, or a name of UTC
+fn parse_offset(time_zone: &str) -> Option<i32> {
+    if matches!(time_zone, "UTC" | "Etc/UTC" | "GMT") {
+        return Some(0);
+    }
+
+    let (sign, offset) = match time_zone.as_bytes().first()? {
+        b'+' => (1, &time_zone[1..]),
+        b'-' => (-1, &time_zone[1..]),
+        _ => return None,
+    };
+
+    let (hours, minutes) = offset.split_once(':')?;
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+#[test]
+fn test_parse_offset() {
+    assert_eq!(parse_offset("+00:00"), Some(0));
+    assert_eq!(parse_offset("+05:30"), Some(19800));
+    assert_eq!(parse_offset("-08:00"), Some(-28800));
+    assert_eq!(parse_offset("UTC"), Some(0));
+    assert_eq!(parse_offset("Europe/Berlin"), None);
+    assert_eq!(parse_offset("SYSTEM"), None);
+}
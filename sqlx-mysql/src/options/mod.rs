@@ -1,10 +1,16 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 mod connect;
+mod datetime_mode;
 mod parse;
 mod ssl_mode;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::connection::{LogSettings, QueryRewriter};
+use crate::{net::tls::CertificateInput, MySql};
+pub use datetime_mode::MySqlDatetimeMode;
+pub(crate) use datetime_mode::TimeZoneRules;
 pub use ssl_mode::MySqlSslMode;
 
 /// Options and flags which can be used to configure a MySQL connection.
@@ -29,6 +35,11 @@ pub use ssl_mode::MySqlSslMode;
 /// | `ssl-ca` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
 /// | `socket` | `None` | Path to the unix domain socket, which will be used instead of TCP if set. |
+/// | `pipe` | `None` | Name of the Windows named pipe, which will be used instead of TCP if set. |
+/// | `multi-statements` | `true` | Whether a single query string may contain several `;`-separated statements. |
+/// | `time-zone` | `+00:00` | The time zone of the session, see [`time_zone`](Self::time_zone). |
+/// | `datetime-mode` | `utc` | How `DATETIME` values are decoded as date-times with a time zone. See [`MySqlDatetimeMode`]. |
+/// | `reset-on-release` | `false` | Whether the session is reset when the connection is returned to a pool. |
 ///
 /// # Example
 ///
@@ -64,6 +75,7 @@ pub struct MySqlConnectOptions {
     pub(crate) host: String,
     pub(crate) port: u16,
     pub(crate) socket: Option<PathBuf>,
+    pub(crate) pipe: Option<PathBuf>,
     pub(crate) username: String,
     pub(crate) password: Option<String>,
     pub(crate) database: Option<String>,
@@ -75,7 +87,17 @@ pub struct MySqlConnectOptions {
     pub(crate) charset: String,
     pub(crate) collation: Option<String>,
     pub(crate) log_settings: LogSettings,
+    pub(crate) query_rewriter: Option<Arc<dyn QueryRewriter<MySql>>>,
     pub(crate) pipes_as_concat: bool,
+    pub(crate) multi_statements: bool,
+    pub(crate) track_session_state: bool,
+    pub(crate) track_gtids: bool,
+    pub(crate) reset_on_release: bool,
+    pub(crate) connect_attrs: Vec<(String, String)>,
+    pub(crate) query_timeout: Option<Duration>,
+    pub(crate) cancel_on_drop: bool,
+    pub(crate) time_zone: String,
+    pub(crate) datetime_mode: MySqlDatetimeMode,
 }
 
 impl Default for MySqlConnectOptions {
@@ -91,6 +113,7 @@ impl MySqlConnectOptions {
             port: 3306,
             host: String::from("localhost"),
             socket: None,
+            pipe: None,
             username: String::from("root"),
             password: None,
             database: None,
@@ -102,7 +125,17 @@ impl MySqlConnectOptions {
             ssl_client_key: None,
             statement_cache_capacity: 100,
             log_settings: Default::default(),
+            query_rewriter: None,
             pipes_as_concat: true,
+            multi_statements: true,
+            track_session_state: false,
+            track_gtids: false,
+            reset_on_release: false,
+            connect_attrs: Vec::new(),
+            query_timeout: None,
+            cancel_on_drop: false,
+            time_zone: String::from("+00:00"),
+            datetime_mode: MySqlDatetimeMode::Utc,
         }
     }
 
@@ -126,12 +159,32 @@ impl MySqlConnectOptions {
     /// Pass a path to a Unix socket. This changes the connection stream from
     /// TCP to UDS.
     ///
+    /// Accounts using socket authentication (`auth_socket` in MySQL, `unix_socket` in MariaDB)
+    /// can connect through the socket without a password, if the [username](Self::username)
+    /// matches the name of the operating system user running the application.
+    ///
     /// By default set to `None`.
     pub fn socket(mut self, path: impl AsRef<Path>) -> Self {
         self.socket = Some(path.as_ref().to_path_buf());
         self
     }
 
+    /// Sets the name of a Windows named pipe to connect to instead of TCP, e.g. `MySQL` for a
+    /// server started with `--named-pipe`. Names not starting with `\\` are opened
+    /// as `\\.\pipe\{name}`.
+    ///
+    /// Named pipes are only supported on Windows with the Tokio runtime.
+    ///
+    /// By default set to `None`.
+    pub fn pipe(mut self, name: &str) -> Self {
+        self.pipe = Some(if name.starts_with(r"\\") {
+            PathBuf::from(name)
+        } else {
+            PathBuf::from(format!(r"\\.\pipe\{name}"))
+        });
+        self
+    }
+
     /// Sets the username to connect as.
     pub fn username(mut self, username: &str) -> Self {
         self.username = username.to_owned();
@@ -139,6 +192,9 @@ impl MySqlConnectOptions {
     }
 
     /// Sets the password to connect with.
+    ///
+    /// Accounts authenticated by the server with PAM or LDAP request the password in
+    /// cleartext (`mysql_clear_password`), which is only sent over TLS or a local socket.
     pub fn password(mut self, password: &str) -> Self {
         self.password = Some(password.to_owned());
         self
@@ -266,4 +322,114 @@ impl MySqlConnectOptions {
         self.pipes_as_concat = flag_val;
         self
     }
+
+    /// Sets whether the server should accept several `;`-separated statements in
+    /// a single (unprepared) query string (`CLIENT_MULTI_STATEMENTS`).
+    ///
+    /// This is enabled by default as it is required to run migration scripts. Disabling it
+    /// makes the server reject such query strings which can limit the damage of SQL injection.
+    ///
+    /// The results of each statement can be read separately with
+    /// [`MySqlConnection::fetch_result_sets`](crate::MySqlConnection::fetch_result_sets).
+    pub fn multi_statements(mut self, enable: bool) -> Self {
+        self.multi_statements = enable;
+        self
+    }
+
+    /// Sets whether the server reports changes to the state of the session, such as the default
+    /// schema, system variables and the transaction state (`CLIENT_SESSION_TRACK`).
+    ///
+    /// The reported state is available through
+    /// [`MySqlConnection::session_state`](crate::MySqlConnection::session_state).
+    ///
+    /// The default is `false`. Requires MySQL 5.7 or MariaDB 10.2 or newer.
+    pub fn track_session_state(mut self, enable: bool) -> Self {
+        self.track_session_state = enable;
+        self
+    }
+
+    /// Sets whether the server reports the GTIDs of the transactions committed by the session
+    /// (`session_track_gtids = OWN_GTID`), for read-your-writes routing to replicas.
+    ///
+    /// Implies [`track_session_state`](Self::track_session_state). Not supported by MariaDB.
+    ///
+    /// The default is `false`.
+    pub fn track_gtids(mut self, enable: bool) -> Self {
+        self.track_gtids = enable;
+        if enable {
+            self.track_session_state = true;
+        }
+        self
+    }
+
+    /// Sets whether the session is reset with [`MySqlConnection::reset`](crate::MySqlConnection::reset)
+    /// (`COM_RESET_CONNECTION`) when the connection is returned to a pool.
+    ///
+    /// This keeps temporary tables, user-defined variables, locks and session settings from
+    /// leaking between users of the pool, at the cost of re-preparing statements after
+    /// each checkin. The reset replaces the ping which tests the connection on release.
+    ///
+    /// The default is `false`. Requires MySQL 5.7.3 or MariaDB 10.2.4 or newer.
+    pub fn reset_on_release(mut self, enable: bool) -> Self {
+        self.reset_on_release = enable;
+        self
+    }
+
+    /// Adds a connection attribute which is sent to the server when the connection is
+    /// established, to identify it in `performance_schema.session_connect_attrs`.
+    ///
+    /// The attributes `_client_name`, `_client_version`, `_os`, `_platform`, `_pid` and
+    /// `program_name` (and `_host` if the host name is known) are always sent. Attributes
+    /// starting with an underscore are reserved for the client.
+    pub fn connect_attr(mut self, key: &str, value: &str) -> Self {
+        self.connect_attrs.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Sets how long a statement may run before it is cancelled with `KILL QUERY`, see
+    /// [`MySqlCancelHandle`](crate::MySqlCancelHandle). The statement then fails with an error.
+    ///
+    /// The time is measured from when the statement is sent until all of its rows were
+    /// received, so a statement which keeps returning rows is cancelled as well.
+    ///
+    /// Cancelling establishes a new connection with these options. The timeout can be changed
+    /// for a connection with [`MySqlConnection::set_query_timeout`](crate::MySqlConnection::set_query_timeout).
+    ///
+    /// The default is `None`.
+    pub fn query_timeout(mut self, timeout: Duration) -> Self {
+        self.query_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether the statement of a query which is dropped before all of its results were
+    /// received (e.g. when a `fetch` future is cancelled by a timeout) is cancelled with
+    /// `KILL QUERY`, instead of waiting for it to complete before the connection is used again.
+    ///
+    /// The default is `false`.
+    pub fn cancel_on_drop(mut self, enable: bool) -> Self {
+        self.cancel_on_drop = enable;
+        self
+    }
+
+    /// Sets the time zone of the session (`time_zone`), as an offset (e.g. `+02:00`) or
+    /// a named time zone if the time zone tables of the server are loaded.
+    ///
+    /// The server converts `TIMESTAMP` values from and to the session time zone. Decoding them
+    /// as date-times with a time zone (e.g. `chrono::DateTime<Utc>`) requires an offset,
+    /// and binding date-times with a time zone requires a session time zone of UTC,
+    /// as they are sent as UTC date-times. Naive date-times are always passed through as is.
+    ///
+    /// The default is `+00:00` (UTC).
+    pub fn time_zone(mut self, time_zone: &str) -> Self {
+        self.time_zone = time_zone.to_owned();
+        self
+    }
+
+    /// Sets how `DATETIME` values are decoded as date-times with a time zone.
+    ///
+    /// The default is [`MySqlDatetimeMode::Utc`].
+    pub fn datetime_mode(mut self, mode: MySqlDatetimeMode) -> Self {
+        self.datetime_mode = mode;
+        self
+    }
 }
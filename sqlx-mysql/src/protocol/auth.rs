@@ -7,6 +7,10 @@ pub enum AuthPlugin {
     MySqlNativePassword,
     CachingSha2Password,
     Sha256Password,
+    Ed25519,
+    AuthSocket,
+    UnixSocket,
+    ClearPassword,
 }
 
 impl AuthPlugin {
@@ -15,6 +19,10 @@ impl AuthPlugin {
             AuthPlugin::MySqlNativePassword => "mysql_native_password",
             AuthPlugin::CachingSha2Password => "caching_sha2_password",
             AuthPlugin::Sha256Password => "sha256_password",
+            AuthPlugin::Ed25519 => "client_ed25519",
+            AuthPlugin::AuthSocket => "auth_socket",
+            AuthPlugin::UnixSocket => "unix_socket",
+            AuthPlugin::ClearPassword => "mysql_clear_password",
         }
     }
 }
@@ -27,6 +35,10 @@ impl FromStr for AuthPlugin {
             "mysql_native_password" => Ok(AuthPlugin::MySqlNativePassword),
             "caching_sha2_password" => Ok(AuthPlugin::CachingSha2Password),
             "sha256_password" => Ok(AuthPlugin::Sha256Password),
+            "client_ed25519" => Ok(AuthPlugin::Ed25519),
+            "auth_socket" => Ok(AuthPlugin::AuthSocket),
+            "unix_socket" => Ok(AuthPlugin::UnixSocket),
+            "mysql_clear_password" => Ok(AuthPlugin::ClearPassword),
 
             _ => Err(err_protocol!("unknown authentication plugin: {}", s)),
         }
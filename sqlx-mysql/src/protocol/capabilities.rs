@@ -72,6 +72,9 @@ bitflags::bitflags! {
         // Client no longer needs EOF_Packet and will use OK_Packet instead.
         const DEPRECATE_EOF = (1 << 24);
 
+        // Client supports query attributes in COM_QUERY and COM_STMT_EXECUTE
+        const QUERY_ATTRIBUTES = (1 << 27);
+
         // Support ZSTD protocol compression
         const ZSTD_COMPRESSION_ALGORITHM = (1 << 26);
 
@@ -83,5 +86,8 @@ bitflags::bitflags! {
 
         // Don't reset the options after an unsuccessful connect
         const REMEMBER_OPTIONS = (1 << 31);
+
+        // MariaDB: the server supports COM_STMT_BULK_EXECUTE
+        const MARIADB_STMT_BULK_OPERATIONS = (1 << 34);
     }
 }
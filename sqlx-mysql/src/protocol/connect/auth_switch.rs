@@ -26,15 +26,36 @@ impl Decode<'_> for AuthSwitchRequest {
 
         let plugin = buf.get_str_nul()?.parse()?;
 
-        // See: https://github.com/mysql/mysql-server/blob/ea7d2e2d16ac03afdd9cb72a972a95981107bf51/sql/auth/sha2_password.cc#L942
-        if buf.len() != 21 {
-            return Err(err_protocol!(
-                "expected 21 bytes but found {} bytes",
-                buf.len()
-            ));
-        }
-        let data = buf.get_bytes(20);
-        buf.advance(1); // NUL-terminator
+        let data = match plugin {
+            // See: https://github.com/MariaDB/server/blob/10.11/plugin/auth_ed25519/server_ed25519.c
+            AuthPlugin::Ed25519 => {
+                if buf.len() != 32 {
+                    return Err(err_protocol!(
+                        "expected 32 bytes but found {} bytes",
+                        buf.len()
+                    ));
+                }
+
+                buf.get_bytes(32)
+            }
+
+            // socket authentication does not use the data
+            AuthPlugin::AuthSocket | AuthPlugin::UnixSocket => buf.split_to(buf.len()),
+
+            // See: https://github.com/mysql/mysql-server/blob/ea7d2e2d16ac03afdd9cb72a972a95981107bf51/sql/auth/sha2_password.cc#L942
+            _ => {
+                if buf.len() != 21 {
+                    return Err(err_protocol!(
+                        "expected 21 bytes but found {} bytes",
+                        buf.len()
+                    ));
+                }
+                let data = buf.get_bytes(20);
+                buf.advance(1); // NUL-terminator
+
+                data
+            }
+        };
 
         Ok(Self { plugin, data })
     }
@@ -48,3 +69,24 @@ impl Encode<'_, Capabilities> for AuthSwitchResponse {
         buf.extend_from_slice(&self.0);
     }
 }
+
+#[test]
+fn test_decode_auth_switch_client_ed25519() {
+    const AUTH_SWITCH_ED25519: &[u8] = b"\xfeclient_ed25519\x00\x8d\x1e\x0f\x9f\x05\x80\xd8\x9b\x3b\x45\x11\x54\x73\xa1\x4c\x2d\x6e\x2b\x21\x4b\x19\x04\x3a\x16\x41\x7d\x28\x5e\x12\x6f\x27\x01";
+
+    let p = AuthSwitchRequest::decode(AUTH_SWITCH_ED25519.into()).unwrap();
+
+    assert!(matches!(p.plugin, AuthPlugin::Ed25519));
+    assert_eq!(p.data.len(), 32);
+    assert_eq!(&p.data[..4], b"\x8d\x1e\x0f\x9f");
+}
+
+#[test]
+fn test_decode_auth_switch_unix_socket() {
+    const AUTH_SWITCH_UNIX_SOCKET: &[u8] = b"\xfeunix_socket\x00";
+
+    let p = AuthSwitchRequest::decode(AUTH_SWITCH_UNIX_SOCKET.into()).unwrap();
+
+    assert!(matches!(p.plugin, AuthPlugin::UnixSocket));
+    assert!(p.data.is_empty());
+}
@@ -0,0 +1,77 @@
+use crate::io::{BufMutExt, Encode};
+use crate::protocol::auth::AuthPlugin;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_com_change_user.html
+// https://mariadb.com/kb/en/com_change_user/
+
+#[derive(Debug)]
+pub struct ChangeUser<'a> {
+    /// Name of the SQL account to switch to
+    pub username: &'a str,
+
+    /// Opaque authentication response
+    pub auth_response: Option<&'a [u8]>,
+
+    pub database: Option<&'a str>,
+
+    /// Collation for the connection
+    pub collation: u8,
+
+    /// Authentication method used by the client
+    pub auth_plugin: Option<AuthPlugin>,
+}
+
+impl Encode<'_, Capabilities> for ChangeUser<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, capabilities: Capabilities) {
+        buf.push(0x11); // COM_CHANGE_USER
+
+        buf.put_str_nul(self.username);
+
+        if capabilities.contains(Capabilities::SECURE_CONNECTION) {
+            let response = self.auth_response.unwrap_or_default();
+
+            buf.push(response.len() as u8);
+            buf.extend(response);
+        } else {
+            buf.push(0);
+        }
+
+        buf.put_str_nul(self.database.unwrap_or_default());
+
+        if capabilities.contains(Capabilities::PROTOCOL_41) {
+            buf.extend(&u16::from(self.collation).to_le_bytes());
+        }
+
+        if capabilities.contains(Capabilities::PLUGIN_AUTH) {
+            buf.put_str_nul(self.auth_plugin.map(AuthPlugin::name).unwrap_or_default());
+        }
+
+        if capabilities.contains(Capabilities::CONNECT_ATTRS) {
+            // the attributes sent in the handshake are kept
+            buf.push(0);
+        }
+    }
+}
+
+#[test]
+fn test_encode_change_user() {
+    let mut buf = Vec::new();
+
+    ChangeUser {
+        username: "root",
+        auth_response: Some(&[1, 2, 3]),
+        database: Some("db"),
+        collation: 45,
+        auth_plugin: Some(AuthPlugin::MySqlNativePassword),
+    }
+    .encode_with(
+        &mut buf,
+        Capabilities::PROTOCOL_41 | Capabilities::SECURE_CONNECTION | Capabilities::PLUGIN_AUTH,
+    );
+
+    assert_eq!(
+        buf,
+        b"\x11root\x00\x03\x01\x02\x03db\x00\x2d\x00mysql_native_password\x00"
+    );
+}
@@ -178,7 +178,8 @@ fn test_decode_handshake_mariadb_10_4_7() {
             | Capabilities::CAN_HANDLE_EXPIRED_PASSWORDS
             | Capabilities::SESSION_TRACK
             | Capabilities::DEPRECATE_EOF
-            | Capabilities::REMEMBER_OPTIONS,
+            | Capabilities::REMEMBER_OPTIONS
+            | Capabilities::MARIADB_STMT_BULK_OPERATIONS,
     );
 
     assert!(p.server_capabilities.is_empty());
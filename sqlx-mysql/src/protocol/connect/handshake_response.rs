@@ -25,6 +25,9 @@ pub struct HandshakeResponse<'a> {
 
     /// Opaque authentication response
     pub auth_response: Option<&'a [u8]>,
+
+    /// Key/value pairs identifying the client, see `performance_schema.session_connect_attrs`
+    pub connect_attrs: &'a [(String, String)],
 }
 
 impl Encode<'_, Capabilities> for HandshakeResponse<'_> {
@@ -69,5 +72,37 @@ impl Encode<'_, Capabilities> for HandshakeResponse<'_> {
                 buf.push(0);
             }
         }
+
+        if capabilities.contains(Capabilities::CONNECT_ATTRS) {
+            let mut attrs = Vec::new();
+
+            for (key, value) in self.connect_attrs {
+                attrs.put_str_lenenc(key);
+                attrs.put_str_lenenc(value);
+            }
+
+            buf.put_bytes_lenenc(&attrs);
+        }
     }
 }
+
+#[test]
+fn test_encode_handshake_response_with_connect_attrs() {
+    let mut buf = Vec::new();
+
+    HandshakeResponse {
+        database: None,
+        max_packet_size: 1024,
+        collation: 45,
+        username: "root",
+        auth_plugin: None,
+        auth_response: None,
+        connect_attrs: &[("_client_name".to_owned(), "sqlx".to_owned())],
+    }
+    .encode_with(
+        &mut buf,
+        Capabilities::PROTOCOL_41 | Capabilities::CONNECT_ATTRS,
+    );
+
+    assert!(buf.ends_with(b"root\x00\x00\x12\x0c_client_name\x04sqlx"));
+}
@@ -56,17 +56,18 @@ impl Packet<Bytes> {
         T::decode_with(self.0, context)
     }
 
-    pub(crate) fn ok(self) -> Result<OkPacket, Error> {
-        self.decode()
+    pub(crate) fn ok(self, capabilities: Capabilities) -> Result<OkPacket, Error> {
+        self.decode_with(capabilities)
     }
 
     pub(crate) fn eof(self, capabilities: Capabilities) -> Result<EofPacket, Error> {
         if capabilities.contains(Capabilities::DEPRECATE_EOF) {
-            let ok = self.ok()?;
+            let ok = self.ok(capabilities)?;
 
             Ok(EofPacket {
                 warnings: ok.warnings,
                 status: ok.status,
+                session_state_changes: ok.session_state_changes,
             })
         } else {
             self.decode_with(capabilities)
@@ -0,0 +1,137 @@
+use crate::error::Error;
+use crate::io::Encode;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_binlog_dump_gtid.html
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) struct BinlogDumpFlags: u16 {
+        // Return an EOF packet instead of blocking once the end of the binlog is reached
+        const NON_BLOCK = 0x01;
+
+        const THROUGH_POSITION = 0x02;
+
+        // The GTID set in the command is used to locate the first event to send
+        const THROUGH_GTID = 0x04;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct BinlogDumpGtid<'a> {
+    pub(crate) flags: BinlogDumpFlags,
+    pub(crate) server_id: u32,
+    pub(crate) gtid_set: &'a GtidSet,
+}
+
+impl Encode<'_, Capabilities> for BinlogDumpGtid<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.push(0x1e); // COM_BINLOG_DUMP_GTID
+        buf.extend(
+            &(self.flags | BinlogDumpFlags::THROUGH_GTID)
+                .bits()
+                .to_le_bytes(),
+        );
+        buf.extend(&self.server_id.to_le_bytes());
+
+        // the binlog file name is left empty as the position is determined by the GTID set
+        buf.extend(&0_u32.to_le_bytes()); // binlog-filename-len: int<4>
+        buf.extend(&4_u64.to_le_bytes()); // binlog-pos: int<8>
+
+        let mut data = Vec::new();
+        self.gtid_set.encode(&mut data);
+
+        buf.extend(&(data.len() as u32).to_le_bytes()); // data-size: int<4>
+        buf.extend(data);
+    }
+}
+
+/// A set of global transaction identifiers such as
+/// `3E11FA47-71CA-11E1-9E33-C80AA9429562:1-5:11, 4A12...:1-3`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct GtidSet {
+    sets: Vec<(Sid, Vec<Interval>)>,
+}
+
+// the UUID of the source server of a transaction
+type Sid = [u8; 16];
+
+// a half-open interval of transaction numbers
+type Interval = (u64, u64);
+
+impl GtidSet {
+    pub(crate) fn parse(s: &str) -> Result<Self, Error> {
+        let mut sets = Vec::new();
+
+        for set in s.split(',').map(str::trim).filter(|set| !set.is_empty()) {
+            let mut parts = set.split(':');
+
+            let uuid = parts.next().unwrap_or_default().replace('-', "");
+            let mut sid = [0_u8; 16];
+
+            hex::decode_to_slice(uuid.trim(), &mut sid).map_err(|err| {
+                Error::Configuration(format!("invalid GTID set {:?}: {}", s, err).into())
+            })?;
+
+            let mut intervals = Vec::new();
+
+            for interval in parts {
+                let parse = |n: &str| {
+                    n.trim().parse::<u64>().map_err(|err| {
+                        Error::Configuration(format!("invalid GTID set {:?}: {}", s, err).into())
+                    })
+                };
+
+                let (start, end) = match interval.split_once('-') {
+                    Some((start, end)) => (parse(start)?, parse(end)?),
+                    None => {
+                        let n = parse(interval)?;
+                        (n, n)
+                    }
+                };
+
+                // intervals are sent as [start, end)
+                intervals.push((start, end + 1));
+            }
+
+            sets.push((sid, intervals));
+        }
+
+        Ok(Self { sets })
+    }
+
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend(&(self.sets.len() as u64).to_le_bytes()); // n_sids: int<8>
+
+        for (sid, intervals) in &self.sets {
+            buf.extend(sid);
+            buf.extend(&(intervals.len() as u64).to_le_bytes()); // n_intervals: int<8>
+
+            for (start, end) in intervals {
+                buf.extend(&start.to_le_bytes());
+                buf.extend(&end.to_le_bytes());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_encode_gtid_set() {
+    let set = GtidSet::parse("3E11FA47-71CA-11E1-9E33-C80AA9429562:1-5:11").unwrap();
+
+    let mut buf = Vec::new();
+    set.encode(&mut buf);
+
+    assert_eq!(buf.len(), 8 + 16 + 8 + 2 * 16);
+    assert_eq!(&buf[8..12], b"\x3e\x11\xfa\x47");
+    assert_eq!(&buf[24..32], &2_u64.to_le_bytes());
+    assert_eq!(&buf[32..40], &1_u64.to_le_bytes());
+    assert_eq!(&buf[40..48], &6_u64.to_le_bytes());
+    assert_eq!(&buf[48..56], &11_u64.to_le_bytes());
+    assert_eq!(&buf[56..64], &12_u64.to_le_bytes());
+}
+
+#[test]
+fn test_parse_empty_gtid_set() {
+    assert_eq!(GtidSet::parse("").unwrap(), GtidSet::default());
+}
@@ -2,7 +2,7 @@ use bytes::{Buf, Bytes};
 
 use crate::error::Error;
 use crate::io::Decode;
-use crate::protocol::response::Status;
+use crate::protocol::response::{SessionStateChange, Status};
 use crate::protocol::Capabilities;
 
 /// Marks the end of a result set, returning status and warnings.
@@ -15,6 +15,9 @@ use crate::protocol::Capabilities;
 pub struct EofPacket {
     pub warnings: u16,
     pub status: Status,
+
+    // only sent in place of an EOF packet by newer servers (see `Packet::eof`)
+    pub session_state_changes: Vec<SessionStateChange>,
 }
 
 impl Decode<'_, Capabilities> for EofPacket {
@@ -30,6 +33,10 @@ impl Decode<'_, Capabilities> for EofPacket {
         let warnings = buf.get_u16_le();
         let status = Status::from_bits_truncate(buf.get_u16_le());
 
-        Ok(Self { status, warnings })
+        Ok(Self {
+            status,
+            warnings,
+            session_state_changes: Vec::new(),
+        })
     }
 }
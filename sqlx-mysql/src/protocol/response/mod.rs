@@ -10,5 +10,5 @@ mod status;
 
 pub use eof::EofPacket;
 pub use err::ErrPacket;
-pub use ok::OkPacket;
+pub use ok::{OkPacket, SessionStateChange};
 pub use status::Status;
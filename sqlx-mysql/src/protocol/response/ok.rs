@@ -4,6 +4,7 @@ use crate::error::Error;
 use crate::io::Decode;
 use crate::io::MySqlBufExt;
 use crate::protocol::response::Status;
+use crate::protocol::Capabilities;
 
 /// Indicates successful completion of a previous command sent by the client.
 #[derive(Debug)]
@@ -12,10 +13,23 @@ pub struct OkPacket {
     pub last_insert_id: u64,
     pub status: Status,
     pub warnings: u16,
+    pub session_state_changes: Vec<SessionStateChange>,
 }
 
-impl Decode<'_> for OkPacket {
-    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/mysql__com_8h.html#a0cc26d2d2a0da58b2d3d1fdeb4d2e1dc
+// https://mariadb.com/kb/en/ok_packet/#session-change-type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionStateChange {
+    SystemVariable { name: String, value: String },
+    Schema(String),
+    StateChange,
+    Gtids(String),
+    TransactionCharacteristics(String),
+    TransactionState(String),
+}
+
+impl Decode<'_, Capabilities> for OkPacket {
+    fn decode_with(mut buf: Bytes, capabilities: Capabilities) -> Result<Self, Error> {
         let header = buf.get_u8();
         if header != 0 && header != 0xfe {
             return Err(err_protocol!(
@@ -29,11 +43,50 @@ impl Decode<'_> for OkPacket {
         let status = Status::from_bits_truncate(buf.get_u16_le());
         let warnings = buf.get_u16_le();
 
+        let mut session_state_changes = Vec::new();
+
+        if capabilities.contains(Capabilities::SESSION_TRACK) && buf.has_remaining() {
+            let _info = buf.get_bytes_lenenc();
+
+            if status.contains(Status::SERVER_SESSION_STATE_CHANGED) && buf.has_remaining() {
+                let mut changes = buf.get_bytes_lenenc();
+
+                while changes.has_remaining() {
+                    let r#type = changes.get_u8();
+                    let mut data = changes.get_bytes_lenenc();
+
+                    session_state_changes.push(match r#type {
+                        0 => SessionStateChange::SystemVariable {
+                            name: data.get_str_lenenc()?,
+                            value: data.get_str_lenenc()?,
+                        },
+
+                        1 => SessionStateChange::Schema(data.get_str_lenenc()?),
+
+                        2 => SessionStateChange::StateChange,
+
+                        3 => {
+                            let _encoding = data.get_u8();
+                            SessionStateChange::Gtids(data.get_str_lenenc()?)
+                        }
+
+                        4 => SessionStateChange::TransactionCharacteristics(data.get_str_lenenc()?),
+
+                        5 => SessionStateChange::TransactionState(data.get_str_lenenc()?),
+
+                        // ignore types added by future server versions
+                        _ => continue,
+                    });
+                }
+            }
+        }
+
         Ok(Self {
             affected_rows,
             last_insert_id,
             status,
             warnings,
+            session_state_changes,
         })
     }
 }
@@ -42,7 +95,7 @@ impl Decode<'_> for OkPacket {
 fn test_decode_ok_packet() {
     const DATA: &[u8] = b"\x00\x00\x00\x02@\x00\x00";
 
-    let p = OkPacket::decode(DATA.into()).unwrap();
+    let p = OkPacket::decode_with(DATA.into(), Capabilities::empty()).unwrap();
 
     assert_eq!(p.affected_rows, 0);
     assert_eq!(p.last_insert_id, 0);
@@ -50,3 +103,24 @@ fn test_decode_ok_packet() {
     assert!(p.status.contains(Status::SERVER_STATUS_AUTOCOMMIT));
     assert!(p.status.contains(Status::SERVER_SESSION_STATE_CHANGED));
 }
+
+#[test]
+fn test_decode_ok_packet_with_session_state() {
+    // response to `USE test` followed by `SET autocommit = 0`
+    const DATA: &[u8] =
+        b"\x00\x00\x00\x02@\x00\x00\x00\x1c\x01\x05\x04test\x00\x0f\nautocommit\x03OFF\x02\x02\x011";
+
+    let p = OkPacket::decode_with(DATA.into(), Capabilities::SESSION_TRACK).unwrap();
+
+    assert_eq!(
+        p.session_state_changes,
+        vec![
+            SessionStateChange::Schema("test".into()),
+            SessionStateChange::SystemVariable {
+                name: "autocommit".into(),
+                value: "OFF".into()
+            },
+            SessionStateChange::StateChange,
+        ]
+    );
+}
@@ -0,0 +1,129 @@
+use crate::io::Encode;
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::protocol::Capabilities;
+use crate::MySqlArguments;
+
+// https://mariadb.com/kb/en/com_stmt_bulk_execute/
+
+const SEND_TYPES_TO_SERVER: u16 = 128;
+
+const INDICATOR_NONE: u8 = 0;
+const INDICATOR_NULL: u8 = 1;
+
+#[derive(Debug)]
+pub struct BulkExecute<'q> {
+    pub statement: u32,
+
+    // the sets of arguments, all with the same number and types of parameters
+    pub arguments: &'q [MySqlArguments],
+}
+
+impl BulkExecute<'_> {
+    /// Returns `true` if the sets of arguments can be sent in a single bulk execution,
+    /// which requires all of them to have the same parameter types.
+    pub fn supports(arguments: &[MySqlArguments]) -> bool {
+        let Some((first, rest)) = arguments.split_first() else {
+            return false;
+        };
+
+        !first.types.is_empty()
+            && rest.iter().all(|arguments| {
+                arguments.types.len() == first.types.len()
+                    && arguments
+                        .types
+                        .iter()
+                        .zip(&first.types)
+                        .all(|(a, b)| a.r#type == b.r#type && is_unsigned(a) == is_unsigned(b))
+            })
+    }
+}
+
+impl<'q> Encode<'_, Capabilities> for BulkExecute<'q> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.push(0xfa); // COM_STMT_BULK_EXECUTE
+        buf.extend(&self.statement.to_le_bytes());
+        buf.extend(&SEND_TYPES_TO_SERVER.to_le_bytes());
+
+        let Some(first) = self.arguments.first() else {
+            return;
+        };
+
+        for ty in &first.types {
+            buf.push(ty.r#type as u8);
+            buf.push(if is_unsigned(ty) { 0x80 } else { 0 });
+        }
+
+        for arguments in self.arguments {
+            let mut values = &arguments.values[..];
+
+            for (index, ty) in arguments.types.iter().enumerate() {
+                if arguments.null_bitmap[index / 8] & (1 << (index % 8)) as u8 != 0 {
+                    buf.push(INDICATOR_NULL);
+                    continue;
+                }
+
+                let len = value_len(ty.r#type, values);
+
+                buf.push(INDICATOR_NONE);
+                buf.extend(&values[..len]);
+
+                values = &values[len..];
+            }
+        }
+    }
+}
+
+fn is_unsigned(ty: &crate::MySqlTypeInfo) -> bool {
+    ty.flags.contains(ColumnFlags::UNSIGNED)
+}
+
+// the size of the next value in the binary protocol, including its length prefix
+fn value_len(ty: ColumnType, values: &[u8]) -> usize {
+    match ty {
+        ColumnType::LongLong | ColumnType::Double => 8,
+        ColumnType::Long | ColumnType::Int24 | ColumnType::Float => 4,
+        ColumnType::Short | ColumnType::Year => 2,
+        ColumnType::Tiny => 1,
+        ColumnType::Null => 0,
+
+        ColumnType::Time | ColumnType::Timestamp | ColumnType::Date | ColumnType::Datetime => {
+            values[0] as usize + 1
+        }
+
+        // strings, blobs, decimals, JSON and geometries are length-encoded
+        _ => match values[0] {
+            0xfc => 3 + u16::from_le_bytes([values[1], values[2]]) as usize,
+            0xfd => 4 + u32::from_le_bytes([values[1], values[2], values[3], 0]) as usize,
+            0xfe => 9 + u64::from_le_bytes(values[1..9].try_into().unwrap()) as usize,
+            len => 1 + len as usize,
+        },
+    }
+}
+
+#[test]
+fn test_encode_bulk_execute() {
+    use crate::arguments::MySqlArguments;
+
+    let mut a = MySqlArguments::default();
+    a.add(1_i32);
+    a.add("ab");
+
+    let mut b = MySqlArguments::default();
+    b.add(2_i32);
+    b.add(None::<&str>);
+
+    let arguments = [a, b];
+    assert!(BulkExecute::supports(&arguments));
+
+    let mut buf = Vec::new();
+    BulkExecute {
+        statement: 1,
+        arguments: &arguments,
+    }
+    .encode_with(&mut buf, Capabilities::empty());
+
+    assert_eq!(
+        buf,
+        b"\xfa\x01\x00\x00\x00\x80\x00\x03\x00\xfd\x00\x00\x01\x00\x00\x00\x00\x02ab\x00\x02\x00\x00\x00\x01"
+    );
+}
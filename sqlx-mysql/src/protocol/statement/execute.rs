@@ -1,4 +1,5 @@
 use crate::io::Encode;
+use crate::io::MySqlBufMutExt;
 use crate::protocol::text::ColumnFlags;
 use crate::protocol::Capabilities;
 use crate::MySqlArguments;
@@ -9,13 +10,20 @@ use crate::MySqlArguments;
 pub struct Execute<'q> {
     pub statement: u32,
     pub arguments: &'q MySqlArguments,
+
+    // open a read-only cursor to fetch the rows with COM_STMT_FETCH
+    pub cursor: bool,
 }
 
 impl<'q> Encode<'_, Capabilities> for Execute<'q> {
-    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+    fn encode_with(&self, buf: &mut Vec<u8>, capabilities: Capabilities) {
+        if capabilities.contains(Capabilities::QUERY_ATTRIBUTES) {
+            return self.encode_with_attributes(buf);
+        }
+
         buf.push(0x17); // COM_STMT_EXECUTE
         buf.extend(&self.statement.to_le_bytes());
-        buf.push(0); // NO_CURSOR
+        buf.push(self.cursor_type());
         buf.extend(&1_u32.to_le_bytes()); // iterations (always 1): int<4>
 
         if !self.arguments.types.is_empty() {
@@ -36,3 +44,102 @@ impl<'q> Encode<'_, Capabilities> for Execute<'q> {
         }
     }
 }
+
+impl Execute<'_> {
+    fn cursor_type(&self) -> u8 {
+        if self.cursor {
+            0x01 // CURSOR_TYPE_READ_ONLY
+        } else {
+            0x00 // CURSOR_TYPE_NO_CURSOR
+        }
+    }
+
+    // with CLIENT_QUERY_ATTRIBUTES, the parameters are followed by the query attributes
+    // which are sent as named parameters
+    fn encode_with_attributes(&self, buf: &mut Vec<u8>) {
+        let params = self.arguments.types.len();
+        let attributes = &self.arguments.attributes;
+        let count = params + attributes.len();
+
+        buf.push(0x17); // COM_STMT_EXECUTE
+        buf.extend(&self.statement.to_le_bytes());
+        // PARAMETER_COUNT_AVAILABLE
+        buf.push(self.cursor_type() | if count > 0 { 0x08 } else { 0 });
+        buf.extend(&1_u32.to_le_bytes()); // iterations (always 1): int<4>
+
+        if count == 0 {
+            return;
+        }
+
+        buf.put_uint_lenenc(count as u64); // parameter_count: int<lenenc>
+
+        let mut null_bitmap = self.arguments.null_bitmap.clone();
+        null_bitmap.resize(count.div_ceil(8), 0);
+
+        for (i, attribute) in attributes.iter().enumerate() {
+            if attribute.value.is_none() {
+                let index = params + i;
+                null_bitmap[index / 8] |= (1 << (index % 8)) as u8;
+            }
+        }
+
+        buf.extend(&*null_bitmap);
+        buf.push(1); // send type to server
+
+        for ty in &self.arguments.types {
+            buf.push(ty.r#type as u8);
+
+            buf.push(if ty.flags.contains(ColumnFlags::UNSIGNED) {
+                0x80
+            } else {
+                0
+            });
+
+            buf.put_str_lenenc(""); // parameter_name: string<lenenc>
+        }
+
+        for attribute in attributes {
+            buf.push(attribute.type_info.r#type as u8);
+
+            buf.push(
+                if attribute.type_info.flags.contains(ColumnFlags::UNSIGNED) {
+                    0x80
+                } else {
+                    0
+                },
+            );
+
+            buf.put_str_lenenc(&attribute.name);
+        }
+
+        buf.extend(&*self.arguments.values);
+
+        for attribute in attributes {
+            if let Some(value) = &attribute.value {
+                buf.extend(value);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_encode_execute_with_attributes() {
+    use crate::arguments::MySqlArguments;
+
+    let mut arguments = MySqlArguments::default();
+    arguments.add(1_i32);
+    arguments.add_attribute("id", "ab");
+
+    let mut buf = Vec::new();
+    Execute {
+        statement: 1,
+        arguments: &arguments,
+        cursor: false,
+    }
+    .encode_with(&mut buf, Capabilities::QUERY_ATTRIBUTES);
+
+    assert_eq!(
+        buf,
+        b"\x17\x01\x00\x00\x00\x08\x01\x00\x00\x00\x02\x00\x01\x03\x00\x00\xfd\x00\x02id\x01\x00\x00\x00\x02ab"
+    );
+}
@@ -7,8 +7,14 @@ use crate::protocol::Capabilities;
 pub(crate) struct Query<'q>(pub(crate) &'q str);
 
 impl Encode<'_, Capabilities> for Query<'_> {
-    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+    fn encode_with(&self, buf: &mut Vec<u8>, capabilities: Capabilities) {
         buf.push(0x03); // COM_QUERY
+
+        if capabilities.contains(Capabilities::QUERY_ATTRIBUTES) {
+            buf.push(0); // parameter_count: int<lenenc>
+            buf.push(1); // parameter_set_count (always 1): int<lenenc>
+        }
+
         buf.extend(self.0.as_bytes())
     }
 }
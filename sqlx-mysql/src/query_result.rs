@@ -1,9 +1,12 @@
 use std::iter::{Extend, IntoIterator};
 
+use crate::MySqlRow;
+
 #[derive(Debug, Default)]
 pub struct MySqlQueryResult {
     pub(super) rows_affected: u64,
     pub(super) last_insert_id: u64,
+    pub(super) out_params: Option<MySqlRow>,
 }
 
 impl MySqlQueryResult {
@@ -14,6 +17,37 @@ impl MySqlQueryResult {
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    /// The values of the `OUT` and `INOUT` parameters of a stored procedure, if the
+    /// statement was a `CALL` executed as a prepared statement.
+    ///
+    /// The parameters are returned in declaration order as the columns of a single row.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mysql::MySqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::Row;
+    ///
+    /// // CREATE PROCEDURE add_one(IN x INT, OUT y INT) SET y = x + 1
+    /// let result = sqlx::query("CALL add_one(?, ?)")
+    ///     .bind(41_i32)
+    ///     .bind(None::<i32>)
+    ///     .execute(conn)
+    ///     .await?;
+    ///
+    /// let y: i32 = result.out_params().unwrap().try_get(0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn out_params(&self) -> Option<&MySqlRow> {
+        self.out_params.as_ref()
+    }
+
+    /// Takes the values of the `OUT` and `INOUT` parameters of a stored procedure.
+    ///
+    /// See [`out_params`](Self::out_params).
+    pub fn take_out_params(&mut self) -> Option<MySqlRow> {
+        self.out_params.take()
+    }
 }
 
 impl Extend<MySqlQueryResult> for MySqlQueryResult {
@@ -21,6 +55,10 @@ impl Extend<MySqlQueryResult> for MySqlQueryResult {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
             self.last_insert_id = elem.last_insert_id;
+
+            if elem.out_params.is_some() {
+                self.out_params = elem.out_params;
+            }
         }
     }
 }
@@ -0,0 +1,33 @@
+use crate::{MySqlQueryResult, MySqlRow};
+
+/// The rows and the outcome of a single statement executed as part of a query
+/// string containing several `;`-separated statements.
+///
+/// See [`MySqlConnection::fetch_result_sets`](crate::MySqlConnection::fetch_result_sets).
+#[derive(Debug, Default)]
+pub struct MySqlResultSet {
+    pub(crate) rows: Vec<MySqlRow>,
+    pub(crate) result: MySqlQueryResult,
+}
+
+impl MySqlResultSet {
+    /// The rows returned by the statement, if any.
+    pub fn rows(&self) -> &[MySqlRow] {
+        &self.rows
+    }
+
+    /// Consumes the result set, returning the rows of the statement.
+    pub fn into_rows(self) -> Vec<MySqlRow> {
+        self.rows
+    }
+
+    /// The number of rows affected by the statement.
+    pub fn rows_affected(&self) -> u64 {
+        self.result.rows_affected()
+    }
+
+    /// The `LAST_INSERT_ID()` after the statement was executed.
+    pub fn last_insert_id(&self) -> u64 {
+        self.result.last_insert_id()
+    }
+}
@@ -0,0 +1,293 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+
+use crate::describe::Describe;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::pool::PoolConnection;
+use crate::query_scalar::query_scalar;
+use crate::{
+    MySql, MySqlConnection, MySqlPool, MySqlQueryResult, MySqlRow, MySqlStatement, MySqlTypeInfo,
+};
+
+/// Routes statements to the pool of a primary server or the pools of its replicas.
+///
+/// Statements executed through `&MySqlRouter` are routed by their first keyword: `SELECT`,
+/// `SHOW`, `DESCRIBE` and `EXPLAIN` statements without a locking clause (`FOR UPDATE`,
+/// `FOR SHARE`, ..) are executed on a replica, all other statements on the primary.
+/// The replicas are used in turn.
+///
+/// Statements which depend on the state of the session (e.g. `SELECT LAST_INSERT_ID()`)
+/// and transactions must be executed on a connection from [`acquire_writer`](Self::acquire_writer).
+///
+/// # Read-your-writes consistency
+///
+/// Replicas apply the transactions of the primary with a delay. With
+/// [`read_your_writes`](Self::read_your_writes), the router records the GTIDs of the
+/// transactions committed through it and waits for a replica to apply them before reading
+/// from it, falling back to the primary if the replica does not catch up in time. This requires
+/// GTID-based replication and the primary pool to be configured with
+/// [`MySqlConnectOptions::track_gtids`](crate::MySqlConnectOptions::track_gtids).
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use sqlx::mysql::{MySqlConnectOptions, MySqlPool, MySqlRouter};
+///
+/// let primary: MySqlConnectOptions = "mysql://primary/db".parse()?;
+///
+/// let router = MySqlRouter::new(MySqlPool::connect_with(primary.track_gtids(true)).await?)
+///     .replica(MySqlPool::connect("mysql://replica-1/db").await?)
+///     .replica(MySqlPool::connect("mysql://replica-2/db").await?)
+///     .read_your_writes(true);
+///
+/// // executed on the primary
+/// sqlx::query("INSERT INTO events (name) VALUES (?)")
+///     .bind("created")
+///     .execute(&router)
+///     .await?;
+///
+/// // executed on a replica once it applied the insert
+/// let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events")
+///     .fetch_one(&router)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MySqlRouter {
+    primary: MySqlPool,
+    replicas: Vec<MySqlPool>,
+    read_your_writes: bool,
+    gtid_wait_timeout: Duration,
+    shared: Arc<RouterShared>,
+}
+
+#[derive(Debug, Default)]
+struct RouterShared {
+    next_replica: AtomicUsize,
+    last_gtids: Mutex<Option<String>>,
+}
+
+impl MySqlRouter {
+    /// Creates a router for the pool of the primary server, without replicas.
+    pub fn new(primary: MySqlPool) -> Self {
+        Self {
+            primary,
+            replicas: Vec::new(),
+            read_your_writes: false,
+            gtid_wait_timeout: Duration::from_secs(1),
+            shared: Arc::default(),
+        }
+    }
+
+    /// Adds the pool of a replica to read from.
+    pub fn replica(mut self, replica: MySqlPool) -> Self {
+        self.replicas.push(replica);
+        self
+    }
+
+    /// Sets whether reads wait for a replica to apply the transactions committed through
+    /// the router (`WAIT_FOR_EXECUTED_GTID_SET`), see the [type documentation](Self).
+    ///
+    /// The default is `false`. Not supported by MariaDB.
+    pub fn read_your_writes(mut self, enable: bool) -> Self {
+        self.read_your_writes = enable;
+        self
+    }
+
+    /// Sets how long a read waits for a replica to catch up before it falls back to the
+    /// primary, if [`read_your_writes`](Self::read_your_writes) is enabled.
+    ///
+    /// The default is 1 second.
+    pub fn gtid_wait_timeout(mut self, timeout: Duration) -> Self {
+        self.gtid_wait_timeout = timeout;
+        self
+    }
+
+    /// The pool of the primary server.
+    pub fn primary(&self) -> &MySqlPool {
+        &self.primary
+    }
+
+    /// The pools of the replicas.
+    pub fn replicas(&self) -> &[MySqlPool] {
+        &self.replicas
+    }
+
+    /// Acquires a connection to the primary server.
+    ///
+    /// Call [`record_writes`](Self::record_writes) after committing on the connection to make
+    /// the changes visible to subsequent reads with [`read_your_writes`](Self::read_your_writes).
+    pub async fn acquire_writer(&self) -> Result<PoolConnection<MySql>, Error> {
+        self.primary.acquire().await
+    }
+
+    /// Acquires a connection to the next replica, or to the primary server if there are
+    /// no replicas or the replica did not apply the recorded writes in time.
+    pub async fn acquire_reader(&self) -> Result<PoolConnection<MySql>, Error> {
+        if self.replicas.is_empty() {
+            return self.primary.acquire().await;
+        }
+
+        let next = self.shared.next_replica.fetch_add(1, Ordering::Relaxed);
+        let mut conn = self.replicas[next % self.replicas.len()].acquire().await?;
+
+        if let Some(gtids) = self.last_gtids().filter(|_| self.read_your_writes) {
+            // returns 1 if the timeout was exceeded
+            let timed_out: i64 = query_scalar("SELECT WAIT_FOR_EXECUTED_GTID_SET(?, ?)")
+                .bind(gtids)
+                .bind(self.gtid_wait_timeout.as_secs_f64())
+                .fetch_one(&mut *conn)
+                .await?;
+
+            if timed_out != 0 {
+                return self.primary.acquire().await;
+            }
+        }
+
+        Ok(conn)
+    }
+
+    /// Records the GTIDs of the transaction last committed on a connection to the primary
+    /// server, so that subsequent reads wait for them to be applied by the replica.
+    ///
+    /// This is done automatically for statements executed through the router.
+    pub fn record_writes(&self, conn: &MySqlConnection) {
+        if let Some(gtids) = conn.session_state().last_gtids() {
+            *self.shared.last_gtids.lock().unwrap() = Some(gtids.to_owned());
+        }
+    }
+
+    /// The GTIDs of the transaction last recorded by [`record_writes`](Self::record_writes).
+    pub fn last_gtids(&self) -> Option<String> {
+        self.shared.last_gtids.lock().unwrap().clone()
+    }
+
+    async fn acquire_for(&self, sql: &str) -> Result<PoolConnection<MySql>, Error> {
+        if is_read_only(sql) {
+            self.acquire_reader().await
+        } else {
+            self.acquire_writer().await
+        }
+    }
+}
+
+impl<'r> Executor<'r> for &'_ MySqlRouter {
+    type Database = MySql;
+
+    fn fetch_many<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<MySqlQueryResult, MySqlRow>, Error>>
+    where
+        E: Execute<'q, MySql>,
+    {
+        let router = self.clone();
+
+        Box::pin(try_stream! {
+            let read = is_read_only(query.sql());
+            let mut conn = router.acquire_for(query.sql()).await?;
+            let mut s = conn.fetch_many(query);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(v);
+            }
+
+            drop(s);
+
+            if !read {
+                router.record_writes(&conn);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<MySqlRow>, Error>>
+    where
+        E: Execute<'q, MySql>,
+    {
+        let router = self.clone();
+
+        Box::pin(async move {
+            let read = is_read_only(query.sql());
+            let mut conn = router.acquire_for(query.sql()).await?;
+            let row = conn.fetch_optional(query).await?;
+
+            if !read {
+                router.record_writes(&conn);
+            }
+
+            Ok(row)
+        })
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [MySqlTypeInfo],
+    ) -> BoxFuture<'e, Result<MySqlStatement<'q>, Error>> {
+        let router = self.clone();
+
+        Box::pin(async move {
+            router
+                .acquire_for(sql)
+                .await?
+                .prepare_with(sql, parameters)
+                .await
+        })
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<MySql>, Error>> {
+        let router = self.clone();
+
+        Box::pin(async move { router.acquire_writer().await?.describe(sql).await })
+    }
+}
+
+// reads are statements which start with a read-only keyword and do not lock rows
+fn is_read_only(sql: &str) -> bool {
+    let sql = sql.trim_start();
+    let keyword = sql
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default();
+
+    let is_read = ["SELECT", "SHOW", "DESCRIBE", "DESC", "EXPLAIN"]
+        .iter()
+        .any(|read| keyword.eq_ignore_ascii_case(read));
+
+    if !is_read {
+        return false;
+    }
+
+    let sql = sql.to_ascii_uppercase();
+
+    !["FOR UPDATE", "FOR SHARE", "LOCK IN SHARE MODE", "INTO "]
+        .iter()
+        .any(|clause| sql.contains(clause))
+}
+
+#[test]
+fn test_is_read_only() {
+    assert!(is_read_only("SELECT * FROM users"));
+    assert!(is_read_only("  select id from users where id = ?"));
+    assert!(is_read_only("SHOW TABLES"));
+    assert!(is_read_only("EXPLAIN SELECT 1"));
+
+    assert!(!is_read_only("INSERT INTO users (id) VALUES (1)"));
+    assert!(!is_read_only("SELECT * FROM users FOR UPDATE"));
+    assert!(!is_read_only("SELECT id INTO @id FROM users"));
+    assert!(!is_read_only("SELECTION"));
+    assert!(!is_read_only("CALL refresh()"));
+}
@@ -5,6 +5,7 @@ pub(crate) use sqlx_core::row::*;
 use crate::column::ColumnIndex;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
+use crate::options::TimeZoneRules;
 use crate::HashMap;
 use crate::{protocol, MySql, MySqlColumn, MySqlValueFormat, MySqlValueRef};
 
@@ -15,6 +16,7 @@ pub struct MySqlRow {
     pub(crate) format: MySqlValueFormat,
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) time_zone: TimeZoneRules,
 }
 
 impl Row for MySqlRow {
@@ -36,6 +38,7 @@ impl Row for MySqlRow {
             format: self.format,
             row: Some(&self.row.storage),
             type_info: column.type_info.clone(),
+            time_zone: self.time_zone,
             value,
         })
     }
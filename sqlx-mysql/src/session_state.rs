@@ -0,0 +1,110 @@
+use crate::protocol::response::SessionStateChange;
+use crate::HashMap;
+
+/// The state of the session of a connection as reported by the server.
+///
+/// The server only reports changes to the session state if enabled with
+/// [`MySqlConnectOptions::track_session_state`](crate::MySqlConnectOptions::track_session_state).
+/// Which system variables are tracked is determined by `session_track_system_variables`.
+///
+/// Changes made while the connection is established (e.g. setting the `sql_mode` and time zone)
+/// are not counted by [`is_changed`](Self::is_changed).
+#[derive(Debug, Clone, Default)]
+pub struct MySqlSessionState {
+    schema: Option<String>,
+    system_variables: HashMap<String, String>,
+    gtids: Option<String>,
+    transaction_state: Option<String>,
+    transaction_characteristics: Option<String>,
+    changed: bool,
+}
+
+impl MySqlSessionState {
+    /// The current default schema, if it was changed since the connection was established
+    /// (e.g. by `USE db`).
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// The value of a tracked system variable, if it was changed during the session.
+    pub fn system_variable(&self, name: &str) -> Option<&str> {
+        self.system_variables.get(name).map(String::as_str)
+    }
+
+    /// All tracked system variables which were changed during the session and their
+    /// current values.
+    pub fn system_variables(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.system_variables
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The GTIDs of the transactions last committed by this session.
+    ///
+    /// Requires [`MySqlConnectOptions::track_gtids`](crate::MySqlConnectOptions::track_gtids).
+    /// These can be used to wait for a replica to catch up before reading from it
+    /// (e.g. with `WAIT_FOR_EXECUTED_GTID_SET`).
+    pub fn last_gtids(&self) -> Option<&str> {
+        self.gtids.as_deref()
+    }
+
+    /// The state of the current transaction as an 8-character string, see
+    /// `session_track_transaction_info` in the MySQL documentation.
+    pub fn transaction_state(&self) -> Option<&str> {
+        self.transaction_state.as_deref()
+    }
+
+    /// The statements needed to restart the current transaction with the same
+    /// characteristics (e.g. `SET TRANSACTION ISOLATION LEVEL ..`).
+    pub fn transaction_characteristics(&self) -> Option<&str> {
+        self.transaction_characteristics.as_deref()
+    }
+
+    /// Returns `true` if the session state (the default schema, system or user-defined variables,
+    /// temporary tables, prepared statements, ..) was changed since the connection
+    /// was established.
+    ///
+    /// A pool can use this to discard connections which leak state between users.
+    pub fn is_changed(&self) -> bool {
+        self.changed
+    }
+
+    pub(crate) fn apply(&mut self, changes: Vec<SessionStateChange>) {
+        for change in changes {
+            match change {
+                SessionStateChange::SystemVariable { name, value } => {
+                    self.changed = true;
+                    self.system_variables.insert(name, value);
+                }
+
+                SessionStateChange::Schema(schema) => {
+                    self.changed = true;
+                    self.schema = Some(schema);
+                }
+
+                SessionStateChange::StateChange => {
+                    self.changed = true;
+                }
+
+                SessionStateChange::Gtids(gtids) => {
+                    self.gtids = Some(gtids);
+                }
+
+                SessionStateChange::TransactionCharacteristics(characteristics) => {
+                    self.transaction_characteristics = Some(characteristics);
+                }
+
+                SessionStateChange::TransactionState(state) => {
+                    self.transaction_state = Some(state);
+                }
+            }
+        }
+    }
+
+    // changes made while establishing the connection are the baseline
+    pub(crate) fn reset_changed(&mut self) {
+        self.changed = false;
+        self.system_variables.clear();
+        self.schema = None;
+    }
+}
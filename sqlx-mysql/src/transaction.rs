@@ -2,7 +2,6 @@ use futures_core::future::BoxFuture;
 
 use crate::connection::Waiting;
 use crate::error::Error;
-use crate::executor::Executor;
 use crate::protocol::text::Query;
 use crate::{MySql, MySqlConnection};
 
@@ -18,19 +17,65 @@ impl TransactionManager for MySqlTransactionManager {
         Box::pin(async move {
             let depth = conn.transaction_depth;
 
-            conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
+            conn.execute_internal(&begin_ansi_transaction_sql(depth))
+                .await?;
             conn.transaction_depth = depth + 1;
 
             Ok(())
         })
     }
 
+    fn begin_with(
+        conn: &mut MySqlConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            if options.is_default() {
+                return Self::begin(conn).await;
+            }
+
+            if conn.transaction_depth > 0 {
+                return Err(Error::Configuration(
+                    "transaction options cannot be set for a savepoint".into(),
+                ));
+            }
+
+            if options.get_deferrable().is_some() {
+                return Err(Error::Configuration(
+                    "deferrable transactions are not supported by MySQL".into(),
+                ));
+            }
+
+            // applies to the next transaction only
+            // https://dev.mysql.com/doc/refman/8.0/en/set-transaction.html
+            if let Some(level) = options.get_isolation() {
+                conn.execute_internal(&format!(
+                    "SET TRANSACTION ISOLATION LEVEL {}",
+                    level.as_sql()
+                ))
+                .await?;
+            }
+
+            let sql = match options.get_read_only() {
+                Some(true) => "START TRANSACTION READ ONLY",
+                Some(false) => "START TRANSACTION READ WRITE",
+                None => "START TRANSACTION",
+            };
+
+            conn.execute_internal(sql).await?;
+            conn.transaction_depth = 1;
+
+            Ok(())
+        })
+    }
+
     fn commit(conn: &mut MySqlConnection) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.transaction_depth;
 
             if depth > 0 {
-                conn.execute(&*commit_ansi_transaction_sql(depth)).await?;
+                conn.execute_internal(&commit_ansi_transaction_sql(depth))
+                    .await?;
                 conn.transaction_depth = depth - 1;
             }
 
@@ -43,7 +88,8 @@ impl TransactionManager for MySqlTransactionManager {
             let depth = conn.transaction_depth;
 
             if depth > 0 {
-                conn.execute(&*rollback_ansi_transaction_sql(depth)).await?;
+                conn.execute_internal(&rollback_ansi_transaction_sql(depth))
+                    .await?;
                 conn.transaction_depth = depth - 1;
             }
 
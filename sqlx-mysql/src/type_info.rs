@@ -27,6 +27,38 @@ impl MySqlTypeInfo {
         }
     }
 
+    /// Returns `true` if the type is an `UNSIGNED` numeric type.
+    pub fn is_unsigned(&self) -> bool {
+        self.flags.contains(ColumnFlags::UNSIGNED)
+    }
+
+    /// Returns `true` if the type is declared with `ZEROFILL`, in which case
+    /// values should be padded with zeros up to the [display width](Self::display_width).
+    pub fn is_zerofill(&self) -> bool {
+        self.flags.contains(ColumnFlags::ZEROFILL)
+    }
+
+    /// Returns `true` if the values of the type are compared as binary data
+    /// (e.g. `VARBINARY`, `BLOB` or numeric types).
+    pub fn is_binary(&self) -> bool {
+        self.flags.contains(ColumnFlags::BINARY)
+    }
+
+    /// The maximum display width of values of the type as reported by the server,
+    /// e.g. `(M)` of `INT(M)`, the precision plus sign and decimal point of `DECIMAL`, or the
+    /// maximum length in bytes of `VARCHAR` and `CHAR`.
+    ///
+    /// This is only known for the types of columns of a result set.
+    pub fn display_width(&self) -> Option<u32> {
+        self.max_size
+    }
+
+    /// The id of the collation of the type, which determines its character set
+    /// (`63` for binary data and non-string types).
+    pub fn char_set(&self) -> u16 {
+        self.char_set
+    }
+
     #[doc(hidden)]
     pub const fn __enum() -> Self {
         Self {
@@ -19,19 +19,24 @@ impl Type<MySql> for DateTime<Utc> {
     }
 }
 
-/// Note: assumes the connection's `time_zone` is set to `+00:00` (UTC).
+/// Note: requires the connection's `time_zone` to be set to `+00:00` (UTC), the default.
 impl Encode<'_, MySql> for DateTime<Utc> {
     fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
         Encode::<MySql>::encode(&self.naive_utc(), buf)
     }
 }
 
-/// Note: assumes the connection's `time_zone` is set to `+00:00` (UTC).
+/// Note: `TIMESTAMP` values are converted from the connection's `time_zone`, `DATETIME` values
+/// as configured by [`MySqlConnectOptions::datetime_mode`](crate::MySqlConnectOptions::datetime_mode).
 impl<'r> Decode<'r, MySql> for DateTime<Utc> {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        let offset = value.time_zone.utc_offset(value.type_info.r#type)?;
         let naive: NaiveDateTime = Decode::<MySql>::decode(value)?;
 
-        Ok(DateTime::from_utc(naive, Utc))
+        Ok(DateTime::from_utc(
+            naive - chrono::Duration::seconds(offset.into()),
+            Utc,
+        ))
     }
 }
 
@@ -45,14 +50,14 @@ impl Type<MySql> for DateTime<Local> {
     }
 }
 
-/// Note: assumes the connection's `time_zone` is set to `+00:00` (UTC).
+/// Note: requires the connection's `time_zone` to be set to `+00:00` (UTC), the default.
 impl Encode<'_, MySql> for DateTime<Local> {
     fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
         Encode::<MySql>::encode(&self.naive_utc(), buf)
     }
 }
 
-/// Note: assumes the connection's `time_zone` is set to `+00:00` (UTC).
+/// Note: converted as [`DateTime<Utc>`].
 impl<'r> Decode<'r, MySql> for DateTime<Local> {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
         Ok(<DateTime<Utc> as Decode<'r, MySql>>::decode(value)?.with_timezone(&Local))
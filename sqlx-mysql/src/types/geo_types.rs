@@ -0,0 +1,327 @@
+use geo_types::{
+    Coord, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+    Point, Polygon,
+};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::protocol::text::ColumnType;
+use crate::types::{MySqlGeometry, Type};
+use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+// https://dev.mysql.com/doc/refman/8.0/en/gis-data-formats.html#gis-wkb-format
+
+const WKB_POINT: u32 = 1;
+const WKB_LINE_STRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTI_POINT: u32 = 4;
+const WKB_MULTI_LINE_STRING: u32 = 5;
+const WKB_MULTI_POLYGON: u32 = 6;
+const WKB_GEOMETRY_COLLECTION: u32 = 7;
+
+struct WkbReader<'a> {
+    buf: &'a [u8],
+    little_endian: bool,
+}
+
+impl WkbReader<'_> {
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], BoxDynError> {
+        if self.buf.len() < N {
+            return Err("unexpected end of well-known binary geometry".into());
+        }
+
+        let (bytes, rest) = self.buf.split_at(N);
+        self.buf = rest;
+
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn read_u32(&mut self) -> Result<u32, BoxDynError> {
+        let bytes = self.read_bytes()?;
+
+        Ok(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn read_f64(&mut self) -> Result<f64, BoxDynError> {
+        let bytes = self.read_bytes()?;
+
+        Ok(if self.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    fn read_coord(&mut self) -> Result<Coord<f64>, BoxDynError> {
+        Ok(Coord {
+            x: self.read_f64()?,
+            y: self.read_f64()?,
+        })
+    }
+
+    fn read_line_string(&mut self) -> Result<LineString<f64>, BoxDynError> {
+        let len = self.read_u32()?;
+
+        (0..len)
+            .map(|_| self.read_coord())
+            .collect::<Result<_, _>>()
+            .map(LineString)
+    }
+
+    fn read_polygon(&mut self) -> Result<Polygon<f64>, BoxDynError> {
+        let len = self.read_u32()?;
+        let mut rings = (0..len)
+            .map(|_| self.read_line_string())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let exterior = if rings.is_empty() {
+            LineString(Vec::new())
+        } else {
+            rings.remove(0)
+        };
+
+        Ok(Polygon::new(exterior, rings))
+    }
+
+    fn read_geometries<T>(&mut self) -> Result<Vec<T>, BoxDynError>
+    where
+        T: TryFrom<Geometry<f64>>,
+    {
+        let len = self.read_u32()?;
+
+        (0..len)
+            .map(|_| -> Result<T, BoxDynError> {
+                T::try_from(self.read_geometry()?)
+                    .map_err(|_| "unexpected geometry type in collection".into())
+            })
+            .collect()
+    }
+
+    fn read_geometry(&mut self) -> Result<Geometry<f64>, BoxDynError> {
+        self.little_endian = match self.read_bytes::<1>()? {
+            [0] => false,
+            [1] => true,
+            [order] => return Err(format!("invalid byte order {order} in geometry").into()),
+        };
+
+        Ok(match self.read_u32()? {
+            WKB_POINT => Geometry::Point(Point(self.read_coord()?)),
+            WKB_LINE_STRING => Geometry::LineString(self.read_line_string()?),
+            WKB_POLYGON => Geometry::Polygon(self.read_polygon()?),
+            WKB_MULTI_POINT => Geometry::MultiPoint(MultiPoint(self.read_geometries()?)),
+            WKB_MULTI_LINE_STRING => {
+                Geometry::MultiLineString(MultiLineString(self.read_geometries()?))
+            }
+            WKB_MULTI_POLYGON => Geometry::MultiPolygon(MultiPolygon(self.read_geometries()?)),
+            WKB_GEOMETRY_COLLECTION => {
+                Geometry::GeometryCollection(GeometryCollection(self.read_geometries()?))
+            }
+
+            ty => return Err(format!("unsupported geometry type {ty}").into()),
+        })
+    }
+}
+
+fn put_coord(buf: &mut Vec<u8>, coord: &Coord<f64>) {
+    buf.extend_from_slice(&coord.x.to_le_bytes());
+    buf.extend_from_slice(&coord.y.to_le_bytes());
+}
+
+fn put_line_string(buf: &mut Vec<u8>, line_string: &LineString<f64>) {
+    buf.extend_from_slice(&(line_string.0.len() as u32).to_le_bytes());
+
+    for coord in &line_string.0 {
+        put_coord(buf, coord);
+    }
+}
+
+fn put_polygon(buf: &mut Vec<u8>, polygon: &Polygon<f64>) {
+    buf.extend_from_slice(&(polygon.interiors().len() as u32 + 1).to_le_bytes());
+
+    put_line_string(buf, polygon.exterior());
+
+    for interior in polygon.interiors() {
+        put_line_string(buf, interior);
+    }
+}
+
+fn put_header(buf: &mut Vec<u8>, ty: u32) {
+    buf.push(1); // little endian
+    buf.extend_from_slice(&ty.to_le_bytes());
+}
+
+fn put_geometry(buf: &mut Vec<u8>, geometry: &Geometry<f64>) {
+    match geometry {
+        Geometry::Point(point) => {
+            put_header(buf, WKB_POINT);
+            put_coord(buf, &point.0);
+        }
+
+        Geometry::Line(line) => {
+            put_header(buf, WKB_LINE_STRING);
+            put_line_string(buf, &LineString(vec![line.start, line.end]));
+        }
+
+        Geometry::LineString(line_string) => {
+            put_header(buf, WKB_LINE_STRING);
+            put_line_string(buf, line_string);
+        }
+
+        Geometry::Polygon(polygon) => {
+            put_header(buf, WKB_POLYGON);
+            put_polygon(buf, polygon);
+        }
+
+        Geometry::Rect(rect) => {
+            put_header(buf, WKB_POLYGON);
+            put_polygon(buf, &rect.to_polygon());
+        }
+
+        Geometry::Triangle(triangle) => {
+            put_header(buf, WKB_POLYGON);
+            put_polygon(buf, &triangle.to_polygon());
+        }
+
+        Geometry::MultiPoint(points) => {
+            put_header(buf, WKB_MULTI_POINT);
+            buf.extend_from_slice(&(points.0.len() as u32).to_le_bytes());
+
+            for point in &points.0 {
+                put_header(buf, WKB_POINT);
+                put_coord(buf, &point.0);
+            }
+        }
+
+        Geometry::MultiLineString(line_strings) => {
+            put_header(buf, WKB_MULTI_LINE_STRING);
+            buf.extend_from_slice(&(line_strings.0.len() as u32).to_le_bytes());
+
+            for line_string in &line_strings.0 {
+                put_header(buf, WKB_LINE_STRING);
+                put_line_string(buf, line_string);
+            }
+        }
+
+        Geometry::MultiPolygon(polygons) => {
+            put_header(buf, WKB_MULTI_POLYGON);
+            buf.extend_from_slice(&(polygons.0.len() as u32).to_le_bytes());
+
+            for polygon in &polygons.0 {
+                put_header(buf, WKB_POLYGON);
+                put_polygon(buf, polygon);
+            }
+        }
+
+        Geometry::GeometryCollection(geometries) => {
+            put_header(buf, WKB_GEOMETRY_COLLECTION);
+            buf.extend_from_slice(&(geometries.0.len() as u32).to_le_bytes());
+
+            for geometry in &geometries.0 {
+                put_geometry(buf, geometry);
+            }
+        }
+    }
+}
+
+pub(crate) fn decode_wkb(wkb: &[u8]) -> Result<Geometry<f64>, BoxDynError> {
+    WkbReader {
+        buf: wkb,
+        little_endian: true,
+    }
+    .read_geometry()
+}
+
+pub(crate) fn encode_wkb(geometry: &Geometry<f64>) -> Vec<u8> {
+    let mut wkb = Vec::new();
+    put_geometry(&mut wkb, geometry);
+    wkb
+}
+
+// geometries are encoded without a spatial reference system (SRID 0),
+// use `MySqlGeometry` to read or write the SRID
+macro_rules! impl_geometry {
+    ($($ty:ty => $to_geometry:expr),*) => {
+        $(
+            impl Type<MySql> for $ty {
+                fn type_info() -> MySqlTypeInfo {
+                    <MySqlGeometry as Type<MySql>>::type_info()
+                }
+
+                fn compatible(ty: &MySqlTypeInfo) -> bool {
+                    <MySqlGeometry as Type<MySql>>::compatible(ty)
+                }
+            }
+
+            impl Encode<'_, MySql> for $ty {
+                fn produces(&self) -> Option<MySqlTypeInfo> {
+                    Some(MySqlTypeInfo::binary(ColumnType::Blob))
+                }
+
+                fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+                    let wkb = encode_wkb(&$to_geometry(self.clone()));
+                    MySqlGeometry::encode_internal(0, &wkb, buf);
+
+                    IsNull::No
+                }
+            }
+
+            impl Decode<'_, MySql> for $ty {
+                fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+                    let geometry = <MySqlGeometry as Decode<MySql>>::decode(value)?;
+
+                    <$ty>::try_from(decode_wkb(&geometry.wkb)?).map_err(|_| {
+                        format!(
+                            "expected geometry of type {}",
+                            std::any::type_name::<$ty>()
+                        )
+                        .into()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_geometry!(
+    Geometry<f64> => std::convert::identity,
+    Point<f64> => Geometry::Point,
+    LineString<f64> => Geometry::LineString,
+    Polygon<f64> => Geometry::Polygon,
+    MultiPoint<f64> => Geometry::MultiPoint,
+    MultiLineString<f64> => Geometry::MultiLineString,
+    MultiPolygon<f64> => Geometry::MultiPolygon,
+    GeometryCollection<f64> => Geometry::GeometryCollection
+);
+
+#[test]
+fn test_wkb_round_trip() {
+    let polygon = Geometry::Polygon(Polygon::new(
+        LineString::from(vec![(0., 0.), (1., 0.), (1., 1.), (0., 0.)]),
+        vec![],
+    ));
+
+    let collection = Geometry::GeometryCollection(GeometryCollection(vec![
+        Geometry::Point(Point::new(1., 2.)),
+        polygon.clone(),
+    ]));
+
+    assert_eq!(decode_wkb(&encode_wkb(&polygon)).unwrap(), polygon);
+    assert_eq!(decode_wkb(&encode_wkb(&collection)).unwrap(), collection);
+}
+
+#[test]
+fn test_decode_wkb_point() {
+    // SELECT ST_AsBinary(POINT(1, 2))
+    const DATA: &[u8] =
+        b"\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\xf0?\x00\x00\x00\x00\x00\x00\x00@";
+
+    assert_eq!(
+        decode_wkb(DATA).unwrap(),
+        Geometry::Point(Point::new(1., 2.))
+    );
+}
@@ -0,0 +1,98 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::io::MySqlBufMutExt;
+use crate::protocol::text::ColumnType;
+use crate::types::Type;
+use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
+
+/// A value of a spatial column (`GEOMETRY`, `POINT`, `POLYGON`, ..) in the internal
+/// format of MySQL: the spatial reference system identifier followed by the
+/// [well-known binary](https://dev.mysql.com/doc/refman/8.0/en/gis-data-formats.html#gis-wkb-format)
+/// representation of the geometry.
+///
+/// This can be used to read and write spatial columns without converting them with
+/// `ST_AsBinary()` and `ST_GeomFromWKB()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MySqlGeometry {
+    /// The spatial reference system identifier, `0` if the geometry has none.
+    pub srid: u32,
+
+    /// The geometry in well-known binary format.
+    pub wkb: Vec<u8>,
+}
+
+impl MySqlGeometry {
+    pub(crate) fn decode_internal(buf: &[u8]) -> Result<Self, BoxDynError> {
+        if buf.len() < 4 {
+            return Err(format!(
+                "expected at least 4 bytes for geometry but found {}",
+                buf.len()
+            )
+            .into());
+        }
+
+        let (srid, wkb) = buf.split_at(4);
+
+        Ok(Self {
+            srid: u32::from_le_bytes([srid[0], srid[1], srid[2], srid[3]]),
+            wkb: wkb.to_vec(),
+        })
+    }
+
+    pub(crate) fn encode_internal(srid: u32, wkb: &[u8], buf: &mut Vec<u8>) {
+        let mut value = Vec::with_capacity(4 + wkb.len());
+        value.extend_from_slice(&srid.to_le_bytes());
+        value.extend_from_slice(wkb);
+
+        buf.put_bytes_lenenc(&value);
+    }
+}
+
+impl Type<MySql> for MySqlGeometry {
+    fn type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo::binary(ColumnType::Geometry)
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        matches!(
+            ty.r#type,
+            ColumnType::Geometry
+                | ColumnType::Blob
+                | ColumnType::TinyBlob
+                | ColumnType::MediumBlob
+                | ColumnType::LongBlob
+        )
+    }
+}
+
+impl Encode<'_, MySql> for MySqlGeometry {
+    fn produces(&self) -> Option<MySqlTypeInfo> {
+        // the server does not accept GEOMETRY as the type of a parameter but
+        // converts blobs in the internal format to geometries
+        Some(MySqlTypeInfo::binary(ColumnType::Blob))
+    }
+
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        Self::encode_internal(self.srid, &self.wkb, buf);
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, MySql> for MySqlGeometry {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        Self::decode_internal(value.as_bytes()?)
+    }
+}
+
+#[test]
+fn test_decode_geometry() {
+    // SELECT ST_GeomFromText('POINT(1 2)', 4326)
+    const DATA: &[u8] = b"\xe6\x10\x00\x00\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\xf0?\x00\x00\x00\x00\x00\x00\x00@";
+
+    let geometry = MySqlGeometry::decode_internal(DATA).unwrap();
+
+    assert_eq!(geometry.srid, 4326);
+    assert_eq!(geometry.wkb, &DATA[4..]);
+}
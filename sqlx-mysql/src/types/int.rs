@@ -4,10 +4,13 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::types::uint::uint_decode;
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
 
-fn int_compatible(ty: &MySqlTypeInfo) -> bool {
+// unsigned columns are compatible as well, values which are out of range
+// for the Rust type are rejected when decoding
+pub(crate) fn int_compatible(ty: &MySqlTypeInfo) -> bool {
     matches!(
         ty.r#type,
         ColumnType::Tiny
@@ -15,7 +18,7 @@ fn int_compatible(ty: &MySqlTypeInfo) -> bool {
             | ColumnType::Long
             | ColumnType::Int24
             | ColumnType::LongLong
-    ) && !ty.flags.contains(ColumnFlags::UNSIGNED)
+    )
 }
 
 impl Type<MySql> for i8 {
@@ -90,7 +93,14 @@ impl Encode<'_, MySql> for i64 {
     }
 }
 
-fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
+pub(crate) fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
+    if value.type_info.flags.contains(ColumnFlags::UNSIGNED) {
+        let value = uint_decode(value)?;
+
+        return i64::try_from(value)
+            .map_err(|_| format!("unsigned value {value} is out of range for `i64`").into());
+    }
+
     Ok(match value.format() {
         MySqlValueFormat::Text => value.as_str()?.parse()?,
         MySqlValueFormat::Binary => {
@@ -100,21 +110,36 @@ fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
     })
 }
 
+fn int_decode_as<T>(value: MySqlValueRef<'_>) -> Result<T, BoxDynError>
+where
+    T: TryFrom<i64>,
+{
+    let value = int_decode(value)?;
+
+    T::try_from(value).map_err(|_| {
+        format!(
+            "value {value} is out of range for `{}`",
+            std::any::type_name::<T>()
+        )
+        .into()
+    })
+}
+
 impl Decode<'_, MySql> for i8 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        int_decode_as(value)
     }
 }
 
 impl Decode<'_, MySql> for i16 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        int_decode_as(value)
     }
 }
 
 impl Decode<'_, MySql> for i32 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        int_decode_as(value)
     }
 }
 
@@ -17,6 +17,7 @@
 //! | `f64`                                 | DOUBLE                                               |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
 //! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB                              |
+//! | [`MySqlGeometry`]                     | GEOMETRY, POINT, POLYGON, ..                         |
 //!
 //! ### [`chrono`](https://crates.io/crates/chrono)
 //!
@@ -41,6 +42,18 @@
 //! | `time::Date`                          | DATE                                                 |
 //! | `time::Time`                          | TIME                                                 |
 //!
+//! ### Time zones
+//!
+//! The server converts `TIMESTAMP` values from and to the time zone of the session, which is
+//! UTC unless configured otherwise with [`MySqlConnectOptions::time_zone`]. Date-times with a
+//! time zone (`DateTime<Utc>`, `DateTime<Local>` and `OffsetDateTime`) are decoded from
+//! `TIMESTAMP` values with the offset of the session time zone, and from `DATETIME` values as
+//! configured by [`MySqlConnectOptions::datetime_mode`]. They are bound as UTC date-times, which
+//! is refused unless the session time zone is UTC. Naive date-times are never converted.
+//!
+//! [`MySqlConnectOptions::time_zone`]: crate::MySqlConnectOptions::time_zone
+//! [`MySqlConnectOptions::datetime_mode`]: crate::MySqlConnectOptions::datetime_mode
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -75,6 +88,24 @@
 //! | `serde_json::JsonValue`               | JSON                                                 |
 //! | `&serde_json::value::RawValue`        | JSON                                                 |
 //!
+//! ### [`geo-types`](https://crates.io/crates/geo-types)
+//!
+//! Requires the `geo-types` Cargo feature flag.
+//!
+//! | Rust type                             | MySQL type(s)                                        |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `geo_types::Geometry<f64>`            | GEOMETRY                                             |
+//! | `geo_types::Point<f64>`               | POINT                                                |
+//! | `geo_types::LineString<f64>`          | LINESTRING                                           |
+//! | `geo_types::Polygon<f64>`             | POLYGON                                              |
+//! | `geo_types::MultiPoint<f64>`          | MULTIPOINT                                           |
+//! | `geo_types::MultiLineString<f64>`     | MULTILINESTRING                                      |
+//! | `geo_types::MultiPolygon<f64>`        | MULTIPOLYGON                                         |
+//! | `geo_types::GeometryCollection<f64>`  | GEOMETRYCOLLECTION                                   |
+//!
+//! These are encoded without a spatial reference system (SRID 0); use [`MySqlGeometry`]
+//! to read or write the SRID of a geometry.
+//!
 //! # Nullable
 //!
 //! In addition, `Option<T>` is supported where `T` implements `Type`. An `Option<T>` represents
@@ -85,10 +116,13 @@ pub(crate) use sqlx_core::types::*;
 mod bool;
 mod bytes;
 mod float;
+mod geometry;
 mod int;
 mod str;
 mod uint;
 
+pub use geometry::MySqlGeometry;
+
 #[cfg(feature = "json")]
 mod json;
 
@@ -106,3 +140,6 @@ mod time;
 
 #[cfg(feature = "uuid")]
 mod uuid;
+
+#[cfg(feature = "geo-types")]
+mod geo_types;
@@ -4,7 +4,9 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::io::MySqlBufMutExt;
-use crate::protocol::text::ColumnType;
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::types::int::{int_compatible, int_decode};
+use crate::types::uint::uint_decode;
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo, MySqlValueRef};
 
@@ -12,6 +14,10 @@ impl Type<MySql> for Decimal {
     fn type_info() -> MySqlTypeInfo {
         MySqlTypeInfo::binary(ColumnType::NewDecimal)
     }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        matches!(ty.r#type, ColumnType::Decimal | ColumnType::NewDecimal) || int_compatible(ty)
+    }
 }
 
 impl Encode<'_, MySql> for Decimal {
@@ -24,6 +30,16 @@ impl Encode<'_, MySql> for Decimal {
 
 impl Decode<'_, MySql> for Decimal {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        // integers are sent as binary in prepared statements
+        if int_compatible(&value.type_info) {
+            return Ok(if value.type_info.flags.contains(ColumnFlags::UNSIGNED) {
+                Decimal::from(uint_decode(value)?)
+            } else {
+                Decimal::from(int_decode(value)?)
+            });
+        }
+
+        // DECIMAL is always sent as a string, in both the text and the binary protocol
         Ok(value.as_str()?.parse()?)
     }
 }
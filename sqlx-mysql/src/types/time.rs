@@ -21,6 +21,7 @@ impl Type<MySql> for OffsetDateTime {
     }
 }
 
+/// Note: requires the connection's `time_zone` to be set to `+00:00` (UTC), the default.
 impl Encode<'_, MySql> for OffsetDateTime {
     fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
         let utc_dt = self.to_offset(UtcOffset::UTC);
@@ -30,11 +31,16 @@ impl Encode<'_, MySql> for OffsetDateTime {
     }
 }
 
+/// Note: `TIMESTAMP` values are converted from the connection's `time_zone`, `DATETIME` values
+/// as configured by [`MySqlConnectOptions::datetime_mode`](crate::MySqlConnectOptions::datetime_mode).
 impl<'r> Decode<'r, MySql> for OffsetDateTime {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        let offset = value.time_zone.utc_offset(value.type_info.r#type)?;
         let primitive: PrimitiveDateTime = Decode::<MySql>::decode(value)?;
 
-        Ok(primitive.assume_utc())
+        Ok(primitive
+            .assume_offset(UtcOffset::from_whole_seconds(offset)?)
+            .to_offset(UtcOffset::UTC))
     }
 }
 
@@ -2,6 +2,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::types::int::{int_compatible, int_decode};
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
 use byteorder::{ByteOrder, LittleEndian};
@@ -15,7 +16,8 @@ fn uint_type_info(ty: ColumnType) -> MySqlTypeInfo {
     }
 }
 
-fn uint_compatible(ty: &MySqlTypeInfo) -> bool {
+// signed columns are compatible as well, negative values are rejected when decoding
+pub(crate) fn uint_compatible(ty: &MySqlTypeInfo) -> bool {
     matches!(
         ty.r#type,
         ColumnType::Tiny
@@ -25,7 +27,7 @@ fn uint_compatible(ty: &MySqlTypeInfo) -> bool {
             | ColumnType::LongLong
             | ColumnType::Year
             | ColumnType::Bit
-    ) && ty.flags.contains(ColumnFlags::UNSIGNED)
+    )
 }
 
 impl Type<MySql> for u8 {
@@ -100,7 +102,7 @@ impl Encode<'_, MySql> for u64 {
     }
 }
 
-fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
+pub(crate) fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
     if value.type_info.r#type == ColumnType::Bit {
         // NOTE: Regardless of the value format, there is raw binary data here
 
@@ -114,6 +116,13 @@ fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
         return Ok(value);
     }
 
+    if int_compatible(&value.type_info) && !value.type_info.flags.contains(ColumnFlags::UNSIGNED) {
+        let value = int_decode(value)?;
+
+        return u64::try_from(value)
+            .map_err(|_| format!("negative value {value} is out of range for `u64`").into());
+    }
+
     Ok(match value.format() {
         MySqlValueFormat::Text => value.as_str()?.parse()?,
 
@@ -124,21 +133,36 @@ fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
     })
 }
 
+fn uint_decode_as<T>(value: MySqlValueRef<'_>) -> Result<T, BoxDynError>
+where
+    T: TryFrom<u64>,
+{
+    let value = uint_decode(value)?;
+
+    T::try_from(value).map_err(|_| {
+        format!(
+            "value {value} is out of range for `{}`",
+            std::any::type_name::<T>()
+        )
+        .into()
+    })
+}
+
 impl Decode<'_, MySql> for u8 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)?.try_into().map_err(Into::into)
+        uint_decode_as(value)
     }
 }
 
 impl Decode<'_, MySql> for u16 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)?.try_into().map_err(Into::into)
+        uint_decode_as(value)
     }
 }
 
 impl Decode<'_, MySql> for u32 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        uint_decode(value)?.try_into().map_err(Into::into)
+        uint_decode_as(value)
     }
 }
 
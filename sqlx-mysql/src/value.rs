@@ -5,6 +5,7 @@ use bytes::Bytes;
 pub(crate) use sqlx_core::value::*;
 
 use crate::error::{BoxDynError, UnexpectedNullError};
+use crate::options::TimeZoneRules;
 use crate::protocol::text::ColumnType;
 use crate::{MySql, MySqlTypeInfo};
 
@@ -21,6 +22,7 @@ pub struct MySqlValue {
     value: Option<Bytes>,
     type_info: MySqlTypeInfo,
     format: MySqlValueFormat,
+    time_zone: TimeZoneRules,
 }
 
 /// Implementation of [`ValueRef`] for MySQL.
@@ -30,6 +32,7 @@ pub struct MySqlValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: MySqlTypeInfo,
     pub(crate) format: MySqlValueFormat,
+    pub(crate) time_zone: TimeZoneRules,
 }
 
 impl<'r> MySqlValueRef<'r> {
@@ -58,6 +61,7 @@ impl Value for MySqlValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            time_zone: self.time_zone,
         }
     }
 
@@ -86,6 +90,7 @@ impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
             value,
             format: self.format,
             type_info: self.type_info.clone(),
+            time_zone: self.time_zone,
         }
     }
 
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use futures_util::pin_mut;
+
+use crate::connection::stream::PgStream;
+use crate::error::Error;
+use crate::message::{CancelRequest, Message};
+use crate::rt;
+use crate::{PgConnectOptions, PgConnection};
+
+// a handle to cancel the statement executed by a connection, by sending a cancel request
+// on a new connection established with the same options
+// https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.9
+pub(crate) struct PgCancelHandle {
+    options: Arc<PgConnectOptions>,
+    process_id: u32,
+    secret_key: u32,
+}
+
+impl PgCancelHandle {
+    pub(crate) async fn cancel(&self) -> Result<(), Error> {
+        let mut stream = PgStream::connect(&self.options).await?;
+
+        // the server closes the connection without responding
+        stream
+            .send(CancelRequest {
+                process_id: self.process_id,
+                secret_key: self.secret_key,
+            })
+            .await
+    }
+}
+
+impl PgConnection {
+    pub(crate) fn cancel_handle(&self) -> PgCancelHandle {
+        PgCancelHandle {
+            options: Arc::clone(&self.options),
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        }
+    }
+
+    // receives the next message of a statement, cancelling the statement if it is still
+    // running at the deadline
+    pub(crate) async fn recv_until(
+        &mut self,
+        cancel: &mut Option<(PgCancelHandle, Instant)>,
+    ) -> Result<Message, Error> {
+        let Some((handle, deadline)) = cancel.as_ref() else {
+            return self.stream.recv().await;
+        };
+
+        let recv = self.stream.recv();
+        pin_mut!(recv);
+
+        match rt::timeout(
+            deadline.saturating_duration_since(Instant::now()),
+            &mut recv,
+        )
+        .await
+        {
+            Ok(message) => message,
+
+            Err(_) => {
+                handle.cancel().await?;
+                *cancel = None;
+
+                // the server responds with an error once the statement is cancelled
+                recv.await
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::HashMap;
 
 use crate::common::StatementCache;
@@ -7,6 +9,7 @@ use crate::io::Decode;
 use crate::message::{
     Authentication, BackendKeyData, MessageFormat, Password, ReadyForQuery, Startup,
 };
+use crate::net::Socket;
 use crate::types::Oid;
 use crate::{PgConnectOptions, PgConnection};
 
@@ -16,8 +19,24 @@ use crate::{PgConnectOptions, PgConnection};
 impl PgConnection {
     pub(crate) async fn establish(options: &PgConnectOptions) -> Result<Self, Error> {
         // Upgrade to TLS if we were asked to and the server supports it
-        let mut stream = PgStream::connect(options).await?;
+        let stream = PgStream::connect(options).await?;
+
+        Self::establish_with_stream(options, stream).await
+    }
+
+    pub(crate) async fn establish_with_socket(
+        options: &PgConnectOptions,
+        socket: Box<dyn Socket>,
+    ) -> Result<Self, Error> {
+        let stream = PgStream::connect_with_socket(options, socket).await?;
+
+        Self::establish_with_stream(options, stream).await
+    }
 
+    async fn establish_with_stream(
+        options: &PgConnectOptions,
+        mut stream: PgStream,
+    ) -> Result<Self, Error> {
         // To begin a session, a frontend opens a connection to the server
         // and sends a startup message.
 
@@ -139,6 +158,7 @@ impl PgConnection {
             stream,
             process_id,
             secret_key,
+            options: Arc::new(options.clone()),
             transaction_status,
             transaction_depth: 0,
             pending_ready_for_query_count: 0,
@@ -1,3 +1,4 @@
+use crate::connection::rewrite_query;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
@@ -18,6 +19,7 @@ use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
 use sqlx_core::Either;
+use std::time::{Duration, Instant};
 use std::{borrow::Cow, sync::Arc};
 
 async fn prepare(
@@ -160,7 +162,7 @@ impl PgConnection {
         self.pending_ready_for_query_count += 1;
     }
 
-    async fn get_or_prepare<'a>(
+    pub(super) async fn get_or_prepare<'a>(
         &mut self,
         sql: &str,
         parameters: &[PgTypeInfo],
@@ -191,6 +193,34 @@ impl PgConnection {
         Ok(statement)
     }
 
+    /// Applies the [`QueryRewriter`](crate::connection::QueryRewriter) of the connection.
+    pub(crate) fn rewrite_query<'q>(
+        &self,
+        sql: &'q str,
+        arguments: Option<&mut PgArguments>,
+        metadata: &mut Option<Arc<PgStatementMetadata>>,
+    ) -> Result<Cow<'q, str>, Error> {
+        let rewritten = rewrite_query(self.options.query_rewriter.as_ref(), sql, arguments)?;
+
+        // the metadata of a prepared statement describes the original query
+        if *rewritten != *sql {
+            *metadata = None;
+        }
+
+        Ok(rewritten)
+    }
+
+    /// Execute statements issued by the driver itself, without passing them through the
+    /// [`QueryRewriter`](crate::connection::QueryRewriter) of the connection.
+    pub(crate) async fn execute_internal(&mut self, sql: &str) -> Result<(), Error> {
+        let s = self.run(sql, None, 0, false, None, None).await?;
+        pin_mut!(s);
+
+        while s.try_next().await?.is_some() {}
+
+        Ok(())
+    }
+
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         query: &'q str,
@@ -198,6 +228,7 @@ impl PgConnection {
         limit: u8,
         persistent: bool,
         metadata_opt: Option<Arc<PgStatementMetadata>>,
+        timeout: Option<Duration>,
     ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
         let mut logger = QueryLogger::new(query, self.log_settings.clone());
 
@@ -209,9 +240,12 @@ impl PgConnection {
         let format = if let Some(mut arguments) = arguments {
             // prepare the statement if this our first time executing it
             // always return the statement ID here
+            logger.set_arguments(arguments.types.len());
+
             let (statement, metadata_) = self
                 .get_or_prepare(query, &arguments.types, persistent, metadata_opt)
-                .await?;
+                .await
+                .map_err(|e| logger.record_error(e))?;
 
             metadata = metadata_;
 
@@ -271,9 +305,15 @@ impl PgConnection {
 
         self.stream.flush().await?;
 
+        // the statement is cancelled if it is still running at the deadline
+        let mut cancel = timeout.map(|timeout| (self.cancel_handle(), Instant::now() + timeout));
+
         Ok(try_stream! {
             loop {
-                let message = self.stream.recv().await?;
+                let message = self
+                    .recv_until(&mut cancel)
+                    .await
+                    .map_err(|e| logger.record_error(e))?;
 
                 match message.format {
                     MessageFormat::BindComplete
@@ -369,12 +409,14 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         E: Execute<'q, Self::Database>,
     {
         let sql = query.sql();
-        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
-        let arguments = query.take_arguments();
+        let mut metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let mut arguments = query.take_arguments();
         let persistent = query.persistent();
+        let timeout = query.timeout();
 
         Box::pin(try_stream! {
-            let s = self.run(sql, arguments, 0, persistent, metadata).await?;
+            let sql = self.rewrite_query(sql, arguments.as_mut(), &mut metadata)?;
+            let s = self.run(&sql, arguments, 0, persistent, metadata, timeout).await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -394,12 +436,16 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         E: Execute<'q, Self::Database>,
     {
         let sql = query.sql();
-        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
-        let arguments = query.take_arguments();
+        let mut metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let mut arguments = query.take_arguments();
         let persistent = query.persistent();
+        let timeout = query.timeout();
 
         Box::pin(async move {
-            let s = self.run(sql, arguments, 1, persistent, metadata).await?;
+            let sql = self.rewrite_query(sql, arguments.as_mut(), &mut metadata)?;
+            let s = self
+                .run(&sql, arguments, 1, persistent, metadata, timeout)
+                .await?;
             pin_mut!(s);
 
             while let Some(s) = s.try_next().await? {
@@ -423,7 +469,10 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         Box::pin(async move {
             self.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, parameters, true, None).await?;
+            let rewritten = self.rewrite_query(sql, None, &mut None)?;
+            let (_, metadata) = self
+                .get_or_prepare(&rewritten, parameters, true, None)
+                .await?;
 
             Ok(PgStatement {
                 sql: Cow::Borrowed(sql),
@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+use sqlx_core::Either;
+
+use crate::error::Error;
+use crate::executor::Execute;
+use crate::logger::QueryLogger;
+use crate::message::{self, Bind, CommandComplete, DataRow, MessageFormat};
+use crate::statement::PgStatementMetadata;
+use crate::{PgArguments, PgConnection, PgQueryResult, PgRow, PgValueFormat, Postgres};
+
+impl PgConnection {
+    /// Creates a pipeline which sends several queries to the server at once and then receives
+    /// their results in order, saving a round trip per query.
+    ///
+    /// See [`PgPipeline`] for details.
+    pub fn pipeline<'q>(&mut self) -> PgPipeline<'_, 'q> {
+        PgPipeline {
+            conn: self,
+            queries: Vec::new(),
+        }
+    }
+}
+
+/// A batch of independent queries which are sent to the server in one round trip, created with
+/// [`PgConnection::pipeline()`].
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// let mut pipeline = conn.pipeline();
+///
+/// pipeline
+///     .push(sqlx::query("UPDATE users SET seen_at = now() WHERE id = $1").bind(1_i64))
+///     .push(sqlx::query("SELECT name FROM users WHERE id = $1").bind(2_i64));
+///
+/// let rows = pipeline.fetch_all().await?;
+/// assert_eq!(rows.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Each query must consist of a single statement and is executed with the extended query
+/// protocol, even without arguments. The queries are only prepared in a separate round trip if
+/// they are not in the statement cache yet.
+///
+/// The queries run in a single implicit transaction, unless a transaction is already open on the
+/// connection: if a query fails, the queries after it are skipped and the effects of the queries
+/// before it are rolled back.
+pub struct PgPipeline<'c, 'q> {
+    conn: &'c mut PgConnection,
+    queries: Vec<PipelinedQuery<'q>>,
+}
+
+struct PipelinedQuery<'q> {
+    sql: &'q str,
+    arguments: PgArguments,
+    persistent: bool,
+    metadata: Option<Arc<PgStatementMetadata>>,
+}
+
+impl<'c, 'q> PgPipeline<'c, 'q> {
+    /// Adds a query to the pipeline.
+    pub fn push<E>(&mut self, mut query: E) -> &mut Self
+    where
+        E: Execute<'q, Postgres>,
+    {
+        self.queries.push(PipelinedQuery {
+            sql: query.sql(),
+            metadata: query.statement().map(|s| Arc::clone(&s.metadata)),
+            arguments: query.take_arguments().unwrap_or_default(),
+            persistent: query.persistent(),
+        });
+
+        self
+    }
+
+    /// Returns the number of queries in the pipeline.
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    /// Returns `true` if the pipeline contains no queries.
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    /// Executes the queries, returning the result of each query in order.
+    pub async fn execute(self) -> Result<Vec<PgQueryResult>, Error> {
+        self.fetch_many()
+            .try_filter_map(|step| async move {
+                Ok(match step {
+                    Either::Left(result) => Some(result),
+                    Either::Right(_) => None,
+                })
+            })
+            .try_collect()
+            .await
+    }
+
+    /// Executes the queries, returning the rows of each query in order.
+    pub async fn fetch_all(self) -> Result<Vec<Vec<PgRow>>, Error> {
+        let mut results = Vec::with_capacity(self.queries.len());
+        let mut rows = Vec::new();
+
+        let mut s = self.fetch_many();
+
+        while let Some(step) = s.try_next().await? {
+            match step {
+                Either::Left(_) => results.push(std::mem::take(&mut rows)),
+                Either::Right(row) => rows.push(row),
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Executes the queries, returning a stream of the rows of each query, each followed by the
+    /// result of the query.
+    pub fn fetch_many<'e>(self) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, Error>>
+    where
+        'c: 'e,
+        'q: 'e,
+    {
+        let PgPipeline { conn, mut queries } = self;
+
+        Box::pin(try_stream! {
+            if queries.is_empty() {
+                return Ok(());
+            }
+
+            let mut sqls = Vec::with_capacity(queries.len());
+
+            for query in &mut queries {
+                sqls.push(conn.rewrite_query(
+                    query.sql,
+                    Some(&mut query.arguments),
+                    &mut query.metadata,
+                )?);
+            }
+
+            // before we continue, wait until we are "ready" to accept more queries
+            conn.wait_until_ready().await?;
+
+            let mut loggers = Vec::with_capacity(queries.len());
+            let mut statements = Vec::with_capacity(queries.len());
+
+            // prepare the statements which are not cached yet
+            for (query, sql) in queries.iter_mut().zip(&sqls) {
+                let mut logger = QueryLogger::new(sql, conn.log_settings.clone());
+                logger.set_arguments(query.arguments.types.len());
+
+                let (statement, metadata) = conn
+                    .get_or_prepare(
+                        sql,
+                        &query.arguments.types,
+                        query.persistent,
+                        query.metadata.take(),
+                    )
+                    .await
+                    .map_err(|e| logger.record_error(e))?;
+
+                loggers.push(logger);
+
+                // patch holes created during encoding
+                query.arguments.apply_patches(conn, &metadata.parameters).await?;
+
+                statements.push((statement, metadata));
+            }
+
+            // consume messages till `ReadyForQuery` before bind and execute
+            conn.wait_until_ready().await?;
+
+            for (query, (statement, _)) in queries.iter().zip(&statements) {
+                conn.stream.write(Bind {
+                    portal: None,
+                    statement: *statement,
+                    formats: &[PgValueFormat::Binary],
+                    num_params: query.arguments.types.len() as i16,
+                    params: &*query.arguments.buffer,
+                    result_formats: &[PgValueFormat::Binary],
+                });
+
+                conn.stream.write(message::Execute {
+                    portal: None,
+                    limit: 0,
+                });
+
+                conn.stream.write(message::Close::Portal(None));
+            }
+
+            // a single [Sync] for all queries, so that the server processes them in one go
+            conn.write_sync();
+            conn.stream.flush().await?;
+
+            // the index of the query whose results we are receiving
+            let mut index = 0;
+
+            loop {
+                let message = match conn.stream.recv().await {
+                    Ok(message) => message,
+                    // the queries after the failed query are skipped
+                    Err(e) => match loggers.get_mut(index) {
+                        Some(logger) => return Err(logger.record_error(e)),
+                        None => return Err(e),
+                    },
+                };
+
+                match message.format {
+                    MessageFormat::BindComplete | MessageFormat::CloseComplete => {
+                        // harmless messages to ignore
+                    }
+
+                    MessageFormat::CommandComplete => {
+                        let cc: CommandComplete = message.decode()?;
+
+                        let rows_affected = cc.rows_affected();
+                        loggers[index].increase_rows_affected(rows_affected);
+                        index += 1;
+
+                        r#yield!(Either::Left(PgQueryResult { rows_affected }));
+                    }
+
+                    MessageFormat::EmptyQueryResponse => {
+                        // keep the results aligned with the queries
+                        index += 1;
+
+                        r#yield!(Either::Left(PgQueryResult::default()));
+                    }
+
+                    MessageFormat::DataRow => {
+                        loggers[index].increment_rows_returned();
+
+                        let data: DataRow = message.decode()?;
+                        let row = PgRow {
+                            data,
+                            format: PgValueFormat::Binary,
+                            metadata: Arc::clone(&statements[index].1),
+                        };
+
+                        r#yield!(Either::Right(row));
+                    }
+
+                    MessageFormat::ReadyForQuery => {
+                        // processing of the pipeline is complete
+                        conn.handle_ready_for_query(message)?;
+                        break;
+                    }
+
+                    _ => {
+                        return Err(err_protocol!(
+                            "pipeline: unexpected message: {:?}",
+                            message.format
+                        ));
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
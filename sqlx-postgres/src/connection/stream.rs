@@ -11,7 +11,7 @@ use crate::connection::tls::MaybeUpgradeTls;
 use crate::error::Error;
 use crate::io::{Decode, Encode};
 use crate::message::{Message, MessageFormat, Notice, Notification, ParameterStatus};
-use crate::net::{self, BufferedSocket, Socket};
+use crate::net::{self, BufferedSocket, Socket, WithSocket};
 use crate::{PgConnectOptions, PgDatabaseError, PgSeverity};
 
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
@@ -47,12 +47,26 @@ impl PgStream {
 
         let socket = socket_future.await?;
 
-        Ok(Self {
+        Ok(Self::from_socket(socket))
+    }
+
+    /// Set up a stream over an already-connected socket, upgrading to TLS as configured.
+    pub(super) async fn connect_with_socket(
+        options: &PgConnectOptions,
+        socket: Box<dyn Socket>,
+    ) -> Result<Self, Error> {
+        let socket = MaybeUpgradeTls(options).with_socket(socket).await?;
+
+        Ok(Self::from_socket(socket))
+    }
+
+    fn from_socket(socket: Box<dyn Socket>) -> Self {
+        Self {
             inner: BufferedSocket::new(socket),
             notifications: None,
             parameter_statuses: BTreeMap::default(),
             server_version_num: None,
-        })
+        }
     }
 
     pub(crate) async fn send<'en, T>(&mut self, message: T) -> Result<(), Error>
@@ -6,6 +6,7 @@ use futures_channel::mpsc;
 use futures_core::future::BoxFuture;
 use futures_core::stream::{BoxStream, Stream};
 use futures_util::{FutureExt, StreamExt, TryStreamExt};
+use sqlx_core::listener;
 use sqlx_core::Either;
 
 use crate::describe::Describe;
@@ -408,6 +409,32 @@ impl PgNotification {
     }
 }
 
+impl listener::Listener for PgListener {
+    type Notification = PgNotification;
+
+    fn listen<'a>(&'a mut self, channel: &'a str) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(PgListener::listen(self, channel))
+    }
+
+    fn unlisten<'a>(&'a mut self, channel: &'a str) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(PgListener::unlisten(self, channel))
+    }
+
+    fn recv(&mut self) -> BoxFuture<'_, Result<PgNotification, Error>> {
+        Box::pin(PgListener::recv(self))
+    }
+}
+
+impl listener::Notification for PgNotification {
+    fn channel(&self) -> &str {
+        PgNotification::channel(self)
+    }
+
+    fn payload(&self) -> &str {
+        PgNotification::payload(self)
+    }
+}
+
 impl Debug for PgListener {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PgListener").finish()
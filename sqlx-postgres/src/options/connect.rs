@@ -1,9 +1,12 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, QueryEventHandler, QueryRewriter};
 use crate::error::Error;
-use crate::{PgConnectOptions, PgConnection};
+use crate::{PgConnectOptions, PgConnection, Postgres};
 use futures_core::future::BoxFuture;
+use futures_io::{AsyncRead, AsyncWrite};
 use log::LevelFilter;
+use sqlx_core::net::StreamSocket;
 use sqlx_core::Url;
+use std::sync::Arc;
 use std::time::Duration;
 
 impl ConnectOptions for PgConnectOptions {
@@ -29,4 +32,32 @@ impl ConnectOptions for PgConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn event_handler(mut self, handler: Arc<dyn QueryEventHandler>) -> Self {
+        self.log_settings.event_handler(handler);
+        self
+    }
+
+    fn query_rewriter(mut self, rewriter: Arc<dyn QueryRewriter<Postgres>>) -> Self {
+        self.query_rewriter = Some(rewriter);
+        self
+    }
+}
+
+impl PgConnectOptions {
+    /// Establish a connection over an already-connected stream instead of opening a socket to
+    /// [`host`][Self::host] and [`port`][Self::port].
+    ///
+    /// This allows connecting through custom transports (e.g. an SSH tunnel or a proxy), or
+    /// over an in-memory duplex stream in tests. TLS is still negotiated according to
+    /// [`ssl_mode`][Self::ssl_mode].
+    ///
+    /// Note that statements exceeding a [`Query::timeout`](sqlx_core::query::Query::timeout)
+    /// are still cancelled by connecting to the configured host and port.
+    pub async fn connect_with_stream<S>(&self, stream: S) -> Result<PgConnection, Error>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + Sync + 'static,
+    {
+        PgConnection::establish_with_socket(self, Box::new(StreamSocket::new(stream))).await
+    }
 }
@@ -2,10 +2,12 @@ use std::borrow::Cow;
 use std::env::var;
 use std::fmt::{Display, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 pub use ssl_mode::PgSslMode;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::connection::{LogSettings, QueryRewriter};
+use crate::{net::tls::CertificateInput, Postgres};
 
 mod connect;
 mod parse;
@@ -100,6 +102,7 @@ pub struct PgConnectOptions {
     pub(crate) statement_cache_capacity: usize,
     pub(crate) application_name: Option<String>,
     pub(crate) log_settings: LogSettings,
+    pub(crate) query_rewriter: Option<Arc<dyn QueryRewriter<Postgres>>>,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
     pub(crate) options: Option<String>,
 }
@@ -167,6 +170,7 @@ impl PgConnectOptions {
             application_name: var("PGAPPNAME").ok(),
             extra_float_digits: Some("3".into()),
             log_settings: Default::default(),
+            query_rewriter: None,
             options: var("PGOPTIONS").ok(),
         }
     }
@@ -1,7 +1,6 @@
 use futures_core::future::BoxFuture;
 
 use crate::error::Error;
-use crate::executor::Executor;
 
 use crate::{PgConnection, Postgres};
 
@@ -15,7 +14,31 @@ impl TransactionManager for PgTransactionManager {
 
     fn begin(conn: &mut PgConnection) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
-            conn.execute(&*begin_ansi_transaction_sql(conn.transaction_depth))
+            conn.execute_internal(&begin_ansi_transaction_sql(conn.transaction_depth))
+                .await?;
+
+            conn.transaction_depth += 1;
+
+            Ok(())
+        })
+    }
+
+    fn begin_with(
+        conn: &mut PgConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            if options.is_default() {
+                return Self::begin(conn).await;
+            }
+
+            if conn.transaction_depth > 0 {
+                return Err(Error::Configuration(
+                    "transaction options cannot be set for a savepoint".into(),
+                ));
+            }
+
+            conn.execute_internal(&begin_transaction_sql(options))
                 .await?;
 
             conn.transaction_depth += 1;
@@ -27,7 +50,7 @@ impl TransactionManager for PgTransactionManager {
     fn commit(conn: &mut PgConnection) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             if conn.transaction_depth > 0 {
-                conn.execute(&*commit_ansi_transaction_sql(conn.transaction_depth))
+                conn.execute_internal(&commit_ansi_transaction_sql(conn.transaction_depth))
                     .await?;
 
                 conn.transaction_depth -= 1;
@@ -40,7 +63,7 @@ impl TransactionManager for PgTransactionManager {
     fn rollback(conn: &mut PgConnection) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             if conn.transaction_depth > 0 {
-                conn.execute(&*rollback_ansi_transaction_sql(conn.transaction_depth))
+                conn.execute_internal(&rollback_ansi_transaction_sql(conn.transaction_depth))
                     .await?;
 
                 conn.transaction_depth -= 1;
@@ -58,3 +81,44 @@ impl TransactionManager for PgTransactionManager {
         }
     }
 }
+
+// https://www.postgresql.org/docs/current/sql-begin.html
+fn begin_transaction_sql(options: TransactionOptions) -> String {
+    let mut modes = Vec::new();
+
+    if let Some(level) = options.get_isolation() {
+        modes.push(format!("ISOLATION LEVEL {}", level.as_sql()));
+    }
+
+    match options.get_read_only() {
+        Some(true) => modes.push("READ ONLY".into()),
+        Some(false) => modes.push("READ WRITE".into()),
+        None => {}
+    }
+
+    match options.get_deferrable() {
+        Some(true) => modes.push("DEFERRABLE".into()),
+        Some(false) => modes.push("NOT DEFERRABLE".into()),
+        None => {}
+    }
+
+    format!("BEGIN {}", modes.join(", "))
+}
+
+#[test]
+fn test_begin_transaction_sql() {
+    assert_eq!(
+        begin_transaction_sql(
+            TransactionOptions::new()
+                .isolation(IsolationLevel::Serializable)
+                .read_only(true)
+                .deferrable(true)
+        ),
+        "BEGIN ISOLATION LEVEL SERIALIZABLE, READ ONLY, DEFERRABLE"
+    );
+
+    assert_eq!(
+        begin_transaction_sql(TransactionOptions::new().read_only(false)),
+        "BEGIN READ WRITE"
+    );
+}
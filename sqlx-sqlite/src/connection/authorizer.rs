@@ -0,0 +1,267 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr::{null_mut, NonNull};
+
+use libsqlite3_sys::{
+    sqlite3_set_authorizer, SQLITE_ALTER_TABLE, SQLITE_ANALYZE, SQLITE_ATTACH, SQLITE_CREATE_INDEX,
+    SQLITE_CREATE_TABLE, SQLITE_CREATE_TEMP_INDEX, SQLITE_CREATE_TEMP_TABLE,
+    SQLITE_CREATE_TEMP_TRIGGER, SQLITE_CREATE_TEMP_VIEW, SQLITE_CREATE_TRIGGER, SQLITE_CREATE_VIEW,
+    SQLITE_CREATE_VTABLE, SQLITE_DELETE, SQLITE_DENY, SQLITE_DETACH, SQLITE_DROP_INDEX,
+    SQLITE_DROP_TABLE, SQLITE_DROP_TEMP_INDEX, SQLITE_DROP_TEMP_TABLE, SQLITE_DROP_TEMP_TRIGGER,
+    SQLITE_DROP_TEMP_VIEW, SQLITE_DROP_TRIGGER, SQLITE_DROP_VIEW, SQLITE_DROP_VTABLE,
+    SQLITE_FUNCTION, SQLITE_IGNORE, SQLITE_INSERT, SQLITE_OK, SQLITE_PRAGMA, SQLITE_READ,
+    SQLITE_RECURSIVE, SQLITE_REINDEX, SQLITE_SAVEPOINT, SQLITE_SELECT, SQLITE_TRANSACTION,
+    SQLITE_UPDATE,
+};
+
+use crate::connection::{ConnectionState, LockedSqliteHandle};
+
+/// The operation a statement is about to perform, see [`SqliteAuthorizerRequest`].
+///
+/// The meaning of the arguments of the request depends on the action, see
+/// [Authorizer Action Codes](https://www.sqlite.org/c3ref/c_alter_table.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteAuthorizerAction {
+    /// Index name, table name
+    CreateIndex,
+    /// Table name
+    CreateTable,
+    /// Index name, table name
+    CreateTempIndex,
+    /// Table name
+    CreateTempTable,
+    /// Trigger name, table name
+    CreateTempTrigger,
+    /// View name
+    CreateTempView,
+    /// Trigger name, table name
+    CreateTrigger,
+    /// View name
+    CreateView,
+    /// Table name
+    Delete,
+    /// Index name, table name
+    DropIndex,
+    /// Table name
+    DropTable,
+    /// Index name, table name
+    DropTempIndex,
+    /// Table name
+    DropTempTable,
+    /// Trigger name, table name
+    DropTempTrigger,
+    /// View name
+    DropTempView,
+    /// Trigger name, table name
+    DropTrigger,
+    /// View name
+    DropView,
+    /// Table name
+    Insert,
+    /// Pragma name, first argument or `None`
+    Pragma,
+    /// Table name, column name
+    Read,
+    Select,
+    /// Operation, e.g. `BEGIN`
+    Transaction,
+    /// Table name, column name
+    Update,
+    /// Filename
+    Attach,
+    /// Database name
+    Detach,
+    /// Database name, table name
+    AlterTable,
+    /// Index name
+    Reindex,
+    /// Table name
+    Analyze,
+    /// Table name, module name
+    CreateVtable,
+    /// Table name, module name
+    DropVtable,
+    /// Function name
+    Function,
+    /// Operation, savepoint name
+    Savepoint,
+    Recursive,
+    Unknown(i32),
+}
+
+impl From<c_int> for SqliteAuthorizerAction {
+    fn from(code: c_int) -> Self {
+        match code {
+            SQLITE_CREATE_INDEX => SqliteAuthorizerAction::CreateIndex,
+            SQLITE_CREATE_TABLE => SqliteAuthorizerAction::CreateTable,
+            SQLITE_CREATE_TEMP_INDEX => SqliteAuthorizerAction::CreateTempIndex,
+            SQLITE_CREATE_TEMP_TABLE => SqliteAuthorizerAction::CreateTempTable,
+            SQLITE_CREATE_TEMP_TRIGGER => SqliteAuthorizerAction::CreateTempTrigger,
+            SQLITE_CREATE_TEMP_VIEW => SqliteAuthorizerAction::CreateTempView,
+            SQLITE_CREATE_TRIGGER => SqliteAuthorizerAction::CreateTrigger,
+            SQLITE_CREATE_VIEW => SqliteAuthorizerAction::CreateView,
+            SQLITE_DELETE => SqliteAuthorizerAction::Delete,
+            SQLITE_DROP_INDEX => SqliteAuthorizerAction::DropIndex,
+            SQLITE_DROP_TABLE => SqliteAuthorizerAction::DropTable,
+            SQLITE_DROP_TEMP_INDEX => SqliteAuthorizerAction::DropTempIndex,
+            SQLITE_DROP_TEMP_TABLE => SqliteAuthorizerAction::DropTempTable,
+            SQLITE_DROP_TEMP_TRIGGER => SqliteAuthorizerAction::DropTempTrigger,
+            SQLITE_DROP_TEMP_VIEW => SqliteAuthorizerAction::DropTempView,
+            SQLITE_DROP_TRIGGER => SqliteAuthorizerAction::DropTrigger,
+            SQLITE_DROP_VIEW => SqliteAuthorizerAction::DropView,
+            SQLITE_INSERT => SqliteAuthorizerAction::Insert,
+            SQLITE_PRAGMA => SqliteAuthorizerAction::Pragma,
+            SQLITE_READ => SqliteAuthorizerAction::Read,
+            SQLITE_SELECT => SqliteAuthorizerAction::Select,
+            SQLITE_TRANSACTION => SqliteAuthorizerAction::Transaction,
+            SQLITE_UPDATE => SqliteAuthorizerAction::Update,
+            SQLITE_ATTACH => SqliteAuthorizerAction::Attach,
+            SQLITE_DETACH => SqliteAuthorizerAction::Detach,
+            SQLITE_ALTER_TABLE => SqliteAuthorizerAction::AlterTable,
+            SQLITE_REINDEX => SqliteAuthorizerAction::Reindex,
+            SQLITE_ANALYZE => SqliteAuthorizerAction::Analyze,
+            SQLITE_CREATE_VTABLE => SqliteAuthorizerAction::CreateVtable,
+            SQLITE_DROP_VTABLE => SqliteAuthorizerAction::DropVtable,
+            SQLITE_FUNCTION => SqliteAuthorizerAction::Function,
+            SQLITE_SAVEPOINT => SqliteAuthorizerAction::Savepoint,
+            SQLITE_RECURSIVE => SqliteAuthorizerAction::Recursive,
+            code => SqliteAuthorizerAction::Unknown(code),
+        }
+    }
+}
+
+/// An operation of a statement being prepared, passed to an authorizer set with
+/// [`LockedSqliteHandle::set_authorizer()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqliteAuthorizerRequest<'a> {
+    /// The operation the statement is about to perform.
+    pub action: SqliteAuthorizerAction,
+
+    /// The first argument of the action, e.g. the name of a table.
+    pub arg1: Option<&'a str>,
+
+    /// The second argument of the action, e.g. the name of a column.
+    pub arg2: Option<&'a str>,
+
+    /// The name of the database the action applies to, e.g. `main` or `temp`.
+    pub database: Option<&'a str>,
+
+    /// The name of the innermost trigger or view which is responsible for the action,
+    /// or `None` if the action is performed directly by the statement.
+    pub accessor: Option<&'a str>,
+}
+
+/// The decision of an authorizer on a [`SqliteAuthorizerRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteAuthorization {
+    /// Allow the action.
+    Allow,
+
+    /// Fail preparing the statement with the error `SQLITE_AUTH` ("not authorized").
+    Deny,
+
+    /// Disallow the action but continue preparing the statement, e.g. columns which may not be
+    /// read are read as `NULL`. The effect depends on the action, see
+    /// [`sqlite3_set_authorizer()`](https://www.sqlite.org/c3ref/set_authorizer.html).
+    Ignore,
+}
+
+/// Represents an authorizer that will be shared with the underlying sqlite3 connection.
+pub(crate) struct AuthorizerHandler(
+    NonNull<dyn FnMut(SqliteAuthorizerRequest<'_>) -> SqliteAuthorization + Send + 'static>,
+);
+unsafe impl Send for AuthorizerHandler {}
+
+impl ConnectionState {
+    /// Drops the `authorizer_callback` if it exists.
+    pub(crate) fn remove_authorizer(&mut self) {
+        if let Some(mut handler) = self.authorizer_callback.take() {
+            unsafe {
+                sqlite3_set_authorizer(self.handle.as_ptr(), None, null_mut());
+                let _ = { Box::from_raw(handler.0.as_mut()) };
+            }
+        }
+    }
+}
+
+impl LockedSqliteHandle<'_> {
+    /// Sets a callback which is invoked for every operation of a statement while it is prepared,
+    /// to allow, deny or ignore the operation, e.g. to restrict the tables that SQL supplied by
+    /// users may access.
+    ///
+    /// A statement with a denied operation fails to prepare with the error `SQLITE_AUTH`
+    /// (extended result code `23`). The callback is also invoked for statements executed by
+    /// SQLx itself, like `BEGIN` or `PRAGMA`; statements prepared before the callback was set
+    /// are prepared again and re-authorized before they are executed next.
+    ///
+    /// Only a single authorizer may be defined at one time per database connection; setting a
+    /// new authorizer removes the old one. The callback must not use the database connection.
+    ///
+    /// See [`sqlite3_set_authorizer()`](https://www.sqlite.org/c3ref/set_authorizer.html) for details.
+    pub fn set_authorizer<F>(&mut self, callback: F)
+    where
+        F: FnMut(SqliteAuthorizerRequest<'_>) -> SqliteAuthorization + Send + 'static,
+    {
+        unsafe {
+            let callback_boxed = Box::new(callback);
+            // SAFETY: `Box::into_raw()` always returns a non-null pointer.
+            let callback = NonNull::new_unchecked(Box::into_raw(callback_boxed));
+            let handler = callback.as_ptr() as *mut _;
+            self.guard.remove_authorizer();
+            self.guard.authorizer_callback = Some(AuthorizerHandler(callback));
+
+            sqlite3_set_authorizer(
+                self.as_raw_handle().as_mut(),
+                Some(authorizer::<F>),
+                handler,
+            );
+        }
+    }
+
+    /// Removes the authorizer on a database connection. The method does nothing if no authorizer was set.
+    pub fn remove_authorizer(&mut self) {
+        self.guard.remove_authorizer();
+    }
+}
+
+/// Implements a C binding to an authorizer. Panics deny the action.
+extern "C" fn authorizer<F>(
+    callback: *mut c_void,
+    action: c_int,
+    arg1: *const c_char,
+    arg2: *const c_char,
+    database: *const c_char,
+    accessor: *const c_char,
+) -> c_int
+where
+    F: FnMut(SqliteAuthorizerRequest<'_>) -> SqliteAuthorization,
+{
+    unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+        if s.is_null() {
+            None
+        } else {
+            CStr::from_ptr(s).to_str().ok()
+        }
+    }
+
+    unsafe {
+        let r = catch_unwind(AssertUnwindSafe(|| {
+            let callback: *mut F = callback.cast::<F>();
+
+            (*callback)(SqliteAuthorizerRequest {
+                action: action.into(),
+                arg1: to_str(arg1),
+                arg2: to_str(arg2),
+                database: to_str(database),
+                accessor: to_str(accessor),
+            })
+        }));
+
+        match r {
+            Ok(SqliteAuthorization::Allow) => SQLITE_OK,
+            Ok(SqliteAuthorization::Ignore) => SQLITE_IGNORE,
+            Ok(SqliteAuthorization::Deny) | Err(_) => SQLITE_DENY,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::task::{Context, Poll};
+
+use libsqlite3_sys::{
+    sqlite3_backup, sqlite3_backup_finish, sqlite3_backup_init, sqlite3_backup_pagecount,
+    sqlite3_backup_remaining, sqlite3_backup_step, SQLITE_BUSY, SQLITE_DONE, SQLITE_LOCKED,
+    SQLITE_OK,
+};
+
+use crate::connection::handle::ConnectionHandle;
+use crate::error::Error;
+use crate::SqliteError;
+
+/// The progress of an online backup, see
+/// [`SqliteConnection::backup_to()`](crate::SqliteConnection::backup_to).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqliteBackupProgress {
+    /// The number of pages which remain to be copied.
+    pub remaining: u32,
+
+    /// The total number of pages in the source database.
+    pub page_count: u32,
+}
+
+// name of the main database of a connection
+static MAIN: &[u8] = b"main\0";
+
+/// Finishes the backup on drop, releasing its locks.
+struct Backup(NonNull<sqlite3_backup>);
+
+// SAFETY: the backup is only used while both database handles are locked
+unsafe impl Send for Backup {}
+
+impl Drop for Backup {
+    fn drop(&mut self) {
+        // https://www.sqlite.org/c3ref/backup_finish.html#sqlite3backupfinish
+        unsafe {
+            sqlite3_backup_finish(self.0.as_ptr());
+        }
+    }
+}
+
+/// Copies the main database of `source` into `dest`, `pages_per_step` pages at a time.
+///
+/// <https://www.sqlite.org/backup.html>
+pub(crate) async fn backup(
+    source: &mut ConnectionHandle,
+    dest: &mut ConnectionHandle,
+    pages_per_step: i32,
+    mut progress: impl FnMut(SqliteBackupProgress),
+) -> Result<(), Error> {
+    // https://www.sqlite.org/c3ref/backup_finish.html#sqlite3backupinit
+    let backup = unsafe {
+        sqlite3_backup_init(
+            dest.as_ptr(),
+            MAIN.as_ptr().cast(),
+            source.as_ptr(),
+            MAIN.as_ptr().cast(),
+        )
+    };
+
+    let backup = match NonNull::new(backup) {
+        Some(backup) => Backup(backup),
+        // the error is stored in the destination connection
+        None => return Err(SqliteError::new(dest.as_ptr()).into()),
+    };
+
+    loop {
+        let status = unsafe { sqlite3_backup_step(backup.0.as_ptr(), pages_per_step) };
+
+        let (remaining, page_count) = unsafe {
+            (
+                sqlite3_backup_remaining(backup.0.as_ptr()),
+                sqlite3_backup_pagecount(backup.0.as_ptr()),
+            )
+        };
+
+        progress(SqliteBackupProgress {
+            remaining: remaining as u32,
+            page_count: page_count as u32,
+        });
+
+        match status {
+            SQLITE_DONE => break,
+
+            // more pages remain, or the databases are locked by another connection and
+            // the busy handler of the destination already waited for them
+            SQLITE_OK | SQLITE_BUSY | SQLITE_LOCKED => YieldNow(false).await,
+
+            // errors of the backup are reported by the destination connection
+            _ => return Err(SqliteError::new(dest.as_ptr()).into()),
+        }
+    }
+
+    let status = unsafe { sqlite3_backup_finish(backup.0.as_ptr()) };
+    std::mem::forget(backup);
+
+    if status != SQLITE_OK {
+        return Err(SqliteError::new(dest.as_ptr()).into());
+    }
+
+    Ok(())
+}
+
+// yields to other tasks between the steps of a backup
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+
+        Poll::Pending
+    }
+}
@@ -0,0 +1,241 @@
+use std::cmp;
+use std::ffi::CString;
+use std::io;
+use std::os::raw::{c_int, c_void};
+use std::pin::Pin;
+use std::ptr::{null_mut, NonNull};
+use std::task::{Context, Poll};
+
+use futures_io::{AsyncRead, AsyncSeek, AsyncWrite, SeekFrom};
+use libsqlite3_sys::{
+    sqlite3_blob, sqlite3_blob_bytes, sqlite3_blob_close, sqlite3_blob_open, sqlite3_blob_read,
+    sqlite3_blob_reopen, sqlite3_blob_write, SQLITE_OK,
+};
+
+use crate::connection::LockedSqliteHandle;
+use crate::error::Error;
+use crate::SqliteError;
+
+// name of the main database of a connection
+static MAIN: &[u8] = b"main\0";
+
+/// A handle for incremental I/O on a BLOB, see
+/// [`SqliteConnection::blob_open()`](crate::SqliteConnection::blob_open).
+///
+/// The handle implements [`AsyncRead`], [`AsyncWrite`] and [`AsyncSeek`], so that large values
+/// can be streamed without loading them into memory at once. The size of the BLOB cannot be
+/// changed: reading stops at its end and writing past its end fails with
+/// [`io::ErrorKind::WriteZero`]. To create a BLOB for writing, insert a `zeroblob(N)` of the
+/// required size first.
+///
+/// The connection is locked while the handle exists. Reads and writes are performed without
+/// waiting, as calls into SQLite on the current thread.
+pub struct SqliteBlob<'c> {
+    handle: LockedSqliteHandle<'c>,
+    blob: NonNull<sqlite3_blob>,
+    len: u64,
+    position: u64,
+}
+
+// SAFETY: the BLOB is only used while the connection is locked
+unsafe impl Send for SqliteBlob<'_> {}
+
+impl<'c> SqliteBlob<'c> {
+    pub(crate) fn open(
+        handle: LockedSqliteHandle<'c>,
+        table: &str,
+        column: &str,
+        rowid: i64,
+        read_only: bool,
+    ) -> Result<Self, Error> {
+        let table =
+            CString::new(table).map_err(|_| err_protocol!("invalid table name: {:?}", table))?;
+        let column =
+            CString::new(column).map_err(|_| err_protocol!("invalid column name: {:?}", column))?;
+
+        let db = handle.guard.handle.as_ptr();
+        let mut blob = null_mut();
+
+        // https://www.sqlite.org/c3ref/blob_open.html
+        let status = unsafe {
+            sqlite3_blob_open(
+                db,
+                MAIN.as_ptr().cast(),
+                table.as_ptr(),
+                column.as_ptr(),
+                rowid,
+                c_int::from(!read_only),
+                &mut blob,
+            )
+        };
+
+        let blob = match NonNull::new(blob) {
+            Some(blob) if status == SQLITE_OK => blob,
+            _ => return Err(SqliteError::new(db).into()),
+        };
+
+        let len = unsafe { sqlite3_blob_bytes(blob.as_ptr()) } as u64;
+
+        Ok(SqliteBlob {
+            handle,
+            blob,
+            len,
+            position: 0,
+        })
+    }
+
+    /// Returns the size of the BLOB in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the BLOB is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Moves the handle to the same column of another row of the table, and rewinds it.
+    ///
+    /// This is faster than opening a new handle. If it fails, the handle can no longer be used.
+    ///
+    /// See [`sqlite3_blob_reopen()`](https://www.sqlite.org/c3ref/blob_reopen.html) for details.
+    pub fn reopen(&mut self, rowid: i64) -> Result<(), Error> {
+        let status = unsafe { sqlite3_blob_reopen(self.blob.as_ptr(), rowid) };
+
+        if status != SQLITE_OK {
+            return Err(SqliteError::new(self.handle.guard.handle.as_ptr()).into());
+        }
+
+        self.len = unsafe { sqlite3_blob_bytes(self.blob.as_ptr()) } as u64;
+        self.position = 0;
+
+        Ok(())
+    }
+
+    fn io_error(&self) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Other,
+            SqliteError::new(self.handle.guard.handle.as_ptr()),
+        )
+    }
+
+    /// Returns the number of bytes from the current position to the end, limited to `n`.
+    fn remaining(&self, n: usize) -> usize {
+        cmp::min(self.len.saturating_sub(self.position), n as u64) as usize
+    }
+}
+
+impl AsyncRead for SqliteBlob<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let n = self.remaining(buf.len());
+
+        if n == 0 {
+            return Poll::Ready(Ok(0));
+        }
+
+        // https://www.sqlite.org/c3ref/blob_read.html
+        let status = unsafe {
+            sqlite3_blob_read(
+                self.blob.as_ptr(),
+                buf.as_mut_ptr() as *mut c_void,
+                n as c_int,
+                self.position as c_int,
+            )
+        };
+
+        if status != SQLITE_OK {
+            return Poll::Ready(Err(self.io_error()));
+        }
+
+        self.position += n as u64;
+
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl AsyncWrite for SqliteBlob<'_> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let n = self.remaining(buf.len());
+
+        if n == 0 {
+            return Poll::Ready(Ok(0));
+        }
+
+        // https://www.sqlite.org/c3ref/blob_write.html
+        let status = unsafe {
+            sqlite3_blob_write(
+                self.blob.as_ptr(),
+                buf.as_ptr() as *const c_void,
+                n as c_int,
+                self.position as c_int,
+            )
+        };
+
+        if status != SQLITE_OK {
+            return Poll::Ready(Err(self.io_error()));
+        }
+
+        self.position += n as u64;
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // writes go directly to the database
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncSeek for SqliteBlob<'_> {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => add_offset(self.len, offset),
+            SeekFrom::Current(offset) => add_offset(self.position, offset),
+        };
+
+        match position {
+            Some(position) => {
+                self.position = position;
+                Poll::Ready(Ok(position))
+            }
+            None => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ))),
+        }
+    }
+}
+
+fn add_offset(position: u64, offset: i64) -> Option<u64> {
+    if offset >= 0 {
+        position.checked_add(offset as u64)
+    } else {
+        position.checked_sub(offset.unsigned_abs())
+    }
+}
+
+impl Drop for SqliteBlob<'_> {
+    fn drop(&mut self) {
+        // https://www.sqlite.org/c3ref/blob_close.html
+        unsafe {
+            sqlite3_blob_close(self.blob.as_ptr());
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::cmp;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::os::raw::{c_int, c_void};
+use std::panic::catch_unwind;
+use std::ptr::{null_mut, NonNull};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use libsqlite3_sys::sqlite3_busy_handler;
+
+use crate::connection::{ConnectionState, LockedSqliteHandle};
+
+/// Represents a busy handler that will be shared with the underlying sqlite3 connection.
+pub(crate) struct BusyHandler(NonNull<dyn FnMut(u32) -> bool + Send + 'static>);
+unsafe impl Send for BusyHandler {}
+
+impl ConnectionState {
+    pub(crate) fn set_busy_handler<F>(&mut self, callback: F)
+    where
+        F: FnMut(u32) -> bool + Send + 'static,
+    {
+        unsafe {
+            let callback_boxed = Box::new(callback);
+            // SAFETY: `Box::into_raw()` always returns a non-null pointer.
+            let callback = NonNull::new_unchecked(Box::into_raw(callback_boxed));
+            let handler = callback.as_ptr() as *mut _;
+            self.remove_busy_handler();
+            self.busy_handler_callback = Some(BusyHandler(callback));
+
+            sqlite3_busy_handler(self.handle.as_ptr(), Some(busy_callback::<F>), handler);
+        }
+    }
+
+    /// Drops the `busy_handler_callback` if it exists.
+    pub(crate) fn remove_busy_handler(&mut self) {
+        if let Some(mut handler) = self.busy_handler_callback.take() {
+            unsafe {
+                sqlite3_busy_handler(self.handle.as_ptr(), None, null_mut());
+                let _ = { Box::from_raw(handler.0.as_mut()) };
+            }
+        }
+    }
+}
+
+impl LockedSqliteHandle<'_> {
+    /// Sets a callback which is invoked when a statement cannot access a table because it is
+    /// locked by another connection (`SQLITE_BUSY`).
+    ///
+    /// The callback is passed the number of times it was invoked for the same lock before. If it
+    /// returns `true`, SQLite tries to access the table again, otherwise the statement fails with
+    /// `SQLITE_BUSY`. The callback is invoked on the worker thread of the connection, so it may
+    /// sleep before retrying without blocking other tasks.
+    ///
+    /// This replaces the [busy timeout](crate::SqliteConnectOptions::busy_timeout) and any
+    /// [backoff](crate::SqliteConnectOptions::busy_backoff) configured for the connection.
+    /// The callback must not use the database connection.
+    ///
+    /// See [`sqlite3_busy_handler()`](https://www.sqlite.org/c3ref/busy_handler.html) for details.
+    pub fn set_busy_handler<F>(&mut self, callback: F)
+    where
+        F: FnMut(u32) -> bool + Send + 'static,
+    {
+        self.guard.set_busy_handler(callback);
+    }
+
+    /// Removes the busy handler on a database connection, so that statements fail immediately
+    /// if a table is locked. The method does nothing if no handler was set.
+    pub fn remove_busy_handler(&mut self) {
+        self.guard.remove_busy_handler();
+    }
+}
+
+/// Implements a C binding to a busy handler. The function returns `1` if the user-provided
+/// callback returns `true` to retry, and `0` otherwise.
+extern "C" fn busy_callback<F>(callback: *mut c_void, count: c_int) -> c_int
+where
+    F: FnMut(u32) -> bool,
+{
+    unsafe {
+        let r = catch_unwind(|| {
+            let callback: *mut F = callback.cast::<F>();
+            (*callback)(count as u32)
+        });
+        c_int::from(r.unwrap_or_default())
+    }
+}
+
+/// Returns a busy handler which retries with exponential backoff and jitter, starting at
+/// `initial` and doubling up to `max`, until `timeout` passed since the first retry.
+pub(crate) fn backoff(
+    initial: Duration,
+    max: Duration,
+    timeout: Duration,
+) -> impl FnMut(u32) -> bool + Send + 'static {
+    let random = RandomState::new();
+    let mut started = Instant::now();
+
+    move |count| {
+        if count == 0 {
+            started = Instant::now();
+        }
+
+        let remaining = match timeout.checked_sub(started.elapsed()) {
+            Some(remaining) if !remaining.is_zero() => remaining,
+            _ => return false,
+        };
+
+        let delay = initial
+            .checked_mul(1 << cmp::min(count, 16))
+            .map_or(max, |delay| cmp::min(delay, max));
+
+        // sleep between half and all of the delay, so that concurrent writers which were
+        // blocked at the same time do not retry at the same time
+        let mut hasher = random.build_hasher();
+        (count, Instant::now()).hash(&mut hasher);
+        let jitter = delay / 2 * (hasher.finish() % 1024) as u32 / 1024;
+
+        thread::sleep(cmp::min(delay / 2 + jitter, remaining));
+
+        true
+    }
+}
@@ -0,0 +1,44 @@
+/// Refer to [SQLite documentation] for the meaning of the checkpoint modes.
+///
+/// [SQLite documentation]: https://www.sqlite.org/pragma.html#pragma_wal_checkpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteCheckpointMode {
+    Passive,
+    Full,
+    Restart,
+    Truncate,
+}
+
+impl SqliteCheckpointMode {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            SqliteCheckpointMode::Passive => "PASSIVE",
+            SqliteCheckpointMode::Full => "FULL",
+            SqliteCheckpointMode::Restart => "RESTART",
+            SqliteCheckpointMode::Truncate => "TRUNCATE",
+        }
+    }
+}
+
+impl Default for SqliteCheckpointMode {
+    fn default() -> Self {
+        SqliteCheckpointMode::Passive
+    }
+}
+
+/// The result of a checkpoint, see
+/// [`SqliteConnection::wal_checkpoint()`](crate::SqliteConnection::wal_checkpoint).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqliteCheckpoint {
+    /// `true` if a `FULL`, `RESTART` or `TRUNCATE` checkpoint could not complete because
+    /// of other connections reading or writing the database.
+    pub busy: bool,
+
+    /// The number of frames (modified pages) in the write-ahead log, or `-1` if the database
+    /// is not in WAL mode.
+    pub log_frames: i64,
+
+    /// The number of frames in the write-ahead log which were written back into the database,
+    /// or `-1` if the database is not in WAL mode.
+    pub checkpointed_frames: i64,
+}
@@ -79,42 +79,6 @@ impl Debug for Collation {
     }
 }
 
-pub(crate) fn create_collation<F>(
-    handle: &mut ConnectionHandle,
-    name: &str,
-    compare: F,
-) -> Result<(), Error>
-where
-    F: Fn(&str, &str) -> Ordering + Send + Sync + 'static,
-{
-    unsafe extern "C" fn free_boxed_value<T>(p: *mut c_void) {
-        drop(Box::from_raw(p as *mut T));
-    }
-
-    let boxed_f: *mut F = Box::into_raw(Box::new(compare));
-    let c_name =
-        CString::new(name).map_err(|_| err_protocol!("invalid collation name: {}", name))?;
-    let flags = SQLITE_UTF8;
-    let r = unsafe {
-        sqlite3_create_collation_v2(
-            handle.as_ptr(),
-            c_name.as_ptr(),
-            flags,
-            boxed_f as *mut c_void,
-            Some(call_boxed_closure::<F>),
-            Some(free_boxed_value::<F>),
-        )
-    };
-
-    if r == SQLITE_OK {
-        Ok(())
-    } else {
-        // The xDestroy callback is not called if the sqlite3_create_collation_v2() function fails.
-        drop(unsafe { Box::from_raw(boxed_f) });
-        Err(Error::Database(Box::new(SqliteError::new(handle.as_ptr()))))
-    }
-}
-
 unsafe extern "C" fn call_boxed_closure<C>(
     data: *mut c_void,
     left_len: c_int,
@@ -1,47 +1,34 @@
+use crate::connection::busy;
 use crate::connection::handle::ConnectionHandle;
 use crate::connection::LogSettings;
 use crate::connection::{ConnectionState, Statements};
 use crate::error::Error;
 use crate::{SqliteConnectOptions, SqliteError};
 use libsqlite3_sys::{
-    sqlite3, sqlite3_busy_timeout, sqlite3_db_config, sqlite3_extended_result_codes, sqlite3_free,
-    sqlite3_load_extension, sqlite3_open_v2, SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION, SQLITE_OK,
+    sqlite3_busy_timeout, sqlite3_extended_result_codes, sqlite3_open_v2, SQLITE_OK,
     SQLITE_OPEN_CREATE, SQLITE_OPEN_FULLMUTEX, SQLITE_OPEN_MEMORY, SQLITE_OPEN_NOMUTEX,
     SQLITE_OPEN_PRIVATECACHE, SQLITE_OPEN_READONLY, SQLITE_OPEN_READWRITE, SQLITE_OPEN_SHAREDCACHE,
 };
 use sqlx_core::IndexMap;
-use std::ffi::{c_void, CStr, CString};
+use std::ffi::CString;
 use std::io;
-use std::os::raw::c_int;
-use std::ptr::{addr_of_mut, null, null_mut};
+use std::ptr::{null, null_mut};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 static THREAD_ID: AtomicU64 = AtomicU64::new(0);
 
-enum SqliteLoadExtensionMode {
-    /// Enables only the C-API, leaving the SQL function disabled.
-    Enable,
-    /// Disables both the C-API and the SQL function.
-    DisableAll,
-}
-
-impl SqliteLoadExtensionMode {
-    fn as_int(self) -> c_int {
-        match self {
-            SqliteLoadExtensionMode::Enable => 1,
-            SqliteLoadExtensionMode::DisableAll => 0,
-        }
-    }
-}
-
 pub struct EstablishParams {
     filename: CString,
     open_flags: i32,
     busy_timeout: Duration,
+    busy_backoff: Option<(Duration, Duration)>,
+    statement_timeout: Option<Duration>,
     statement_cache_capacity: usize,
+    prepare_persistent: bool,
     log_settings: LogSettings,
     extensions: IndexMap<CString, Option<CString>>,
+    load_extension_sql: bool,
     pub(crate) thread_name: String,
     pub(crate) command_channel_size: usize,
     #[cfg(feature = "regexp")]
@@ -91,6 +78,13 @@ impl EstablishParams {
 
         let mut query_params: Vec<String> = vec![];
 
+        // A named in-memory database is only shared between connections opened by URI
+        // <https://www.sqlite.org/inmemorydb.html#sharedmemdb>
+        if options.in_memory && options.shared_cache {
+            query_params.push("mode=memory".into());
+            query_params.push("cache=shared".into());
+        }
+
         if options.immutable {
             query_params.push("immutable=true".into())
         }
@@ -100,7 +94,9 @@ impl EstablishParams {
         }
 
         if !query_params.is_empty() {
-            filename = format!("file:{}?{}", filename, query_params.join("&"));
+            // the filename may already be a URI, e.g. `file:name` from a connection URL
+            let name = filename.strip_prefix("file:").unwrap_or(&filename);
+            filename = format!("file:{}?{}", name, query_params.join("&"));
             flags |= libsqlite3_sys::SQLITE_OPEN_URI;
         }
 
@@ -142,9 +138,13 @@ impl EstablishParams {
             filename,
             open_flags: flags,
             busy_timeout: options.busy_timeout,
+            busy_backoff: options.busy_backoff,
+            statement_timeout: options.statement_timeout,
             statement_cache_capacity: options.statement_cache_capacity,
+            prepare_persistent: options.prepare_persistent,
             log_settings: options.log_settings.clone(),
             extensions,
+            load_extension_sql: options.load_extension_sql,
             thread_name: (options.thread_name)(THREAD_ID.fetch_add(1, Ordering::AcqRel)),
             command_channel_size: options.command_channel_size,
             #[cfg(feature = "regexp")]
@@ -152,28 +152,6 @@ impl EstablishParams {
         })
     }
 
-    // Enable extension loading via the db_config function, as recommended by the docs rather
-    // than the more obvious `sqlite3_enable_load_extension`
-    // https://www.sqlite.org/c3ref/db_config.html
-    // https://www.sqlite.org/c3ref/c_dbconfig_defensive.html#sqlitedbconfigenableloadextension
-    unsafe fn sqlite3_set_load_extension(
-        db: *mut sqlite3,
-        mode: SqliteLoadExtensionMode,
-    ) -> Result<(), Error> {
-        let status = sqlite3_db_config(
-            db,
-            SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION,
-            mode.as_int(),
-            null::<i32>(),
-        );
-
-        if status != SQLITE_OK {
-            return Err(Error::Database(Box::new(SqliteError::new(db))));
-        }
-
-        Ok(())
-    }
-
     pub(crate) fn establish(&self) -> Result<ConnectionState, Error> {
         let mut handle = null_mut();
 
@@ -192,7 +170,7 @@ impl EstablishParams {
 
         // SAFE: tested for NULL just above
         // This allows any returns below to close this handle with RAII
-        let handle = unsafe { ConnectionHandle::new(handle) };
+        let mut handle = unsafe { ConnectionHandle::new(handle) };
 
         if status != SQLITE_OK {
             return Err(Error::Database(Box::new(SqliteError::new(handle.as_ptr()))));
@@ -205,52 +183,22 @@ impl EstablishParams {
             sqlite3_extended_result_codes(handle.as_ptr(), 1);
         }
 
-        if !self.extensions.is_empty() {
+        if !self.extensions.is_empty() || self.load_extension_sql {
             // Enable loading extensions
-            unsafe {
-                Self::sqlite3_set_load_extension(handle.as_ptr(), SqliteLoadExtensionMode::Enable)?;
+            handle.set_load_extension(true)?;
+
+            for (name, entry_point) in self.extensions.iter() {
+                handle.load_extension(name, entry_point.as_deref())?;
             }
 
-            for ext in self.extensions.iter() {
-                // `sqlite3_load_extension` is unusual as it returns its errors via an out-pointer
-                // rather than by calling `sqlite3_errmsg`
-                let mut error = null_mut();
-                status = unsafe {
-                    sqlite3_load_extension(
-                        handle.as_ptr(),
-                        ext.0.as_ptr(),
-                        ext.1.as_ref().map_or(null(), |e| e.as_ptr()),
-                        addr_of_mut!(error),
-                    )
-                };
-
-                if status != SQLITE_OK {
-                    // SAFETY: We become responsible for any memory allocation at `&error`, so test
-                    // for null and take an RAII version for returns
-                    let err_msg = if !error.is_null() {
-                        unsafe {
-                            let e = CStr::from_ptr(error).into();
-                            sqlite3_free(error as *mut c_void);
-                            e
-                        }
-                    } else {
-                        CString::new("Unknown error when loading extension")
-                            .expect("text should be representable as a CString")
-                    };
-                    return Err(Error::Database(Box::new(SqliteError::extension(
-                        handle.as_ptr(),
-                        &err_msg,
-                    ))));
-                }
-            } // Preempt any hypothetical security issues arising from leaving ENABLE_LOAD_EXTENSION
-              // on by disabling the flag again once we've loaded all the requested modules.
-              // Fail-fast (via `?`) if disabling the extension loader didn't work for some reason,
-              // avoids an unexpected state going undetected.
-            unsafe {
-                Self::sqlite3_set_load_extension(
-                    handle.as_ptr(),
-                    SqliteLoadExtensionMode::DisableAll,
-                )?;
+            if self.load_extension_sql {
+                handle.enable_load_extension_sql()?;
+            } else {
+                // Preempt any hypothetical security issues arising from leaving ENABLE_LOAD_EXTENSION
+                // on by disabling the flag again once we've loaded all the requested modules.
+                // Fail-fast (via `?`) if disabling the extension loader didn't work for some reason,
+                // avoids an unexpected state going undetected.
+                handle.set_load_extension(false)?;
             }
         }
 
@@ -277,12 +225,27 @@ impl EstablishParams {
             return Err(Error::Database(Box::new(SqliteError::new(handle.as_ptr()))));
         }
 
-        Ok(ConnectionState {
+        let mut state = ConnectionState {
             handle,
-            statements: Statements::new(self.statement_cache_capacity),
+            statements: Statements::new(self.statement_cache_capacity, self.prepare_persistent),
             transaction_depth: 0,
             log_settings: self.log_settings.clone(),
-            progress_handler_callback: None,
-        })
+            progress: Default::default(),
+            statement_timeout: self.statement_timeout,
+            update_hook_callback: None,
+            commit_hook_callback: None,
+            rollback_hook_callback: None,
+            busy_handler_callback: None,
+            authorizer_callback: None,
+            #[cfg(feature = "session")]
+            session: None,
+        };
+
+        // Replace the busy timeout with a backoff which is bounded by the same timeout
+        if let Some((initial, max)) = self.busy_backoff {
+            state.set_busy_handler(busy::backoff(initial, max, self.busy_timeout));
+        }
+
+        Ok(state)
     }
 }
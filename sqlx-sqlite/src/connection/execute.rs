@@ -15,6 +15,9 @@ pub struct ExecuteIter<'a> {
     /// this keeps track of the number of arguments so far
     args_used: usize,
 
+    /// the total changes of the connection before the current statement was executed
+    total_changes: u64,
+
     goto_next: bool,
 }
 
@@ -27,7 +30,8 @@ pub(crate) fn iter<'a>(
     // fetch the cached statement or allocate a new one
     let statement = conn.statements.get(query, persistent)?;
 
-    let logger = QueryLogger::new(query, conn.log_settings.clone());
+    let mut logger = QueryLogger::new(query, conn.log_settings.clone());
+    logger.set_arguments(args.as_ref().map_or(0, |args| args.values.len()));
 
     Ok(ExecuteIter {
         handle: &mut conn.handle,
@@ -35,6 +39,7 @@ pub(crate) fn iter<'a>(
         logger,
         args,
         args_used: 0,
+        total_changes: 0,
         goto_next: true,
     })
 }
@@ -67,6 +72,15 @@ impl Iterator for ExecuteIter<'_> {
     type Item = Result<Either<SqliteQueryResult, SqliteRow>, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        match self.execute_next()? {
+            Ok(step) => Some(Ok(step)),
+            Err(e) => Some(Err(self.logger.record_error(e))),
+        }
+    }
+}
+
+impl ExecuteIter<'_> {
+    fn execute_next(&mut self) -> Option<Result<Either<SqliteQueryResult, SqliteRow>, Error>> {
         let statement = if self.goto_next {
             let mut statement = match self.statement.prepare_next(self.handle) {
                 Ok(Some(statement)) => statement,
@@ -88,6 +102,8 @@ impl Iterator for ExecuteIter<'_> {
                 Err(e) => return Some(Err(e)),
             }
 
+            self.total_changes = self.handle.total_changes();
+
             statement
         } else {
             self.statement.current()?
@@ -106,7 +122,13 @@ impl Iterator for ExecuteIter<'_> {
             Ok(false) => {
                 let last_insert_rowid = self.handle.last_insert_rowid();
 
-                let changes = statement.handle.changes();
+                // `changes()` still returns the changes of a previous statement if this statement
+                // did not change any rows, e.g. a `SELECT` or `CREATE TABLE`
+                let changes = if self.handle.total_changes() == self.total_changes {
+                    0
+                } else {
+                    statement.handle.changes()
+                };
                 self.logger.increase_rows_affected(changes);
 
                 let done = SqliteQueryResult {
@@ -4,6 +4,7 @@ use crate::{
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{TryFutureExt, TryStreamExt};
+use sqlx_core::connection::rewrite_query;
 use sqlx_core::describe::Describe;
 use sqlx_core::error::Error;
 use sqlx_core::executor::{Execute, Executor};
@@ -21,14 +22,20 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         E: Execute<'q, Self::Database>,
     {
         let sql = query.sql();
-        let arguments = query.take_arguments();
+        let mut arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
+        let timeout = query.timeout();
 
         Box::pin(
-            self.worker
-                .execute(sql, arguments, self.row_channel_size, persistent)
-                .map_ok(flume::Receiver::into_stream)
-                .try_flatten_stream(),
+            async move {
+                let sql = rewrite_query(self.query_rewriter.as_ref(), sql, arguments.as_mut())?;
+
+                self.worker
+                    .execute(&sql, arguments, self.row_channel_size, persistent, timeout)
+                    .await
+            }
+            .map_ok(flume::Receiver::into_stream)
+            .try_flatten_stream(),
         )
     }
 
@@ -41,13 +48,16 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         E: Execute<'q, Self::Database>,
     {
         let sql = query.sql();
-        let arguments = query.take_arguments();
+        let mut arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
+        let timeout = query.timeout();
 
         Box::pin(async move {
+            let sql = rewrite_query(self.query_rewriter.as_ref(), sql, arguments.as_mut())?;
+
             let stream = self
                 .worker
-                .execute(sql, arguments, self.row_channel_size, persistent)
+                .execute(&sql, arguments, self.row_channel_size, persistent, timeout)
                 .map_ok(flume::Receiver::into_stream)
                 .try_flatten_stream();
 
@@ -72,7 +82,8 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         'c: 'e,
     {
         Box::pin(async move {
-            let statement = self.worker.prepare(sql).await?;
+            let rewritten = rewrite_query(self.query_rewriter.as_ref(), sql, None)?;
+            let statement = self.worker.prepare(&rewritten).await?;
 
             Ok(SqliteStatement {
                 sql: sql.into(),
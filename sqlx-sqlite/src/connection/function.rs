@@ -0,0 +1,420 @@
+use std::any::Any;
+use std::ffi::CString;
+use std::fmt::{self, Debug, Formatter};
+use std::mem::size_of;
+use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr::null_mut;
+use std::sync::Arc;
+
+use libsqlite3_sys::{
+    sqlite3, sqlite3_aggregate_context, sqlite3_context, sqlite3_create_function_v2,
+    sqlite3_create_window_function, sqlite3_result_blob64, sqlite3_result_double,
+    sqlite3_result_error, sqlite3_result_error_nomem, sqlite3_result_int, sqlite3_result_int64,
+    sqlite3_result_null, sqlite3_result_text64, sqlite3_user_data, sqlite3_value,
+    SQLITE_DETERMINISTIC, SQLITE_OK, SQLITE_TRANSIENT, SQLITE_UTF8,
+};
+
+use crate::connection::handle::ConnectionHandle;
+use crate::encode::{Encode, IsNull};
+use crate::error::{BoxDynError, Error};
+use crate::type_info::DataType;
+use crate::{Sqlite, SqliteArgumentValue, SqliteError, SqliteTypeInfo, SqliteValue};
+
+/// A user-defined aggregate function, see
+/// [`SqliteConnectOptions::aggregate()`](crate::SqliteConnectOptions::aggregate).
+///
+/// SQLite creates a new state with [`init`](Self::init) for every group of rows, calls
+/// [`step`](Self::step) for each row in the group and [`finalize`](Self::finalize) to compute
+/// the result of the group.
+///
+/// ```rust
+/// use sqlx::error::BoxDynError;
+/// use sqlx::sqlite::{SqliteAggregate, SqliteValue};
+/// use sqlx::Value;
+///
+/// /// The product of the values in a group.
+/// struct Product;
+///
+/// impl SqliteAggregate for Product {
+///     type State = i64;
+///     type Output = i64;
+///
+///     fn init(&self) -> i64 {
+///         1
+///     }
+///
+///     fn step(&self, product: &mut i64, args: &[SqliteValue]) -> Result<(), BoxDynError> {
+///         *product *= args[0].try_decode::<i64>()?;
+///         Ok(())
+///     }
+///
+///     fn finalize(&self, product: i64) -> Result<i64, BoxDynError> {
+///         Ok(product)
+///     }
+/// }
+/// ```
+pub trait SqliteAggregate: Send + Sync + 'static {
+    /// The state of the aggregate for a group of rows.
+    type State;
+
+    /// The result of the aggregate, encoded like a bind parameter.
+    type Output: Encode<'static, Sqlite>;
+
+    /// Creates the state for a new group of rows.
+    fn init(&self) -> Self::State;
+
+    /// Adds the arguments of a row to the state of its group.
+    fn step(&self, state: &mut Self::State, args: &[SqliteValue]) -> Result<(), BoxDynError>;
+
+    /// Computes the result of a group from its state.
+    fn finalize(&self, state: Self::State) -> Result<Self::Output, BoxDynError>;
+}
+
+/// A user-defined aggregate window function, see
+/// [`SqliteConnectOptions::window_function()`](crate::SqliteConnectOptions::window_function).
+///
+/// In addition to the callbacks of an aggregate, a window function can remove rows which
+/// leave the frame of the window from its state with [`inverse`](Self::inverse) and compute
+/// the current result without consuming the state with [`value`](Self::value).
+///
+/// Requires SQLite 3.25.0 or later.
+pub trait SqliteWindowFunction: SqliteAggregate {
+    /// Removes the arguments of a row from the state of its window.
+    fn inverse(&self, state: &mut Self::State, args: &[SqliteValue]) -> Result<(), BoxDynError>;
+
+    /// Computes the current result of a window from its state.
+    fn value(&self, state: &Self::State) -> Result<Self::Output, BoxDynError>;
+}
+
+type ScalarFn =
+    dyn Fn(&[SqliteValue]) -> Result<SqliteArgumentValue<'static>, BoxDynError> + Send + Sync;
+
+// registers the function with a name, number of arguments and flags
+type RegisterFn = dyn Fn(*mut sqlite3, *const c_char, c_int, c_int) -> c_int + Send + Sync;
+
+/// A scalar, aggregate or window SQL function implemented in Rust.
+#[derive(Clone)]
+pub struct Function {
+    name: Arc<str>,
+    n_args: i32,
+    deterministic: bool,
+    register: Arc<RegisterFn>,
+}
+
+impl Function {
+    pub fn new<N, F, R>(name: N, n_args: i32, deterministic: bool, func: F) -> Self
+    where
+        N: Into<Arc<str>>,
+        F: Fn(&[SqliteValue]) -> Result<R, BoxDynError> + Send + Sync + 'static,
+        R: Encode<'static, Sqlite>,
+    {
+        let func: Arc<ScalarFn> =
+            Arc::new(move |args: &[SqliteValue]| func(args).map(encode_result));
+
+        Function {
+            name: name.into(),
+            n_args,
+            deterministic,
+            register: Arc::new(move |db, name, n_args, flags| unsafe {
+                let raw_f = Box::into_raw(Box::new(Arc::clone(&func)));
+                let r = sqlite3_create_function_v2(
+                    db,
+                    name,
+                    n_args,
+                    flags,
+                    raw_f as *mut c_void,
+                    Some(call_scalar),
+                    None,
+                    None,
+                    Some(free_boxed_value::<Arc<ScalarFn>>),
+                );
+
+                if r != SQLITE_OK {
+                    // The xDestroy callback is not called if the sqlite3_create_function_v2() function fails.
+                    drop(Box::from_raw(raw_f));
+                }
+
+                r
+            }),
+        }
+    }
+
+    pub fn aggregate<N, A>(name: N, n_args: i32, deterministic: bool, aggregate: A) -> Self
+    where
+        N: Into<Arc<str>>,
+        A: SqliteAggregate,
+    {
+        let aggregate = Arc::new(aggregate);
+
+        Function {
+            name: name.into(),
+            n_args,
+            deterministic,
+            register: Arc::new(move |db, name, n_args, flags| unsafe {
+                let raw_a = Box::into_raw(Box::new(Arc::clone(&aggregate)));
+                let r = sqlite3_create_function_v2(
+                    db,
+                    name,
+                    n_args,
+                    flags,
+                    raw_a as *mut c_void,
+                    None,
+                    Some(call_step::<A>),
+                    Some(call_final::<A>),
+                    Some(free_boxed_value::<Arc<A>>),
+                );
+
+                if r != SQLITE_OK {
+                    // The xDestroy callback is not called if the sqlite3_create_function_v2() function fails.
+                    drop(Box::from_raw(raw_a));
+                }
+
+                r
+            }),
+        }
+    }
+
+    pub fn window<N, A>(name: N, n_args: i32, deterministic: bool, window: A) -> Self
+    where
+        N: Into<Arc<str>>,
+        A: SqliteWindowFunction,
+    {
+        let window = Arc::new(window);
+
+        Function {
+            name: name.into(),
+            n_args,
+            deterministic,
+            register: Arc::new(move |db, name, n_args, flags| unsafe {
+                let raw_w = Box::into_raw(Box::new(Arc::clone(&window)));
+                let r = sqlite3_create_window_function(
+                    db,
+                    name,
+                    n_args,
+                    flags,
+                    raw_w as *mut c_void,
+                    Some(call_step::<A>),
+                    Some(call_final::<A>),
+                    Some(call_value::<A>),
+                    Some(call_inverse::<A>),
+                    Some(free_boxed_value::<Arc<A>>),
+                );
+
+                if r != SQLITE_OK {
+                    // The xDestroy callback is not called if the sqlite3_create_window_function() function fails.
+                    drop(Box::from_raw(raw_w));
+                }
+
+                r
+            }),
+        }
+    }
+
+    pub(crate) fn create(&self, handle: &mut ConnectionHandle) -> Result<(), Error> {
+        let c_name = CString::new(&*self.name)
+            .map_err(|_| err_protocol!("invalid function name: {:?}", self.name))?;
+
+        let mut flags = SQLITE_UTF8;
+
+        if self.deterministic {
+            flags |= SQLITE_DETERMINISTIC;
+        }
+
+        let r = (self.register)(handle.as_ptr(), c_name.as_ptr(), self.n_args, flags);
+
+        if r == SQLITE_OK {
+            Ok(())
+        } else {
+            Err(Error::Database(Box::new(SqliteError::new(handle.as_ptr()))))
+        }
+    }
+}
+
+impl Debug for Function {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Function")
+            .field("name", &self.name)
+            .field("n_args", &self.n_args)
+            .field("deterministic", &self.deterministic)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe extern "C" fn free_boxed_value<T>(p: *mut c_void) {
+    drop(Box::from_raw(p as *mut T));
+}
+
+unsafe extern "C" fn call_scalar(
+    ctx: *mut sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    let func = &*(sqlite3_user_data(ctx) as *const Arc<ScalarFn>);
+    let args = function_args(argc, argv);
+
+    set_result(ctx, catch_unwind(AssertUnwindSafe(|| func(&args))));
+}
+
+unsafe extern "C" fn call_step<A: SqliteAggregate>(
+    ctx: *mut sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    update_state(ctx, argc, argv, A::step);
+}
+
+unsafe extern "C" fn call_inverse<A: SqliteWindowFunction>(
+    ctx: *mut sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) {
+    update_state(ctx, argc, argv, A::inverse);
+}
+
+unsafe fn update_state<A: SqliteAggregate>(
+    ctx: *mut sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut sqlite3_value,
+    update: fn(&A, &mut A::State, &[SqliteValue]) -> Result<(), BoxDynError>,
+) {
+    let aggregate = &*(sqlite3_user_data(ctx) as *const Arc<A>);
+
+    // SQLite allocates a zeroed pointer for the state of each group on the first step
+    let state =
+        sqlite3_aggregate_context(ctx, size_of::<*mut A::State>() as c_int) as *mut *mut A::State;
+
+    if state.is_null() {
+        sqlite3_result_error_nomem(ctx);
+        return;
+    }
+
+    let args = function_args(argc, argv);
+
+    check_result(
+        ctx,
+        catch_unwind(AssertUnwindSafe(|| {
+            if (*state).is_null() {
+                *state = Box::into_raw(Box::new(aggregate.init()));
+            }
+
+            update(aggregate, &mut **state, &args)
+        })),
+    );
+}
+
+unsafe extern "C" fn call_value<A: SqliteWindowFunction>(ctx: *mut sqlite3_context) {
+    let window = &*(sqlite3_user_data(ctx) as *const Arc<A>);
+    let state = sqlite3_aggregate_context(ctx, 0) as *mut *mut A::State;
+
+    set_result(
+        ctx,
+        catch_unwind(AssertUnwindSafe(|| {
+            let result = if state.is_null() || (*state).is_null() {
+                // the window is empty
+                window.value(&window.init())
+            } else {
+                window.value(&**state)
+            };
+
+            result.map(encode_result)
+        })),
+    );
+}
+
+unsafe extern "C" fn call_final<A: SqliteAggregate>(ctx: *mut sqlite3_context) {
+    let aggregate = &*(sqlite3_user_data(ctx) as *const Arc<A>);
+
+    // the state is only allocated if there was at least one step
+    let state = take_state::<A>(ctx);
+
+    set_result(
+        ctx,
+        catch_unwind(AssertUnwindSafe(|| {
+            let state = state.map_or_else(|| aggregate.init(), |state| *state);
+
+            aggregate.finalize(state).map(encode_result)
+        })),
+    );
+}
+
+unsafe fn take_state<A: SqliteAggregate>(ctx: *mut sqlite3_context) -> Option<Box<A::State>> {
+    let state = sqlite3_aggregate_context(ctx, 0) as *mut *mut A::State;
+
+    if state.is_null() || (*state).is_null() {
+        return None;
+    }
+
+    Some(Box::from_raw(std::mem::replace(&mut *state, null_mut())))
+}
+
+/// Copies the arguments of a function call into owned values.
+unsafe fn function_args(argc: c_int, argv: *mut *mut sqlite3_value) -> Vec<SqliteValue> {
+    (0..argc as usize)
+        .map(|i| SqliteValue::new(*argv.add(i), SqliteTypeInfo(DataType::Null)))
+        .collect()
+}
+
+pub(crate) fn encode_result<R: Encode<'static, Sqlite>>(value: R) -> SqliteArgumentValue<'static> {
+    let mut buf = Vec::with_capacity(1);
+
+    match value.encode(&mut buf) {
+        IsNull::Yes => SqliteArgumentValue::Null,
+        IsNull::No => buf.pop().unwrap_or(SqliteArgumentValue::Null),
+    }
+}
+
+/// Sets the result of a function call, or an error if the function failed or panicked.
+pub(crate) unsafe fn set_result(
+    ctx: *mut sqlite3_context,
+    result: Result<Result<SqliteArgumentValue<'_>, BoxDynError>, Box<dyn Any + Send>>,
+) {
+    let value = match result {
+        Ok(Ok(value)) => value,
+        Ok(Err(error)) => return set_error(ctx, &error.to_string()),
+        Err(_) => return set_error(ctx, "panic in user-defined function"),
+    };
+
+    match value {
+        SqliteArgumentValue::Null => sqlite3_result_null(ctx),
+        SqliteArgumentValue::Int(v) => sqlite3_result_int(ctx, v),
+        SqliteArgumentValue::Int64(v) => sqlite3_result_int64(ctx, v),
+        SqliteArgumentValue::Double(v) => sqlite3_result_double(ctx, v),
+
+        SqliteArgumentValue::Text(v) => sqlite3_result_text64(
+            ctx,
+            v.as_ptr() as *const c_char,
+            v.len() as u64,
+            SQLITE_TRANSIENT(),
+            SQLITE_UTF8 as u8,
+        ),
+
+        SqliteArgumentValue::Blob(v) => sqlite3_result_blob64(
+            ctx,
+            v.as_ptr() as *const c_void,
+            v.len() as u64,
+            SQLITE_TRANSIENT(),
+        ),
+    }
+}
+
+/// Sets an error if a step of an aggregate failed or panicked.
+unsafe fn check_result(
+    ctx: *mut sqlite3_context,
+    result: Result<Result<(), BoxDynError>, Box<dyn Any + Send>>,
+) {
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => set_error(ctx, &error.to_string()),
+        Err(_) => set_error(ctx, "panic in user-defined function"),
+    }
+}
+
+pub(crate) unsafe fn set_error(ctx: *mut sqlite3_context, message: &str) {
+    // SQLite copies the message
+    sqlite3_result_error(
+        ctx,
+        message.as_ptr() as *const c_char,
+        message.len() as c_int,
+    );
+}
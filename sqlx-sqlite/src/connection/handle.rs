@@ -1,10 +1,13 @@
-use std::ffi::CString;
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::c_int;
 use std::ptr;
 use std::ptr::NonNull;
 
 use crate::error::Error;
 use libsqlite3_sys::{
-    sqlite3, sqlite3_close, sqlite3_exec, sqlite3_last_insert_rowid, SQLITE_LOCKED_SHAREDCACHE,
+    sqlite3, sqlite3_close, sqlite3_db_config, sqlite3_enable_load_extension, sqlite3_exec,
+    sqlite3_free, sqlite3_get_autocommit, sqlite3_last_insert_rowid, sqlite3_load_extension,
+    sqlite3_total_changes, SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION, SQLITE_LOCKED_SHAREDCACHE,
     SQLITE_OK,
 };
 
@@ -33,6 +36,13 @@ unsafe impl Send for ConnectionHandle {}
 // SAFETY: this type does nothing but provide access to the DB handle pointer.
 unsafe impl Send for ConnectionHandleRaw {}
 
+impl ConnectionHandleRaw {
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *mut sqlite3 {
+        self.0.as_ptr()
+    }
+}
+
 impl ConnectionHandle {
     #[inline]
     pub(super) unsafe fn new(ptr: *mut sqlite3) -> Self {
@@ -58,6 +68,18 @@ impl ConnectionHandle {
         unsafe { sqlite3_last_insert_rowid(self.as_ptr()) }
     }
 
+    /// Returns the number of rows changed by all statements since the connection was opened.
+    pub(crate) fn total_changes(&mut self) -> u64 {
+        // SAFETY: we have exclusive access to the database handle
+        unsafe { sqlite3_total_changes(self.as_ptr()) as u64 }
+    }
+
+    /// Returns `true` if a transaction is active, i.e. the connection is not in autocommit mode.
+    pub(crate) fn in_transaction(&mut self) -> bool {
+        // SAFETY: we have exclusive access to the database handle
+        unsafe { sqlite3_get_autocommit(self.as_ptr()) == 0 }
+    }
+
     pub(crate) fn exec(&mut self, query: impl Into<String>) -> Result<(), Error> {
         let query = query.into();
         let query = CString::new(query).map_err(|_| err_protocol!("query contains nul bytes"))?;
@@ -84,6 +106,102 @@ impl ConnectionHandle {
             }
         }
     }
+
+    // Enable or disable extension loading via the db_config function, as recommended by the docs
+    // rather than the more obvious `sqlite3_enable_load_extension`. This only affects the C-API,
+    // the `load_extension()` SQL function stays disabled.
+    // Returns whether extension loading was enabled before.
+    // https://www.sqlite.org/c3ref/db_config.html
+    // https://www.sqlite.org/c3ref/c_dbconfig_defensive.html#sqlitedbconfigenableloadextension
+    pub(crate) fn set_load_extension(&mut self, enable: bool) -> Result<bool, Error> {
+        let mut previous: c_int = 0;
+
+        // SAFETY: we have exclusive access to the database handle
+        let status = unsafe {
+            sqlite3_db_config(
+                self.as_ptr(),
+                SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION,
+                -1,
+                &mut previous as *mut c_int,
+            )
+        };
+
+        if status != SQLITE_OK {
+            return Err(SqliteError::new(self.as_ptr()).into());
+        }
+
+        // SAFETY: we have exclusive access to the database handle
+        let status = unsafe {
+            sqlite3_db_config(
+                self.as_ptr(),
+                SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION,
+                c_int::from(enable),
+                ptr::null_mut::<c_int>(),
+            )
+        };
+
+        if status != SQLITE_OK {
+            return Err(SqliteError::new(self.as_ptr()).into());
+        }
+
+        Ok(previous != 0)
+    }
+
+    // Enable both the C-API and the `load_extension()` SQL function
+    // https://www.sqlite.org/c3ref/enable_load_extension.html
+    pub(crate) fn enable_load_extension_sql(&mut self) -> Result<(), Error> {
+        // SAFETY: we have exclusive access to the database handle
+        let status = unsafe { sqlite3_enable_load_extension(self.as_ptr(), 1) };
+
+        if status != SQLITE_OK {
+            return Err(SqliteError::new(self.as_ptr()).into());
+        }
+
+        Ok(())
+    }
+
+    // Extension loading must be enabled with `set_load_extension()` first
+    pub(crate) fn load_extension(
+        &mut self,
+        name: &CStr,
+        entry_point: Option<&CStr>,
+    ) -> Result<(), Error> {
+        // `sqlite3_load_extension` is unusual as it returns its errors via an out-pointer
+        // rather than by calling `sqlite3_errmsg`
+        let mut error = ptr::null_mut();
+
+        // SAFETY: we have exclusive access to the database handle
+        let status = unsafe {
+            sqlite3_load_extension(
+                self.as_ptr(),
+                name.as_ptr(),
+                entry_point.map_or(ptr::null(), |e| e.as_ptr()),
+                &mut error,
+            )
+        };
+
+        if status == SQLITE_OK {
+            return Ok(());
+        }
+
+        // SAFETY: We become responsible for any memory allocation at `&error`, so test
+        // for null and take an RAII version for returns
+        let err_msg = if !error.is_null() {
+            unsafe {
+                let e = CStr::from_ptr(error).into();
+                sqlite3_free(error as *mut c_void);
+                e
+            }
+        } else {
+            CString::new("Unknown error when loading extension")
+                .expect("text should be representable as a CString")
+        };
+
+        Err(Error::Database(Box::new(SqliteError::extension(
+            self.as_ptr(),
+            &err_msg,
+        ))))
+    }
 }
 
 impl Drop for ConnectionHandle {
@@ -0,0 +1,251 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::panic::catch_unwind;
+use std::ptr::{null_mut, NonNull};
+
+use libsqlite3_sys::{
+    sqlite3_commit_hook, sqlite3_rollback_hook, sqlite3_update_hook, SQLITE_DELETE, SQLITE_INSERT,
+    SQLITE_UPDATE,
+};
+
+use crate::connection::{ConnectionState, LockedSqliteHandle};
+
+/// The kind of change made to a row, see [`SqliteUpdate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteOperation {
+    Insert,
+    Update,
+    Delete,
+    Unknown(i32),
+}
+
+impl From<c_int> for SqliteOperation {
+    fn from(code: c_int) -> Self {
+        match code {
+            SQLITE_INSERT => SqliteOperation::Insert,
+            SQLITE_UPDATE => SqliteOperation::Update,
+            SQLITE_DELETE => SqliteOperation::Delete,
+            code => SqliteOperation::Unknown(code),
+        }
+    }
+}
+
+/// A row inserted, updated or deleted in a rowid table, reported by
+/// [`SqliteConnection::updates()`](crate::SqliteConnection::updates) or an update hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteUpdate {
+    /// How the row was changed.
+    pub operation: SqliteOperation,
+
+    /// The name of the database containing the table, e.g. `main`.
+    pub database: String,
+
+    /// The name of the table.
+    pub table: String,
+
+    /// The rowid of the changed row.
+    pub rowid: i64,
+}
+
+/// Represents an update hook that will be shared with the underlying sqlite3 connection.
+pub(crate) struct UpdateHookHandler(NonNull<dyn FnMut(SqliteUpdate) + Send + 'static>);
+unsafe impl Send for UpdateHookHandler {}
+
+/// Represents a commit hook that will be shared with the underlying sqlite3 connection.
+pub(crate) struct CommitHookHandler(NonNull<dyn FnMut() -> bool + Send + 'static>);
+unsafe impl Send for CommitHookHandler {}
+
+/// Represents a rollback hook that will be shared with the underlying sqlite3 connection.
+pub(crate) struct RollbackHookHandler(NonNull<dyn FnMut() + Send + 'static>);
+unsafe impl Send for RollbackHookHandler {}
+
+impl ConnectionState {
+    /// Drops the `commit_hook_callback` if it exists.
+    pub(crate) fn remove_commit_hook(&mut self) {
+        if let Some(mut handler) = self.commit_hook_callback.take() {
+            unsafe {
+                sqlite3_commit_hook(self.handle.as_ptr(), None, null_mut());
+                let _ = { Box::from_raw(handler.0.as_mut()) };
+            }
+        }
+    }
+
+    /// Drops the `rollback_hook_callback` if it exists.
+    pub(crate) fn remove_rollback_hook(&mut self) {
+        if let Some(mut handler) = self.rollback_hook_callback.take() {
+            unsafe {
+                sqlite3_rollback_hook(self.handle.as_ptr(), None, null_mut());
+                let _ = { Box::from_raw(handler.0.as_mut()) };
+            }
+        }
+    }
+
+    /// Drops the `update_hook_callback` if it exists.
+    pub(crate) fn remove_update_hook(&mut self) {
+        if let Some(mut handler) = self.update_hook_callback.take() {
+            unsafe {
+                sqlite3_update_hook(self.handle.as_ptr(), None, null_mut());
+                let _ = { Box::from_raw(handler.0.as_mut()) };
+            }
+        }
+    }
+}
+
+impl LockedSqliteHandle<'_> {
+    /// Sets a callback which is invoked whenever a row is inserted, updated or deleted in a
+    /// rowid table of the database, e.g. to invalidate caches.
+    ///
+    /// The callback is invoked on the thread executing the statement while it is running, before
+    /// the change is committed, and is not invoked for changes to `WITHOUT ROWID` tables or
+    /// rows deleted by `ON CONFLICT REPLACE` or truncating a table.
+    ///
+    /// Only a single update hook may be defined at one time per database connection; setting a
+    /// new hook removes the old one. The callback must not use the database connection.
+    ///
+    /// See [`sqlite3_update_hook()`](https://www.sqlite.org/c3ref/update_hook.html) for details.
+    pub fn set_update_hook<F>(&mut self, callback: F)
+    where
+        F: FnMut(SqliteUpdate) + Send + 'static,
+    {
+        unsafe {
+            let callback_boxed = Box::new(callback);
+            // SAFETY: `Box::into_raw()` always returns a non-null pointer.
+            let callback = NonNull::new_unchecked(Box::into_raw(callback_boxed));
+            let handler = callback.as_ptr() as *mut _;
+            self.guard.remove_update_hook();
+            self.guard.update_hook_callback = Some(UpdateHookHandler(callback));
+
+            sqlite3_update_hook(
+                self.as_raw_handle().as_mut(),
+                Some(update_hook::<F>),
+                handler,
+            );
+        }
+    }
+
+    /// Removes the update hook on a database connection. The method does nothing if no hook was set.
+    pub fn remove_update_hook(&mut self) {
+        self.guard.remove_update_hook();
+    }
+
+    /// Sets a callback which is invoked whenever a transaction is about to be committed,
+    /// including the implicit transactions of statements executed outside of a transaction.
+    ///
+    /// If the callback returns `false`, the transaction is rolled back instead and the
+    /// statement committing it fails with `SQLITE_CONSTRAINT_COMMITHOOK`. This can be used to
+    /// enforce invariants or to coordinate the commit with an external system.
+    ///
+    /// Only a single commit hook may be defined at one time per database connection; setting a
+    /// new hook removes the old one. The callback must not use the database connection.
+    ///
+    /// See [`sqlite3_commit_hook()`](https://www.sqlite.org/c3ref/commit_hook.html) for details.
+    pub fn set_commit_hook<F>(&mut self, callback: F)
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        unsafe {
+            let callback_boxed = Box::new(callback);
+            // SAFETY: `Box::into_raw()` always returns a non-null pointer.
+            let callback = NonNull::new_unchecked(Box::into_raw(callback_boxed));
+            let handler = callback.as_ptr() as *mut _;
+            self.guard.remove_commit_hook();
+            self.guard.commit_hook_callback = Some(CommitHookHandler(callback));
+
+            sqlite3_commit_hook(
+                self.as_raw_handle().as_mut(),
+                Some(commit_hook::<F>),
+                handler,
+            );
+        }
+    }
+
+    /// Removes the commit hook on a database connection. The method does nothing if no hook was set.
+    pub fn remove_commit_hook(&mut self) {
+        self.guard.remove_commit_hook();
+    }
+
+    /// Sets a callback which is invoked whenever a transaction is rolled back, including
+    /// transactions rolled back by a [commit hook](Self::set_commit_hook) but not the automatic
+    /// rollback when the connection is closed.
+    ///
+    /// Only a single rollback hook may be defined at one time per database connection; setting a
+    /// new hook removes the old one. The callback must not use the database connection.
+    ///
+    /// See [`sqlite3_rollback_hook()`](https://www.sqlite.org/c3ref/commit_hook.html) for details.
+    pub fn set_rollback_hook<F>(&mut self, callback: F)
+    where
+        F: FnMut() + Send + 'static,
+    {
+        unsafe {
+            let callback_boxed = Box::new(callback);
+            // SAFETY: `Box::into_raw()` always returns a non-null pointer.
+            let callback = NonNull::new_unchecked(Box::into_raw(callback_boxed));
+            let handler = callback.as_ptr() as *mut _;
+            self.guard.remove_rollback_hook();
+            self.guard.rollback_hook_callback = Some(RollbackHookHandler(callback));
+
+            sqlite3_rollback_hook(
+                self.as_raw_handle().as_mut(),
+                Some(rollback_hook::<F>),
+                handler,
+            );
+        }
+    }
+
+    /// Removes the rollback hook on a database connection. The method does nothing if no hook was set.
+    pub fn remove_rollback_hook(&mut self) {
+        self.guard.remove_rollback_hook();
+    }
+}
+
+/// Implements a C binding to an update hook.
+extern "C" fn update_hook<F>(
+    callback: *mut c_void,
+    operation: c_int,
+    database: *const c_char,
+    table: *const c_char,
+    rowid: i64,
+) where
+    F: FnMut(SqliteUpdate),
+{
+    unsafe {
+        let _ = catch_unwind(|| {
+            let callback: *mut F = callback.cast::<F>();
+
+            (*callback)(SqliteUpdate {
+                operation: operation.into(),
+                database: CStr::from_ptr(database).to_string_lossy().into_owned(),
+                table: CStr::from_ptr(table).to_string_lossy().into_owned(),
+                rowid,
+            });
+        });
+    }
+}
+
+/// Implements a C binding to a commit hook. The function returns `0` if the user-provided
+/// callback returns `true`, and `1` otherwise to turn the commit into a rollback.
+extern "C" fn commit_hook<F>(callback: *mut c_void) -> c_int
+where
+    F: FnMut() -> bool,
+{
+    unsafe {
+        let r = catch_unwind(|| {
+            let callback: *mut F = callback.cast::<F>();
+            (*callback)()
+        });
+        c_int::from(!r.unwrap_or_default())
+    }
+}
+
+/// Implements a C binding to a rollback hook.
+extern "C" fn rollback_hook<F>(callback: *mut c_void)
+where
+    F: FnMut(),
+{
+    unsafe {
+        let _ = catch_unwind(|| {
+            let callback: *mut F = callback.cast::<F>();
+            (*callback)()
+        });
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use libsqlite3_sys::sqlite3_interrupt;
+
+use crate::connection::worker::WorkerSharedState;
+use crate::connection::ConnectionHandleRaw;
+
+/// A handle to interrupt the statements of a connection from another task or thread, see
+/// [`SqliteConnection::interrupt_handle()`](crate::SqliteConnection::interrupt_handle).
+///
+/// Interrupted statements fail with `SQLITE_INTERRUPT` (result code `9`). The connection
+/// remains usable, but SQLite rolls back the active transaction if an interrupted statement
+/// was modifying the database.
+#[derive(Clone)]
+pub struct SqliteInterruptHandle {
+    handle: ConnectionHandleRaw,
+    // keeps the database handle open as long as the interrupt handle exists
+    _shared: Arc<WorkerSharedState>,
+}
+
+// SAFETY: `sqlite3_interrupt()` may be called from any thread while the database handle is open
+unsafe impl Send for SqliteInterruptHandle {}
+unsafe impl Sync for SqliteInterruptHandle {}
+
+impl SqliteInterruptHandle {
+    pub(crate) fn new(handle: ConnectionHandleRaw, shared: Arc<WorkerSharedState>) -> Self {
+        Self {
+            handle,
+            _shared: shared,
+        }
+    }
+
+    /// Interrupts the statements currently running on the connection, including statements
+    /// which start before they all completed. The method does nothing if no statement is
+    /// running.
+    ///
+    /// See [`sqlite3_interrupt()`](https://www.sqlite.org/c3ref/interrupt.html) for details.
+    pub fn interrupt(&self) {
+        unsafe { sqlite3_interrupt(self.handle.as_ptr()) }
+    }
+}
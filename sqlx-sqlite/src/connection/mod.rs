@@ -1,36 +1,72 @@
 use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
 use futures_intrusive::sync::MutexGuard;
-use futures_util::future;
-use libsqlite3_sys::{sqlite3, sqlite3_progress_handler};
+use futures_util::{future, TryStreamExt};
+use libsqlite3_sys::sqlite3;
 use sqlx_core::common::StatementCache;
 use sqlx_core::error::Error;
 use sqlx_core::transaction::Transaction;
 use std::cmp::Ordering;
+use std::ffi::CString;
 use std::fmt::{self, Debug, Formatter};
-use std::os::raw::{c_int, c_void};
-use std::panic::catch_unwind;
+use std::path::Path;
 use std::ptr::NonNull;
+use std::sync::Arc;
+use std::time::Duration;
 
+use crate::connection::authorizer::AuthorizerHandler;
+use crate::connection::busy::BusyHandler;
+use crate::connection::collation::Collation;
 use crate::connection::establish::EstablishParams;
+use crate::connection::function::Function;
+use crate::connection::hooks::{CommitHookHandler, RollbackHookHandler, UpdateHookHandler};
+use crate::connection::progress::Progress;
+use crate::connection::vtab::Module;
 use crate::connection::worker::ConnectionWorker;
-use crate::options::OptimizeOnClose;
+use crate::error::BoxDynError;
+use crate::options::{quote_literal, OptimizeOnClose};
 use crate::statement::VirtualStatement;
-use crate::{Sqlite, SqliteConnectOptions};
-use sqlx_core::executor::Executor;
+use crate::{Sqlite, SqliteConnectOptions, SqliteRow, SqliteValue};
+use sqlx_core::encode::Encode;
+use sqlx_core::row::Row;
 use std::fmt::Write;
 
 pub(crate) use sqlx_core::connection::*;
 
 pub(crate) use handle::{ConnectionHandle, ConnectionHandleRaw};
 
+pub use authorizer::{SqliteAuthorization, SqliteAuthorizerAction, SqliteAuthorizerRequest};
+pub use backup::SqliteBackupProgress;
+pub use blob::SqliteBlob;
+pub use checkpoint::{SqliteCheckpoint, SqliteCheckpointMode};
+pub use function::{SqliteAggregate, SqliteWindowFunction};
+pub use hooks::{SqliteOperation, SqliteUpdate};
+pub use interrupt::SqliteInterruptHandle;
+#[cfg(feature = "session")]
+pub use session::{SqliteConflict, SqliteConflictAction, SqliteConflictKind};
+pub use vtab::{SqliteVirtualCursor, SqliteVirtualTable, SqliteVirtualValue};
+
+mod authorizer;
+mod backup;
+mod blob;
+mod busy;
+mod checkpoint;
 pub(crate) mod collation;
 pub(crate) mod describe;
 pub(crate) mod establish;
 pub(crate) mod execute;
 mod executor;
 mod explain;
+pub(crate) mod function;
 mod handle;
+mod hooks;
+mod interrupt;
 mod intmap;
+mod progress;
+mod serialize;
+#[cfg(feature = "session")]
+mod session;
+pub(crate) mod vtab;
 
 mod worker;
 
@@ -44,20 +80,32 @@ mod worker;
 ///
 /// You can explicitly call [`.close()`][Self::close] to ensure the database is closed successfully
 /// or get an error otherwise.
+///
+/// ### Multiple statements
+/// A query may consist of several statements separated by `;`, e.g. a schema script. The
+/// statements are prepared and executed one after another, and the arguments of the query are
+/// bound to their parameters in order.
+///
+/// [`Executor::execute()`] returns the sum of the results of all statements, while
+/// [`Executor::execute_many()`] and [`Executor::fetch_many()`] return a
+/// [`SqliteQueryResult`](crate::SqliteQueryResult) after each statement, following its rows.
+/// Execution stops at the first statement which fails; the statements before it are not rolled
+/// back unless the query runs in a transaction.
+///
+/// [`Executor::execute()`]: sqlx_core::executor::Executor::execute
+/// [`Executor::execute_many()`]: sqlx_core::executor::Executor::execute_many
+/// [`Executor::fetch_many()`]: sqlx_core::executor::Executor::fetch_many
 pub struct SqliteConnection {
     optimize_on_close: OptimizeOnClose,
     pub(crate) worker: ConnectionWorker,
     pub(crate) row_channel_size: usize,
+    pub(crate) query_rewriter: Option<Arc<dyn QueryRewriter<Sqlite>>>,
 }
 
 pub struct LockedSqliteHandle<'a> {
     pub(crate) guard: MutexGuard<'a, ConnectionState>,
 }
 
-/// Represents a callback handler that will be shared with the underlying sqlite3 connection.
-pub(crate) struct Handler(NonNull<dyn FnMut() -> bool + Send + 'static>);
-unsafe impl Send for Handler {}
-
 pub(crate) struct ConnectionState {
     pub(crate) handle: ConnectionHandle,
 
@@ -68,21 +116,33 @@ pub(crate) struct ConnectionState {
 
     log_settings: LogSettings,
 
-    /// Stores the progress handler set on the current connection. If the handler returns `false`,
+    /// Stores the progress handlers set on the current connection. If a handler returns `false`,
     /// the query is interrupted.
-    progress_handler_callback: Option<Handler>,
-}
+    progress: Box<Progress>,
 
-impl ConnectionState {
-    /// Drops the `progress_handler_callback` if it exists.
-    pub(crate) fn remove_progress_handler(&mut self) {
-        if let Some(mut handler) = self.progress_handler_callback.take() {
-            unsafe {
-                sqlite3_progress_handler(self.handle.as_ptr(), 0, None, 0 as *mut _);
-                let _ = { Box::from_raw(handler.0.as_mut()) };
-            }
-        }
-    }
+    /// Interrupts statements which run longer than this.
+    pub(crate) statement_timeout: Option<Duration>,
+
+    /// Stores the update hook set on the current connection.
+    update_hook_callback: Option<UpdateHookHandler>,
+
+    /// Stores the commit hook set on the current connection. If the hook returns `false`,
+    /// the commit is turned into a rollback.
+    commit_hook_callback: Option<CommitHookHandler>,
+
+    /// Stores the rollback hook set on the current connection.
+    rollback_hook_callback: Option<RollbackHookHandler>,
+
+    /// Stores the busy handler set on the current connection. If the handler returns `false`,
+    /// the statement fails with `SQLITE_BUSY`.
+    busy_handler_callback: Option<BusyHandler>,
+
+    /// Stores the authorizer set on the current connection.
+    authorizer_callback: Option<AuthorizerHandler>,
+
+    /// Stores the session capturing changes on the current connection.
+    #[cfg(feature = "session")]
+    session: Option<session::Session>,
 }
 
 pub(crate) struct Statements {
@@ -90,6 +150,8 @@ pub(crate) struct Statements {
     cached: StatementCache<VirtualStatement>,
     // most recent non-persistent statement
     temp: Option<VirtualStatement>,
+    // whether cached statements are prepared with `SQLITE_PREPARE_PERSISTENT`
+    persistent: bool,
 }
 
 impl SqliteConnection {
@@ -97,12 +159,30 @@ impl SqliteConnection {
         let params = EstablishParams::from_options(options)?;
         let worker = ConnectionWorker::establish(params).await?;
         Ok(Self {
-            optimize_on_close: options.optimize_on_close.clone(),
+            // read-only connections cannot store the statistics gathered by `PRAGMA optimize`
+            optimize_on_close: if options.read_only || options.immutable {
+                OptimizeOnClose::Disabled
+            } else {
+                options.optimize_on_close.clone()
+            },
             worker,
             row_channel_size: options.row_channel_size,
+            query_rewriter: options.query_rewriter.clone(),
         })
     }
 
+    /// Execute statements issued by the driver itself and return their rows, without passing
+    /// them through the [`QueryRewriter`] of the connection.
+    pub(crate) async fn fetch_all_internal(&mut self, sql: &str) -> Result<Vec<SqliteRow>, Error> {
+        self.worker
+            .execute(sql, None, self.row_channel_size, false, None)
+            .await?
+            .into_stream()
+            .try_filter_map(|step| future::ok(step.right()))
+            .try_collect()
+            .await
+    }
+
     /// Lock the SQLite database handle out from the worker thread so direct SQLite API calls can
     /// be made safely.
     ///
@@ -112,6 +192,380 @@ impl SqliteConnection {
 
         Ok(LockedSqliteHandle { guard })
     }
+
+    /// Returns a handle to interrupt the statements running on this connection from another
+    /// task or thread, e.g. to cancel a query from a timer:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::SqliteConnection) -> sqlx::Result<()> {
+    /// use sqlx::Executor;
+    /// use std::time::Duration;
+    ///
+    /// let interrupt = conn.interrupt_handle();
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(Duration::from_secs(1));
+    ///     interrupt.interrupt();
+    /// });
+    ///
+    /// // fails with `SQLITE_INTERRUPT` if it runs longer than a second
+    /// let result = conn.execute("DELETE FROM logs").await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// See [`SqliteInterruptHandle`] for details.
+    pub fn interrupt_handle(&self) -> SqliteInterruptHandle {
+        SqliteInterruptHandle::new(
+            self.worker.handle_raw.clone(),
+            Arc::clone(&self.worker.shared),
+        )
+    }
+
+    /// Sets the [statement timeout](SqliteConnectOptions::statement_timeout) of this
+    /// connection, or removes it if `None`.
+    ///
+    /// Statements which run longer than the timeout are interrupted and fail with
+    /// [`Error::Timeout`]; the connection remains usable. Setting the timeout before and
+    /// resetting it after a query limits the time of that query only.
+    pub async fn set_statement_timeout(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+        self.lock_handle().await?.guard.statement_timeout = timeout;
+        Ok(())
+    }
+
+    /// Sets the capacity of the statement cache of this connection, finalizing the least
+    /// recently used statements if it holds more, or disables the cache if `0`.
+    ///
+    /// See [`SqliteConnectOptions::statement_cache_capacity()`] for details.
+    pub async fn set_statement_cache_capacity(&mut self, capacity: usize) -> Result<(), Error> {
+        self.lock_handle()
+            .await?
+            .guard
+            .statements
+            .set_capacity(capacity);
+        Ok(())
+    }
+
+    /// Removes the statement for `query` from the statement cache of this connection and
+    /// finalizes it, returning `true` if it was cached.
+    ///
+    /// SQLite prepares cached statements again when the schema changes, so this is only
+    /// needed to release their resources early. See
+    /// [`Connection::clear_cached_statements()`] to remove all statements.
+    pub async fn invalidate_statement(&mut self, query: &str) -> Result<bool, Error> {
+        Ok(self.lock_handle().await?.guard.statements.remove(query))
+    }
+
+    /// Opens the BLOB in `column` of the row `rowid` of `table` for incremental reading and
+    /// writing, to stream large values without loading them into memory at once.
+    ///
+    /// The connection is locked until the returned handle is dropped. Fails if the value is not
+    /// a BLOB or text, or if the column is indexed or part of a foreign key.
+    ///
+    /// See [`SqliteBlob`] and [`sqlite3_blob_open()`](https://www.sqlite.org/c3ref/blob_open.html)
+    /// for details.
+    pub async fn blob_open(
+        &mut self,
+        table: &str,
+        column: &str,
+        rowid: i64,
+    ) -> Result<SqliteBlob<'_>, Error> {
+        SqliteBlob::open(self.lock_handle().await?, table, column, rowid, false)
+    }
+
+    /// Opens the BLOB in `column` of the row `rowid` of `table` for incremental reading only,
+    /// which also works on read-only databases.
+    ///
+    /// See [`Self::blob_open()`] for details.
+    pub async fn blob_open_read_only(
+        &mut self,
+        table: &str,
+        column: &str,
+        rowid: i64,
+    ) -> Result<SqliteBlob<'_>, Error> {
+        SqliteBlob::open(self.lock_handle().await?, table, column, rowid, true)
+    }
+
+    /// Starts capturing the changes to `tables`, or to all tables if `tables` is `None`.
+    ///
+    /// See [`LockedSqliteHandle::start_session()`] for details.
+    #[cfg(feature = "session")]
+    pub async fn start_session(&mut self, tables: Option<&[&str]>) -> Result<(), Error> {
+        self.lock_handle().await?.start_session(tables)
+    }
+
+    /// Returns the changes captured since the session was started as a changeset.
+    ///
+    /// See [`LockedSqliteHandle::changeset()`] for details.
+    #[cfg(feature = "session")]
+    pub async fn changeset(&mut self) -> Result<Vec<u8>, Error> {
+        self.lock_handle().await?.changeset()
+    }
+
+    /// Ends the session and discards the captured changes.
+    #[cfg(feature = "session")]
+    pub async fn end_session(&mut self) -> Result<(), Error> {
+        self.lock_handle().await?.end_session();
+        Ok(())
+    }
+
+    /// Applies a changeset captured by a session, e.g. on another database, to the main
+    /// database in a single transaction.
+    ///
+    /// `conflict` is called for every change which cannot be applied as is, e.g. because the
+    /// row to update was changed or deleted in the meantime, and decides whether to skip the
+    /// change, apply it anyway, or to abort and roll back all changes.
+    ///
+    /// See [`sqlite3changeset_apply()`](https://www.sqlite.org/session/sqlite3changeset_apply.html)
+    /// for details.
+    #[cfg(feature = "session")]
+    pub async fn apply_changeset<F>(&mut self, changeset: &[u8], conflict: F) -> Result<(), Error>
+    where
+        F: FnMut(&SqliteConflict) -> SqliteConflictAction,
+    {
+        self.lock_handle()
+            .await?
+            .apply_changeset(changeset, conflict)
+    }
+
+    /// Copies the main database into the main database of `dest` while it is in use, with the
+    /// [online backup API](https://www.sqlite.org/backup.html).
+    ///
+    /// The database is copied `pages_per_step` pages at a time, or all at once if it is
+    /// negative. After every step `progress` is called with the number of pages remaining.
+    /// Both connections are locked for the duration of the backup, other connections
+    /// can use the database between steps. The existing content of `dest` is replaced.
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use sqlx::sqlite::SqliteConnection;
+    /// use sqlx::Connection;
+    ///
+    /// let mut conn = SqliteConnection::connect("sqlite://data.db").await?;
+    /// let mut backup = SqliteConnection::connect("sqlite::memory:").await?;
+    ///
+    /// conn.backup_to(&mut backup, 100, |progress| {
+    ///     println!("{} of {} pages remaining", progress.remaining, progress.page_count);
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn backup_to(
+        &mut self,
+        dest: &mut SqliteConnection,
+        pages_per_step: i32,
+        progress: impl FnMut(SqliteBackupProgress),
+    ) -> Result<(), Error> {
+        let mut source = self.lock_handle().await?;
+        let mut dest = dest.lock_handle().await?;
+
+        backup::backup(
+            &mut source.guard.handle,
+            &mut dest.guard.handle,
+            pages_per_step,
+            progress,
+        )
+        .await
+    }
+
+    /// Copies the main database into the database file at `path` while it is in use,
+    /// creating the file if it does not exist.
+    ///
+    /// See [`backup_to()`](Self::backup_to) for details.
+    pub async fn backup_to_path(
+        &mut self,
+        path: impl AsRef<Path>,
+        pages_per_step: i32,
+        progress: impl FnMut(SqliteBackupProgress),
+    ) -> Result<(), Error> {
+        let mut dest = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .connect()
+            .await?;
+
+        self.backup_to(&mut dest, pages_per_step, progress).await?;
+
+        dest.close().await
+    }
+
+    /// Returns a stream of the rows inserted, updated or deleted in rowid tables by this
+    /// connection, e.g. to invalidate caches or update views.
+    ///
+    /// Changes are reported as they are made, including changes which are rolled back later.
+    /// The stream is unbounded, so it should be consumed promptly. Calling this again or setting
+    /// another update hook with [`LockedSqliteHandle::set_update_hook()`] ends the stream.
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use futures::StreamExt;
+    /// use sqlx::sqlite::SqliteConnection;
+    /// use sqlx::Connection;
+    ///
+    /// let mut conn = SqliteConnection::connect("sqlite://data.db").await?;
+    /// let mut updates = conn.updates().await?;
+    ///
+    /// sqlx::query("DELETE FROM sessions").execute(&mut conn).await?;
+    ///
+    /// while let Some(update) = updates.next().await {
+    ///     println!("{:?} {} in {}", update.operation, update.rowid, update.table);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn updates(&mut self) -> Result<BoxStream<'static, SqliteUpdate>, Error> {
+        let (tx, rx) = flume::unbounded();
+
+        self.lock_handle().await?.set_update_hook(move |update| {
+            // the stream was dropped
+            let _ = tx.send(update);
+        });
+
+        Ok(Box::pin(rx.into_stream()))
+    }
+
+    /// Runs a [checkpoint](https://www.sqlite.org/wal.html#checkpointing) of the write-ahead log
+    /// of all attached databases, copying its frames back into the database files.
+    ///
+    /// SQLite checkpoints automatically when the log exceeds a number of pages, see
+    /// [`SqliteConnectOptions::wal_autocheckpoint()`]. Long-running services with constant
+    /// readers may need to checkpoint explicitly, e.g. with
+    /// [`SqliteCheckpointMode::Truncate`] to limit the size of the log file.
+    ///
+    /// See [`PRAGMA wal_checkpoint`](https://www.sqlite.org/pragma.html#pragma_wal_checkpoint)
+    /// for details.
+    pub async fn wal_checkpoint(
+        &mut self,
+        mode: SqliteCheckpointMode,
+    ) -> Result<SqliteCheckpoint, Error> {
+        let rows = self
+            .fetch_all_internal(&format!("PRAGMA wal_checkpoint({})", mode.as_str()))
+            .await?;
+        let row = rows.first().ok_or(Error::RowNotFound)?;
+
+        Ok(SqliteCheckpoint {
+            busy: row.try_get(0)?,
+            log_frames: row.try_get(1)?,
+            checkpointed_frames: row.try_get(2)?,
+        })
+    }
+
+    /// Copies the main database into a byte vector in the format of a database file.
+    ///
+    /// This is useful to snapshot an in-memory database, which can be restored with
+    /// [`deserialize()`](Self::deserialize) later.
+    ///
+    /// See [`sqlite3_serialize()`](https://www.sqlite.org/c3ref/serialize.html) for details.
+    pub async fn serialize(&mut self) -> Result<Vec<u8>, Error> {
+        serialize::serialize(&mut self.lock_handle().await?.guard.handle)
+    }
+
+    /// Replaces the main database with a copy of `data` in the format of a database file,
+    /// e.g. from [`serialize()`](Self::serialize).
+    ///
+    /// The main database becomes an in-memory database of this connection, the file it was
+    /// opened from is not changed. If `read_only` is set, the database cannot be written to.
+    ///
+    /// See [`sqlite3_deserialize()`](https://www.sqlite.org/c3ref/deserialize.html) for details.
+    pub async fn deserialize(&mut self, data: &[u8], read_only: bool) -> Result<(), Error> {
+        serialize::deserialize(&mut self.lock_handle().await?.guard.handle, data, read_only)
+    }
+
+    /// Changes the key of an encrypted database (`PRAGMA rekey`), see
+    /// [`SqliteConnectOptions::pragma_key()`].
+    ///
+    /// Requires SQLCipher or the SQLite Encryption Extension. Connections opened afterwards
+    /// must use the new key, so update the options of a pool before rekeying through it.
+    pub async fn rekey(&mut self, key: &str) -> Result<(), Error> {
+        self.fetch_all_internal(&format!("PRAGMA rekey = {};", quote_literal(key)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Load an [extension](https://www.sqlite.org/loadext.html) into the open database, using
+    /// the default entry point if `entry_point` is `None`.
+    ///
+    /// Extensions which should be loaded by every connection of a pool should be added with
+    /// [`SqliteConnectOptions::extension()`] instead.
+    pub async fn load_extension(
+        &mut self,
+        name: &str,
+        entry_point: Option<&str>,
+    ) -> Result<(), Error> {
+        self.lock_handle().await?.load_extension(name, entry_point)
+    }
+
+    /// Register a collation for comparing strings in SQL.
+    ///
+    /// See [`SqliteConnectOptions::collation()`] for details.
+    pub async fn create_collation(
+        &mut self,
+        name: &str,
+        compare: impl Fn(&str, &str) -> Ordering + Send + Sync + 'static,
+    ) -> Result<(), Error> {
+        self.lock_handle().await?.create_collation(name, compare)
+    }
+
+    /// Register a scalar SQL function implemented by `func`.
+    ///
+    /// See [`SqliteConnectOptions::function()`] for details.
+    pub async fn create_function<F, R>(
+        &mut self,
+        name: &str,
+        n_args: i32,
+        deterministic: bool,
+        func: F,
+    ) -> Result<(), Error>
+    where
+        F: Fn(&[SqliteValue]) -> Result<R, BoxDynError> + Send + Sync + 'static,
+        R: Encode<'static, Sqlite>,
+    {
+        self.lock_handle()
+            .await?
+            .create_function(name, n_args, deterministic, func)
+    }
+
+    /// Register an aggregate SQL function.
+    ///
+    /// See [`SqliteConnectOptions::aggregate()`] for details.
+    pub async fn create_aggregate(
+        &mut self,
+        name: &str,
+        n_args: i32,
+        deterministic: bool,
+        aggregate: impl SqliteAggregate,
+    ) -> Result<(), Error> {
+        self.lock_handle()
+            .await?
+            .create_aggregate(name, n_args, deterministic, aggregate)
+    }
+
+    /// Register an aggregate window function.
+    ///
+    /// See [`SqliteConnectOptions::window_function()`] for details.
+    pub async fn create_window_function(
+        &mut self,
+        name: &str,
+        n_args: i32,
+        deterministic: bool,
+        window: impl SqliteWindowFunction,
+    ) -> Result<(), Error> {
+        self.lock_handle()
+            .await?
+            .create_window_function(name, n_args, deterministic, window)
+    }
+
+    /// Register a virtual table module.
+    ///
+    /// See [`SqliteConnectOptions::module()`] for details.
+    pub async fn create_module(
+        &mut self,
+        name: &str,
+        table: impl SqliteVirtualTable,
+    ) -> Result<(), Error> {
+        self.lock_handle().await?.create_module(name, table)
+    }
 }
 
 impl Debug for SqliteConnection {
@@ -136,7 +590,7 @@ impl Connection for SqliteConnection {
                     write!(pragma_string, "PRAGMA analysis_limit = {}; ", limit).ok();
                 }
                 pragma_string.push_str("PRAGMA optimize;");
-                self.execute(&*pragma_string).await?;
+                self.fetch_all_internal(&pragma_string).await?;
             }
             let shutdown = self.worker.shutdown();
             // Drop the statement worker, which should
@@ -200,21 +654,6 @@ impl Connection for SqliteConnection {
     }
 }
 
-/// Implements a C binding to a progress callback. The function returns `0` if the
-/// user-provided callback returns `true`, and `1` otherwise to signal an interrupt.
-extern "C" fn progress_callback<F>(callback: *mut c_void) -> c_int
-where
-    F: FnMut() -> bool,
-{
-    unsafe {
-        let r = catch_unwind(|| {
-            let callback: *mut F = callback.cast::<F>();
-            (*callback)()
-        });
-        c_int::from(!r.unwrap_or_default())
-    }
-}
-
 impl LockedSqliteHandle<'_> {
     /// Returns the underlying sqlite3* connection handle.
     ///
@@ -234,6 +673,28 @@ impl LockedSqliteHandle<'_> {
         self.guard.handle.as_non_null_ptr()
     }
 
+    /// Load an extension into the open database.
+    ///
+    /// See [`SqliteConnection::load_extension()`] for details.
+    pub fn load_extension(&mut self, name: &str, entry_point: Option<&str>) -> Result<(), Error> {
+        let name =
+            CString::new(name).map_err(|_| err_protocol!("invalid extension name: {:?}", name))?;
+        let entry_point = entry_point
+            .map(|e| {
+                CString::new(e).map_err(|_| err_protocol!("invalid extension entry point: {:?}", e))
+            })
+            .transpose()?;
+
+        let handle = &mut self.guard.handle;
+        let enabled = handle.set_load_extension(true)?;
+        let result = handle.load_extension(&name, entry_point.as_deref());
+
+        // leave extension loading as it was, e.g. enabled by `enable_load_extension`
+        handle.set_load_extension(enabled)?;
+
+        result
+    }
+
     /// Apply a collation to the open database.
     ///
     /// See [`SqliteConnectOptions::collation()`] for details.
@@ -242,7 +703,61 @@ impl LockedSqliteHandle<'_> {
         name: &str,
         compare: impl Fn(&str, &str) -> Ordering + Send + Sync + 'static,
     ) -> Result<(), Error> {
-        collation::create_collation(&mut self.guard.handle, name, compare)
+        Collation::new(name, compare).create(&mut self.guard.handle)
+    }
+
+    /// Register a scalar SQL function on the open database.
+    ///
+    /// See [`SqliteConnectOptions::function()`] for details.
+    pub fn create_function<F, R>(
+        &mut self,
+        name: &str,
+        n_args: i32,
+        deterministic: bool,
+        func: F,
+    ) -> Result<(), Error>
+    where
+        F: Fn(&[SqliteValue]) -> Result<R, BoxDynError> + Send + Sync + 'static,
+        R: Encode<'static, Sqlite>,
+    {
+        Function::new(name, n_args, deterministic, func).create(&mut self.guard.handle)
+    }
+
+    /// Register an aggregate SQL function on the open database.
+    ///
+    /// See [`SqliteConnectOptions::aggregate()`] for details.
+    pub fn create_aggregate(
+        &mut self,
+        name: &str,
+        n_args: i32,
+        deterministic: bool,
+        aggregate: impl SqliteAggregate,
+    ) -> Result<(), Error> {
+        Function::aggregate(name, n_args, deterministic, aggregate).create(&mut self.guard.handle)
+    }
+
+    /// Register an aggregate window function on the open database.
+    ///
+    /// See [`SqliteConnectOptions::window_function()`] for details.
+    pub fn create_window_function(
+        &mut self,
+        name: &str,
+        n_args: i32,
+        deterministic: bool,
+        window: impl SqliteWindowFunction,
+    ) -> Result<(), Error> {
+        Function::window(name, n_args, deterministic, window).create(&mut self.guard.handle)
+    }
+
+    /// Register a virtual table module on the open database.
+    ///
+    /// See [`SqliteConnectOptions::module()`] for details.
+    pub fn create_module(
+        &mut self,
+        name: &str,
+        table: impl SqliteVirtualTable,
+    ) -> Result<(), Error> {
+        Module::new(name, table).create(&mut self.guard.handle)
     }
 
     /// Sets a progress handler that is invoked periodically during long running calls. If the progress callback
@@ -258,25 +773,14 @@ impl LockedSqliteHandle<'_> {
     /// The progress handler callback must not do anything that will modify the database connection that invoked
     /// the progress handler. Note that sqlite3_prepare_v2() and sqlite3_step() both modify their database connections
     /// in this context.
-    pub fn set_progress_handler<F>(&mut self, num_ops: i32, mut callback: F)
+    ///
+    /// Cancelled statements and statements exceeding the
+    /// [statement timeout](crate::SqliteConnectOptions::statement_timeout) are interrupted independently of this handler.
+    pub fn set_progress_handler<F>(&mut self, num_ops: i32, callback: F)
     where
         F: FnMut() -> bool + Send + 'static,
     {
-        unsafe {
-            let callback_boxed = Box::new(callback);
-            // SAFETY: `Box::into_raw()` always returns a non-null pointer.
-            let callback = NonNull::new_unchecked(Box::into_raw(callback_boxed));
-            let handler = callback.as_ptr() as *mut _;
-            self.guard.remove_progress_handler();
-            self.guard.progress_handler_callback = Some(Handler(callback));
-
-            sqlite3_progress_handler(
-                self.as_raw_handle().as_mut(),
-                num_ops,
-                Some(progress_callback::<F>),
-                handler,
-            );
-        }
+        self.guard.set_progress_handler(num_ops, callback);
     }
 
     /// Removes the progress handler on a database connection. The method does nothing if no handler was set.
@@ -290,14 +794,23 @@ impl Drop for ConnectionState {
         // explicitly drop statements before the connection handle is dropped
         self.statements.clear();
         self.remove_progress_handler();
+        self.unwatch_progress();
+        self.remove_update_hook();
+        self.remove_commit_hook();
+        self.remove_rollback_hook();
+        self.remove_busy_handler();
+        self.remove_authorizer();
+        #[cfg(feature = "session")]
+        self.end_session();
     }
 }
 
 impl Statements {
-    fn new(capacity: usize) -> Self {
+    fn new(capacity: usize, persistent: bool) -> Self {
         Statements {
             cached: StatementCache::new(capacity),
             temp: None,
+            persistent,
         }
     }
 
@@ -309,7 +822,7 @@ impl Statements {
         let exists = self.cached.contains_key(query);
 
         if !exists {
-            let statement = VirtualStatement::new(query, true)?;
+            let statement = VirtualStatement::new(query, self.persistent)?;
             self.cached.insert(query, statement);
         }
 
@@ -327,6 +840,14 @@ impl Statements {
         self.cached.len()
     }
 
+    fn set_capacity(&mut self, capacity: usize) {
+        self.cached.set_capacity(capacity);
+    }
+
+    fn remove(&mut self, query: &str) -> bool {
+        self.cached.remove(query).is_some()
+    }
+
     fn clear(&mut self) {
         self.cached.clear();
         self.temp = None;
@@ -0,0 +1,131 @@
+use std::cmp;
+use std::os::raw::{c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr::null_mut;
+
+use libsqlite3_sys::sqlite3_progress_handler;
+
+use crate::connection::ConnectionState;
+
+/// The approximate number of virtual machine instructions between checks whether a running
+/// statement was cancelled.
+const WATCH_OPS: c_int = 1000;
+
+type ProgressFn = dyn FnMut() -> bool + Send + 'static;
+
+/// The progress handlers of a connection, which share the single progress handler of SQLite.
+///
+/// Boxed by `ConnectionState`, so that its address remains stable while it is registered.
+#[derive(Default)]
+pub(crate) struct Progress {
+    /// The progress handler set by the user, invoked every `handler_ops` instructions.
+    handler: Option<Box<ProgressFn>>,
+    handler_ops: c_int,
+
+    /// Checked while the worker executes a statement, to interrupt statements which are
+    /// cancelled or time out.
+    watch: Option<Box<ProgressFn>>,
+
+    /// The number of instructions between invocations of the SQLite progress handler.
+    interval: c_int,
+
+    /// The number of instructions since the user handler was last invoked.
+    elapsed: c_int,
+}
+
+impl Progress {
+    /// Returns `false` to interrupt the running statement.
+    fn tick(&mut self) -> bool {
+        if let Some(watch) = &mut self.watch {
+            if !watch() {
+                return false;
+            }
+        }
+
+        if let Some(handler) = &mut self.handler {
+            self.elapsed = self.elapsed.saturating_add(self.interval);
+
+            if self.elapsed >= self.handler_ops {
+                self.elapsed = 0;
+                return handler();
+            }
+        }
+
+        true
+    }
+}
+
+impl ConnectionState {
+    pub(crate) fn set_progress_handler<F>(&mut self, num_ops: i32, callback: F)
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        if num_ops < 1 {
+            self.remove_progress_handler();
+            return;
+        }
+
+        self.progress.handler = Some(Box::new(callback));
+        self.progress.handler_ops = num_ops;
+        self.install_progress();
+    }
+
+    /// Drops the user-provided progress handler if it exists.
+    pub(crate) fn remove_progress_handler(&mut self) {
+        if self.progress.handler.take().is_some() {
+            self.install_progress();
+        }
+    }
+
+    /// Interrupts statements executed until [`Self::unwatch_progress()`] when `watch` returns
+    /// `false`.
+    pub(crate) fn watch_progress<F>(&mut self, watch: F)
+    where
+        F: FnMut() -> bool + Send + 'static,
+    {
+        self.progress.watch = Some(Box::new(watch));
+        self.install_progress();
+    }
+
+    pub(crate) fn unwatch_progress(&mut self) {
+        if self.progress.watch.take().is_some() {
+            self.install_progress();
+        }
+    }
+
+    /// Registers the progress handler with SQLite, or removes it if no handler is left.
+    fn install_progress(&mut self) {
+        let progress = &mut *self.progress;
+
+        progress.interval = match (&progress.handler, &progress.watch) {
+            (None, None) => 0,
+            (Some(_), None) => progress.handler_ops,
+            (None, Some(_)) => WATCH_OPS,
+            (Some(_), Some(_)) => cmp::min(progress.handler_ops, WATCH_OPS),
+        };
+        progress.elapsed = 0;
+
+        unsafe {
+            if progress.interval == 0 {
+                sqlite3_progress_handler(self.handle.as_ptr(), 0, None, null_mut());
+            } else {
+                sqlite3_progress_handler(
+                    self.handle.as_ptr(),
+                    progress.interval,
+                    Some(progress_callback),
+                    progress as *mut Progress as *mut c_void,
+                );
+            }
+        }
+    }
+}
+
+/// Implements a C binding to the progress handlers of a connection. The function returns `0`
+/// if the handlers return `true`, and `1` otherwise to signal an interrupt.
+extern "C" fn progress_callback(progress: *mut c_void) -> c_int {
+    unsafe {
+        let progress = &mut *(progress as *mut Progress);
+        let r = catch_unwind(AssertUnwindSafe(|| progress.tick()));
+        c_int::from(!r.unwrap_or_default())
+    }
+}
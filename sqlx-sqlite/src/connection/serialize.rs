@@ -0,0 +1,92 @@
+use std::cmp;
+use std::os::raw::c_uint;
+use std::ptr;
+use std::slice;
+
+use libsqlite3_sys::{
+    sqlite3_deserialize, sqlite3_free, sqlite3_int64, sqlite3_malloc64, sqlite3_serialize,
+    SQLITE_DESERIALIZE_FREEONCLOSE, SQLITE_DESERIALIZE_READONLY, SQLITE_DESERIALIZE_RESIZEABLE,
+    SQLITE_OK,
+};
+
+use crate::connection::handle::ConnectionHandle;
+use crate::error::Error;
+use crate::SqliteError;
+
+// name of the main database of a connection
+static MAIN: &[u8] = b"main\0";
+
+/// Copies the main database into a byte vector in the format of a database file.
+///
+/// <https://www.sqlite.org/c3ref/serialize.html>
+pub(crate) fn serialize(handle: &mut ConnectionHandle) -> Result<Vec<u8>, Error> {
+    // SQLite writes the size of the database unless it fails
+    let mut size: sqlite3_int64 = -1;
+
+    // SAFETY: we have exclusive access to the database handle
+    let data = unsafe { sqlite3_serialize(handle.as_ptr(), MAIN.as_ptr().cast(), &mut size, 0) };
+
+    if data.is_null() {
+        // an empty database has no pages
+        if size == 0 {
+            return Ok(Vec::new());
+        }
+
+        return Err(SqliteError::new(handle.as_ptr()).into());
+    }
+
+    // SAFETY: SQLite returns a buffer of `size` bytes which we must free
+    let bytes = unsafe { slice::from_raw_parts(data, size as usize).to_vec() };
+
+    unsafe { sqlite3_free(data.cast()) };
+
+    Ok(bytes)
+}
+
+/// Replaces the main database with a copy of `data` in the format of a database file,
+/// turning it into an in-memory database.
+///
+/// <https://www.sqlite.org/c3ref/deserialize.html>
+pub(crate) fn deserialize(
+    handle: &mut ConnectionHandle,
+    data: &[u8],
+    read_only: bool,
+) -> Result<(), Error> {
+    // SQLite takes ownership of the buffer, so it must be allocated by SQLite
+    let buf = unsafe { sqlite3_malloc64(cmp::max(data.len(), 1) as u64) } as *mut u8;
+
+    if buf.is_null() {
+        return Err(err_protocol!(
+            "SQLite is unable to allocate {} bytes to deserialize the database",
+            data.len()
+        ));
+    }
+
+    // SAFETY: the buffer was allocated with at least `data.len()` bytes
+    unsafe { ptr::copy_nonoverlapping(data.as_ptr(), buf, data.len()) };
+
+    let flags = if read_only {
+        SQLITE_DESERIALIZE_FREEONCLOSE | SQLITE_DESERIALIZE_READONLY
+    } else {
+        SQLITE_DESERIALIZE_FREEONCLOSE | SQLITE_DESERIALIZE_RESIZEABLE
+    };
+
+    // SAFETY: we have exclusive access to the database handle, SQLite frees the buffer
+    // even if it fails
+    let status = unsafe {
+        sqlite3_deserialize(
+            handle.as_ptr(),
+            MAIN.as_ptr().cast(),
+            buf,
+            data.len() as sqlite3_int64,
+            data.len() as sqlite3_int64,
+            flags as c_uint,
+        )
+    };
+
+    if status != SQLITE_OK {
+        return Err(SqliteError::new(handle.as_ptr()).into());
+    }
+
+    Ok(())
+}
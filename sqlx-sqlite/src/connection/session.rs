@@ -0,0 +1,275 @@
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr::{null, null_mut, NonNull};
+use std::slice;
+
+use libsqlite3_sys::{
+    sqlite3_changeset_iter, sqlite3_free, sqlite3_session, sqlite3changeset_apply,
+    sqlite3changeset_op, sqlite3session_attach, sqlite3session_changeset, sqlite3session_create,
+    sqlite3session_delete, SQLITE_CHANGESET_ABORT, SQLITE_CHANGESET_CONFLICT,
+    SQLITE_CHANGESET_CONSTRAINT, SQLITE_CHANGESET_DATA, SQLITE_CHANGESET_FOREIGN_KEY,
+    SQLITE_CHANGESET_NOTFOUND, SQLITE_CHANGESET_OMIT, SQLITE_CHANGESET_REPLACE, SQLITE_OK,
+};
+
+use crate::connection::{ConnectionState, LockedSqliteHandle, SqliteOperation};
+use crate::error::Error;
+use crate::SqliteError;
+
+// name of the main database of a connection
+static MAIN: &[u8] = b"main\0";
+
+/// The reason a change of a changeset cannot be applied, see [`SqliteConflict`].
+///
+/// See [Constants Passed To The Conflict Handler](https://www.sqlite.org/session/c_changeset_conflict.html)
+/// for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteConflictKind {
+    /// The row to update or delete exists, but its values differ from the changeset.
+    Data,
+    /// The row to update or delete does not exist.
+    NotFound,
+    /// The row to insert already exists.
+    Conflict,
+    /// The change violates a `NOT NULL`, `CHECK` or `UNIQUE` constraint.
+    Constraint,
+    /// The changeset violates a foreign key constraint after all changes were applied.
+    ForeignKey,
+    Unknown(i32),
+}
+
+impl From<c_int> for SqliteConflictKind {
+    fn from(code: c_int) -> Self {
+        match code {
+            SQLITE_CHANGESET_DATA => SqliteConflictKind::Data,
+            SQLITE_CHANGESET_NOTFOUND => SqliteConflictKind::NotFound,
+            SQLITE_CHANGESET_CONFLICT => SqliteConflictKind::Conflict,
+            SQLITE_CHANGESET_CONSTRAINT => SqliteConflictKind::Constraint,
+            SQLITE_CHANGESET_FOREIGN_KEY => SqliteConflictKind::ForeignKey,
+            code => SqliteConflictKind::Unknown(code),
+        }
+    }
+}
+
+/// A change which cannot be applied to the database, passed to the conflict handler of
+/// [`SqliteConnection::apply_changeset()`](crate::SqliteConnection::apply_changeset).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteConflict {
+    /// Why the change cannot be applied.
+    pub kind: SqliteConflictKind,
+
+    /// The name of the table, or an empty string for a [foreign key](SqliteConflictKind::ForeignKey)
+    /// conflict.
+    pub table: String,
+
+    /// The kind of change, or [`Unknown`](SqliteOperation::Unknown) for a
+    /// [foreign key](SqliteConflictKind::ForeignKey) conflict.
+    pub operation: SqliteOperation,
+}
+
+/// How to resolve a [`SqliteConflict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteConflictAction {
+    /// Skip the change.
+    Omit,
+
+    /// Apply the change anyway, replacing the conflicting row. Only valid for
+    /// [`Data`](SqliteConflictKind::Data) and [`Conflict`](SqliteConflictKind::Conflict)
+    /// conflicts.
+    Replace,
+
+    /// Roll back all changes applied so far and fail with `SQLITE_ABORT`.
+    Abort,
+}
+
+/// Captures the changes to the tables of the main database, see
+/// [`LockedSqliteHandle::start_session()`].
+pub(crate) struct Session(NonNull<sqlite3_session>);
+unsafe impl Send for Session {}
+
+impl ConnectionState {
+    /// Deletes the `session` if it exists.
+    pub(crate) fn end_session(&mut self) {
+        if let Some(session) = self.session.take() {
+            unsafe {
+                sqlite3session_delete(session.0.as_ptr());
+            }
+        }
+    }
+}
+
+impl LockedSqliteHandle<'_> {
+    /// Starts capturing the changes to `tables` of the main database, or to all tables with
+    /// a primary key if `tables` is `None`, so that they can be retrieved as a changeset with
+    /// [`changeset()`](Self::changeset).
+    ///
+    /// Only a single session may be active at one time per database connection; starting a
+    /// new session ends the old one and discards its changes.
+    ///
+    /// See [The Session Extension](https://www.sqlite.org/sessionintro.html) for details.
+    pub fn start_session(&mut self, tables: Option<&[&str]>) -> Result<(), Error> {
+        let tables = tables
+            .map(|tables| {
+                tables
+                    .iter()
+                    .map(|table| {
+                        CString::new(*table)
+                            .map_err(|_| err_protocol!("invalid table name: {:?}", table))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        self.guard.end_session();
+
+        let db = self.guard.handle.as_ptr();
+        let mut session = null_mut();
+
+        // https://www.sqlite.org/session/sqlite3session_create.html
+        let status = unsafe { sqlite3session_create(db, MAIN.as_ptr().cast(), &mut session) };
+
+        let session = match NonNull::new(session) {
+            Some(session) if status == SQLITE_OK => Session(session),
+            _ => return Err(SqliteError::new(db).into()),
+        };
+
+        let session_ptr = session.0.as_ptr();
+        self.guard.session = Some(session);
+
+        let status = match &tables {
+            // attaching `NULL` captures all tables
+            None => unsafe { sqlite3session_attach(session_ptr, null()) },
+            Some(tables) => tables
+                .iter()
+                .map(|table| unsafe { sqlite3session_attach(session_ptr, table.as_ptr()) })
+                .find(|status| *status != SQLITE_OK)
+                .unwrap_or(SQLITE_OK),
+        };
+
+        if status != SQLITE_OK {
+            self.guard.end_session();
+            return Err(SqliteError::from_code(status).into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the changes captured since the session was started as a changeset, which can
+    /// be applied to another database with
+    /// [`SqliteConnection::apply_changeset()`](crate::SqliteConnection::apply_changeset).
+    ///
+    /// The session continues to capture changes.
+    pub fn changeset(&mut self) -> Result<Vec<u8>, Error> {
+        let session = match &self.guard.session {
+            Some(session) => session.0.as_ptr(),
+            None => return Err(err_protocol!("no session was started on the connection")),
+        };
+
+        let mut size: c_int = 0;
+        let mut data: *mut c_void = null_mut();
+
+        // https://www.sqlite.org/session/sqlite3session_changeset.html
+        let status = unsafe { sqlite3session_changeset(session, &mut size, &mut data) };
+
+        if status != SQLITE_OK {
+            return Err(SqliteError::from_code(status).into());
+        }
+
+        if data.is_null() {
+            return Ok(Vec::new());
+        }
+
+        // SAFETY: SQLite returns a buffer of `size` bytes which we must free
+        let changeset = unsafe { slice::from_raw_parts(data as *const u8, size as usize).to_vec() };
+
+        unsafe { sqlite3_free(data) };
+
+        Ok(changeset)
+    }
+
+    /// Ends the session and discards the captured changes. The method does nothing if no
+    /// session was started.
+    pub fn end_session(&mut self) {
+        self.guard.end_session();
+    }
+
+    /// Applies a changeset to the main database, calling `conflict` for every change which
+    /// cannot be applied as is.
+    ///
+    /// See [`SqliteConnection::apply_changeset()`](crate::SqliteConnection::apply_changeset).
+    pub fn apply_changeset<F>(&mut self, changeset: &[u8], mut conflict: F) -> Result<(), Error>
+    where
+        F: FnMut(&SqliteConflict) -> SqliteConflictAction,
+    {
+        let db = self.guard.handle.as_ptr();
+
+        // https://www.sqlite.org/session/sqlite3changeset_apply.html
+        let status = unsafe {
+            sqlite3changeset_apply(
+                db,
+                changeset.len() as c_int,
+                // SQLite does not modify the changeset
+                changeset.as_ptr() as *mut c_void,
+                None,
+                Some(conflict_handler::<F>),
+                &mut conflict as *mut F as *mut c_void,
+            )
+        };
+
+        if status != SQLITE_OK {
+            return Err(SqliteError::new(db).into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Implements a C binding to a conflict handler. Panics abort applying the changeset.
+extern "C" fn conflict_handler<F>(
+    callback: *mut c_void,
+    kind: c_int,
+    iter: *mut sqlite3_changeset_iter,
+) -> c_int
+where
+    F: FnMut(&SqliteConflict) -> SqliteConflictAction,
+{
+    unsafe {
+        let mut table: *const c_char = null();
+        let mut columns: c_int = 0;
+        let mut operation: c_int = 0;
+        let mut indirect: c_int = 0;
+
+        // the change responsible for a foreign key conflict is unknown
+        if kind != SQLITE_CHANGESET_FOREIGN_KEY {
+            // https://www.sqlite.org/session/sqlite3changeset_op.html
+            sqlite3changeset_op(
+                iter,
+                &mut table,
+                &mut columns,
+                &mut operation,
+                &mut indirect,
+            );
+        }
+
+        let conflict = SqliteConflict {
+            kind: kind.into(),
+            table: if table.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(table).to_string_lossy().into_owned()
+            },
+            operation: operation.into(),
+        };
+
+        let r = catch_unwind(AssertUnwindSafe(|| {
+            let callback: *mut F = callback.cast::<F>();
+            (*callback)(&conflict)
+        }));
+
+        match r {
+            Ok(SqliteConflictAction::Omit) => SQLITE_CHANGESET_OMIT,
+            Ok(SqliteConflictAction::Replace) => SQLITE_CHANGESET_REPLACE,
+            Ok(SqliteConflictAction::Abort) | Err(_) => SQLITE_CHANGESET_ABORT,
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use std::ffi::CString;
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+
+use libsqlite3_sys::{
+    sqlite3, sqlite3_context, sqlite3_create_module_v2, sqlite3_declare_vtab, sqlite3_free,
+    sqlite3_index_info, sqlite3_int64, sqlite3_module, sqlite3_mprintf, sqlite3_value,
+    sqlite3_vtab, sqlite3_vtab_cursor, SQLITE_ERROR, SQLITE_OK,
+};
+
+use crate::connection::function::{encode_result, set_error, set_result};
+use crate::connection::handle::ConnectionHandle;
+use crate::encode::Encode;
+use crate::error::{BoxDynError, Error};
+use crate::{Sqlite, SqliteError};
+
+/// A read-only virtual table implemented in Rust, see
+/// [`SqliteConnectOptions::module()`](crate::SqliteConnectOptions::module).
+///
+/// Every scan of the table [opens](Self::open) a new cursor, which SQLite advances over all
+/// rows of the table; constraints of the query are applied by SQLite to the returned rows.
+///
+/// ```rust
+/// use sqlx::error::BoxDynError;
+/// use sqlx::sqlite::{SqliteVirtualCursor, SqliteVirtualTable, SqliteVirtualValue};
+///
+/// /// The numbers from 1 to 10 and their squares.
+/// struct Squares;
+///
+/// impl SqliteVirtualTable for Squares {
+///     type Cursor = SquaresCursor;
+///
+///     fn schema(&self) -> String {
+///         "CREATE TABLE x(n INTEGER, square INTEGER)".into()
+///     }
+///
+///     fn open(&self) -> Result<SquaresCursor, BoxDynError> {
+///         Ok(SquaresCursor(0))
+///     }
+/// }
+///
+/// struct SquaresCursor(i64);
+///
+/// impl SqliteVirtualCursor for SquaresCursor {
+///     fn next(&mut self) -> Result<bool, BoxDynError> {
+///         self.0 += 1;
+///         Ok(self.0 <= 10)
+///     }
+///
+///     fn column(&self, index: usize, value: &mut SqliteVirtualValue<'_>) -> Result<(), BoxDynError> {
+///         match index {
+///             0 => value.set(self.0),
+///             _ => value.set(self.0 * self.0),
+///         }
+///
+///         Ok(())
+///     }
+///
+///     fn rowid(&self) -> i64 {
+///         self.0
+///     }
+/// }
+/// ```
+pub trait SqliteVirtualTable: Send + Sync + 'static {
+    /// The cursor scanning the rows of the table.
+    type Cursor: SqliteVirtualCursor;
+
+    /// Returns the `CREATE TABLE` statement declaring the columns of the table. The name of
+    /// the table in the statement is ignored.
+    fn schema(&self) -> String;
+
+    /// Opens a cursor to scan the table from the start.
+    fn open(&self) -> Result<Self::Cursor, BoxDynError>;
+}
+
+/// A cursor scanning the rows of a [`SqliteVirtualTable`].
+pub trait SqliteVirtualCursor: Send + 'static {
+    /// Moves the cursor to the next row, or returns `false` if there are no more rows.
+    ///
+    /// The method is called once before reading the first row.
+    fn next(&mut self) -> Result<bool, BoxDynError>;
+
+    /// Sets `value` to the value of the column `index` in the current row.
+    ///
+    /// The value is left `NULL` if it is not set.
+    fn column(&self, index: usize, value: &mut SqliteVirtualValue<'_>) -> Result<(), BoxDynError>;
+
+    /// Returns the rowid of the current row.
+    fn rowid(&self) -> i64;
+}
+
+/// The value of a column read from a [`SqliteVirtualCursor`].
+pub struct SqliteVirtualValue<'a> {
+    ctx: &'a mut sqlite3_context,
+}
+
+impl SqliteVirtualValue<'_> {
+    /// Sets the value of the column, encoded like a bind parameter.
+    pub fn set<T: Encode<'static, Sqlite>>(&mut self, value: T) {
+        // SAFETY: the context is valid for the duration of the `xColumn` callback
+        unsafe { set_result(self.ctx, Ok(Ok(encode_result(value)))) }
+    }
+}
+
+// registers the module with a name
+type RegisterFn = dyn Fn(*mut sqlite3, *const c_char) -> c_int + Send + Sync;
+
+/// A virtual table module implemented in Rust.
+#[derive(Clone)]
+pub struct Module {
+    name: Arc<str>,
+    register: Arc<RegisterFn>,
+}
+
+impl Module {
+    pub fn new<N, T>(name: N, table: T) -> Self
+    where
+        N: Into<Arc<str>>,
+        T: SqliteVirtualTable,
+    {
+        let table = Arc::new(table);
+
+        Module {
+            name: name.into(),
+            register: Arc::new(move |db, name| unsafe {
+                let aux = Box::into_raw(Box::new(ModuleAux {
+                    module: module::<T>(),
+                    table: Arc::clone(&table),
+                }));
+
+                // SQLite calls the destructor even if it fails to create the module
+                sqlite3_create_module_v2(
+                    db,
+                    name,
+                    &(*aux).module,
+                    aux as *mut c_void,
+                    Some(free_aux::<T>),
+                )
+            }),
+        }
+    }
+
+    pub(crate) fn create(&self, handle: &mut ConnectionHandle) -> Result<(), Error> {
+        let c_name = CString::new(&*self.name)
+            .map_err(|_| err_protocol!("invalid module name: {:?}", self.name))?;
+
+        let r = (self.register)(handle.as_ptr(), c_name.as_ptr());
+
+        if r == SQLITE_OK {
+            Ok(())
+        } else {
+            Err(Error::Database(Box::new(SqliteError::new(handle.as_ptr()))))
+        }
+    }
+}
+
+impl Debug for Module {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Module")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The client data of a module, which keeps the module definition alive as long as SQLite uses it.
+struct ModuleAux<T> {
+    module: sqlite3_module,
+    table: Arc<T>,
+}
+
+#[repr(C)]
+struct VTab<T> {
+    // must be the first field, SQLite casts the pointer to `sqlite3_vtab`
+    base: sqlite3_vtab,
+    table: Arc<T>,
+}
+
+#[repr(C)]
+struct VTabCursor<T: SqliteVirtualTable> {
+    // must be the first field, SQLite casts the pointer to `sqlite3_vtab_cursor`
+    base: sqlite3_vtab_cursor,
+    table: Arc<T>,
+    cursor: Option<T::Cursor>,
+}
+
+fn module<T: SqliteVirtualTable>() -> sqlite3_module {
+    sqlite3_module {
+        iVersion: 1,
+        // the module can be used with `CREATE VIRTUAL TABLE` and as an eponymous virtual table
+        xCreate: Some(connect::<T>),
+        xConnect: Some(connect::<T>),
+        xBestIndex: Some(best_index),
+        xDisconnect: Some(disconnect::<T>),
+        xDestroy: Some(disconnect::<T>),
+        xOpen: Some(open::<T>),
+        xClose: Some(close::<T>),
+        xFilter: Some(filter::<T>),
+        xNext: Some(next::<T>),
+        xEof: Some(eof::<T>),
+        xColumn: Some(column::<T>),
+        xRowid: Some(rowid::<T>),
+        // SAFETY: the remaining callbacks are optional
+        ..unsafe { mem::zeroed() }
+    }
+}
+
+unsafe extern "C" fn free_aux<T>(p: *mut c_void) {
+    drop(Box::from_raw(p as *mut ModuleAux<T>));
+}
+
+unsafe extern "C" fn connect<T: SqliteVirtualTable>(
+    db: *mut sqlite3,
+    aux: *mut c_void,
+    _argc: c_int,
+    _argv: *const *const c_char,
+    vtab: *mut *mut sqlite3_vtab,
+    err: *mut *mut c_char,
+) -> c_int {
+    let table = Arc::clone(&(*(aux as *mut ModuleAux<T>)).table);
+
+    let schema = match catch_unwind(AssertUnwindSafe(|| table.schema())) {
+        Ok(schema) => schema,
+        Err(_) => {
+            *err = error_message("panic in virtual table");
+            return SQLITE_ERROR;
+        }
+    };
+
+    let schema = match CString::new(schema) {
+        Ok(schema) => schema,
+        Err(_) => {
+            *err = error_message("virtual table schema must not contain nul bytes");
+            return SQLITE_ERROR;
+        }
+    };
+
+    let r = sqlite3_declare_vtab(db, schema.as_ptr());
+
+    if r != SQLITE_OK {
+        return r;
+    }
+
+    *vtab = Box::into_raw(Box::new(VTab {
+        base: mem::zeroed(),
+        table,
+    })) as *mut sqlite3_vtab;
+
+    SQLITE_OK
+}
+
+unsafe extern "C" fn best_index(_vtab: *mut sqlite3_vtab, info: *mut sqlite3_index_info) -> c_int {
+    // every scan visits all rows, SQLite checks the constraints itself
+    (*info).estimatedCost = 1_000_000.0;
+
+    SQLITE_OK
+}
+
+unsafe extern "C" fn disconnect<T>(vtab: *mut sqlite3_vtab) -> c_int {
+    let vtab = Box::from_raw(vtab as *mut VTab<T>);
+
+    if !vtab.base.zErrMsg.is_null() {
+        sqlite3_free(vtab.base.zErrMsg as *mut c_void);
+    }
+
+    SQLITE_OK
+}
+
+unsafe extern "C" fn open<T: SqliteVirtualTable>(
+    vtab: *mut sqlite3_vtab,
+    cursor: *mut *mut sqlite3_vtab_cursor,
+) -> c_int {
+    let vtab = &*(vtab as *mut VTab<T>);
+
+    *cursor = Box::into_raw(Box::new(VTabCursor::<T> {
+        base: mem::zeroed(),
+        table: Arc::clone(&vtab.table),
+        cursor: None,
+    })) as *mut sqlite3_vtab_cursor;
+
+    SQLITE_OK
+}
+
+unsafe extern "C" fn close<T: SqliteVirtualTable>(cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    drop(Box::from_raw(cursor as *mut VTabCursor<T>));
+
+    SQLITE_OK
+}
+
+unsafe extern "C" fn filter<T: SqliteVirtualTable>(
+    cursor: *mut sqlite3_vtab_cursor,
+    _idx_num: c_int,
+    _idx_str: *const c_char,
+    _argc: c_int,
+    _argv: *mut *mut sqlite3_value,
+) -> c_int {
+    let vtab = (*cursor).pVtab;
+    let cursor = &mut *(cursor as *mut VTabCursor<T>);
+
+    // SQLite may scan the table several times with the same cursor, e.g. in a join
+    cursor.cursor = None;
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut scan = cursor.table.open()?;
+        let more = scan.next()?;
+        Ok::<_, BoxDynError>(more.then_some(scan))
+    }));
+
+    match result {
+        Ok(Ok(scan)) => {
+            cursor.cursor = scan;
+            SQLITE_OK
+        }
+        Ok(Err(error)) => set_vtab_error(vtab, &error.to_string()),
+        Err(_) => set_vtab_error(vtab, "panic in virtual table"),
+    }
+}
+
+unsafe extern "C" fn next<T: SqliteVirtualTable>(cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    let vtab = (*cursor).pVtab;
+    let cursor = &mut *(cursor as *mut VTabCursor<T>);
+
+    let Some(scan) = cursor.cursor.as_mut() else {
+        return SQLITE_OK;
+    };
+
+    match catch_unwind(AssertUnwindSafe(|| scan.next())) {
+        Ok(Ok(true)) => SQLITE_OK,
+        Ok(Ok(false)) => {
+            cursor.cursor = None;
+            SQLITE_OK
+        }
+        Ok(Err(error)) => set_vtab_error(vtab, &error.to_string()),
+        Err(_) => set_vtab_error(vtab, "panic in virtual table"),
+    }
+}
+
+unsafe extern "C" fn eof<T: SqliteVirtualTable>(cursor: *mut sqlite3_vtab_cursor) -> c_int {
+    let cursor = &*(cursor as *mut VTabCursor<T>);
+
+    c_int::from(cursor.cursor.is_none())
+}
+
+unsafe extern "C" fn column<T: SqliteVirtualTable>(
+    cursor: *mut sqlite3_vtab_cursor,
+    ctx: *mut sqlite3_context,
+    index: c_int,
+) -> c_int {
+    let cursor = &*(cursor as *mut VTabCursor<T>);
+
+    let Some(scan) = cursor.cursor.as_ref() else {
+        return SQLITE_OK;
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        scan.column(index as usize, &mut SqliteVirtualValue { ctx: &mut *ctx })
+    }));
+
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => set_error(ctx, &error.to_string()),
+        Err(_) => set_error(ctx, "panic in virtual table"),
+    }
+
+    SQLITE_OK
+}
+
+unsafe extern "C" fn rowid<T: SqliteVirtualTable>(
+    cursor: *mut sqlite3_vtab_cursor,
+    rowid: *mut sqlite3_int64,
+) -> c_int {
+    let vtab = (*cursor).pVtab;
+    let cursor = &*(cursor as *mut VTabCursor<T>);
+
+    let Some(scan) = cursor.cursor.as_ref() else {
+        return SQLITE_OK;
+    };
+
+    match catch_unwind(AssertUnwindSafe(|| scan.rowid())) {
+        Ok(id) => {
+            *rowid = id;
+            SQLITE_OK
+        }
+        Err(_) => set_vtab_error(vtab, "panic in virtual table"),
+    }
+}
+
+/// Copies the message into a string allocated by SQLite, which SQLite frees.
+unsafe fn error_message(message: &str) -> *mut c_char {
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+
+    sqlite3_mprintf(b"%s\0".as_ptr() as *const c_char, message.as_ptr())
+}
+
+/// Reports an error of a virtual table to SQLite.
+unsafe fn set_vtab_error(vtab: *mut sqlite3_vtab, message: &str) -> c_int {
+    if !(*vtab).zErrMsg.is_null() {
+        sqlite3_free((*vtab).zErrMsg as *mut c_void);
+    }
+
+    (*vtab).zErrMsg = error_message(message);
+
+    SQLITE_ERROR
+}
@@ -3,6 +3,7 @@ use std::future::Future;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 
 use futures_intrusive::sync::{Mutex, MutexGuard};
 
@@ -18,7 +19,7 @@ use crate::connection::describe::describe;
 use crate::connection::establish::EstablishParams;
 use crate::connection::ConnectionState;
 use crate::connection::{execute, ConnectionHandleRaw};
-use crate::{Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStatement};
+use crate::{Sqlite, SqliteArguments, SqliteError, SqliteQueryResult, SqliteRow, SqliteStatement};
 
 // Each SQLite connection has a dedicated thread.
 
@@ -29,7 +30,7 @@ use crate::{Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStateme
 pub(crate) struct ConnectionWorker {
     command_tx: flume::Sender<Command>,
     /// The `sqlite3` pointer. NOTE: access is unsynchronized!
-    pub(crate) _handle_raw: ConnectionHandleRaw,
+    pub(crate) handle_raw: ConnectionHandleRaw,
     /// Mutex for locking access to the database.
     pub(crate) shared: Arc<WorkerSharedState>,
 }
@@ -52,6 +53,7 @@ enum Command {
         query: Box<str>,
         arguments: Option<SqliteArguments<'static>>,
         persistent: bool,
+        timeout: Option<Duration>,
         tx: flume::Sender<Result<Either<SqliteQueryResult, SqliteRow>, Error>>,
     },
     Begin {
@@ -104,7 +106,7 @@ impl ConnectionWorker {
                 if establish_tx
                     .send(Ok(Self {
                         command_tx,
-                        _handle_raw: conn.handle.to_raw(),
+                        handle_raw: conn.handle.to_raw(),
                         shared: Arc::clone(&shared),
                     }))
                     .is_err()
@@ -136,23 +138,55 @@ impl ConnectionWorker {
                             query,
                             arguments,
                             persistent,
+                            timeout,
                             tx,
                         } => {
-                            let iter = match execute::iter(&mut conn, &query, arguments, persistent)
-                            {
-                                Ok(iter) => iter,
-                                Err(e) => {
-                                    tx.send(Err(e)).ok();
-                                    continue;
+                            // interrupt the statement if the results are dropped or it
+                            // runs longer than its timeout or the statement timeout
+                            let deadline = timeout
+                                .or(conn.statement_timeout)
+                                .map(|t| Instant::now() + t);
+                            let results = tx.clone();
+
+                            conn.watch_progress(move || {
+                                !results.is_disconnected()
+                                    && deadline.map_or(true, |deadline| Instant::now() < deadline)
+                            });
+
+                            // report interrupts after the deadline as timeouts
+                            let timed_out = |e: Error| match &e {
+                                Error::Database(db)
+                                    if deadline.map_or(false, |d| Instant::now() >= d)
+                                        && db
+                                            .try_downcast_ref::<SqliteError>()
+                                            .map_or(false, SqliteError::is_interrupt) =>
+                                {
+                                    Error::Timeout
                                 }
+                                _ => e,
                             };
 
-                            for res in iter {
-                                if tx.send(res).is_err() {
-                                    break;
+                            match execute::iter(&mut conn, &query, arguments, persistent) {
+                                Ok(iter) => {
+                                    for res in iter {
+                                        if tx.send(res.map_err(timed_out)).is_err() {
+                                            break;
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    tx.send(Err(timed_out(e))).ok();
                                 }
                             }
 
+                            conn.unwatch_progress();
+
+                            if conn.transaction_depth > 0 && !conn.handle.in_transaction() {
+                                // SQLite rolls back the transaction when a statement modifying
+                                // the database is interrupted.
+                                conn.transaction_depth = 0;
+                            }
+
                             update_cached_statements_size(&conn, &shared.cached_statements_size);
                         }
                         Command::Begin { tx } => {
@@ -199,6 +233,12 @@ impl ConnectionWorker {
                             };
                             let res_ok = res.is_ok();
 
+                            if !res_ok && !conn.handle.in_transaction() {
+                                // The COMMIT failed but the transaction was rolled back anyway,
+                                // e.g. by a commit hook.
+                                conn.transaction_depth = 0;
+                            }
+
                             if tx.blocking_send(res).is_err() && res_ok {
                                 // The COMMIT was processed but not acknowledged. This means that
                                 // the `Transaction` doesn't know it was committed and will try to
@@ -285,6 +325,7 @@ impl ConnectionWorker {
         args: Option<SqliteArguments<'_>>,
         chan_size: usize,
         persistent: bool,
+        timeout: Option<Duration>,
     ) -> Result<flume::Receiver<Result<Either<SqliteQueryResult, SqliteRow>, Error>>, Error> {
         let (tx, rx) = flume::bounded(chan_size);
 
@@ -293,6 +334,7 @@ impl ConnectionWorker {
                 query: query.into(),
                 arguments: args.map(SqliteArguments::into_static),
                 persistent,
+                timeout,
                 tx,
             })
             .await
@@ -30,6 +30,9 @@ impl Database for Sqlite {
     const NAME: &'static str = "SQLite";
 
     const URL_SCHEMES: &'static [&'static str] = &["sqlite"];
+
+    // `SQLITE_LIMIT_VARIABLE_NUMBER` defaults to 32766 since SQLite 3.32.0
+    const MAX_BIND_PARAMETERS: usize = 32766;
 }
 
 impl<'r> HasValueRef<'r> for Sqlite {
@@ -5,9 +5,9 @@ use std::os::raw::c_int;
 use std::{borrow::Cow, str::from_utf8_unchecked};
 
 use libsqlite3_sys::{
-    sqlite3, sqlite3_errmsg, sqlite3_extended_errcode, SQLITE_CONSTRAINT_CHECK,
+    sqlite3, sqlite3_errmsg, sqlite3_errstr, sqlite3_extended_errcode, SQLITE_CONSTRAINT_CHECK,
     SQLITE_CONSTRAINT_FOREIGNKEY, SQLITE_CONSTRAINT_NOTNULL, SQLITE_CONSTRAINT_PRIMARYKEY,
-    SQLITE_CONSTRAINT_UNIQUE,
+    SQLITE_CONSTRAINT_UNIQUE, SQLITE_INTERRUPT,
 };
 
 pub(crate) use sqlx_core::error::*;
@@ -40,6 +40,22 @@ impl SqliteError {
         }
     }
 
+    /// For errors without a database handle, e.g. when registering a VFS
+    pub(crate) fn from_code(code: c_int) -> Self {
+        // return English-language text that describes the result code
+        let message = unsafe { CStr::from_ptr(sqlite3_errstr(code)) };
+
+        Self {
+            code,
+            message: message.to_string_lossy().into_owned(),
+        }
+    }
+
+    /// Returns `true` if the statement was interrupted, e.g. by `sqlite3_interrupt()`.
+    pub(crate) fn is_interrupt(&self) -> bool {
+        self.code == SQLITE_INTERRUPT
+    }
+
     /// For errors during extension load, the error message is supplied via a separate pointer
     pub(crate) fn extension(handle: *mut sqlite3, error_msg: &CStr) -> Self {
         let mut err = Self::new(handle);
@@ -33,11 +33,19 @@ use std::sync::atomic::AtomicBool;
 
 pub use arguments::{SqliteArgumentValue, SqliteArguments};
 pub use column::SqliteColumn;
-pub use connection::{LockedSqliteHandle, SqliteConnection};
+pub use connection::{
+    LockedSqliteHandle, SqliteAggregate, SqliteAuthorization, SqliteAuthorizerAction,
+    SqliteAuthorizerRequest, SqliteBackupProgress, SqliteBlob, SqliteCheckpoint,
+    SqliteCheckpointMode, SqliteConnection, SqliteInterruptHandle, SqliteOperation, SqliteUpdate,
+    SqliteVirtualCursor, SqliteVirtualTable, SqliteVirtualValue, SqliteWindowFunction,
+};
+#[cfg(feature = "session")]
+pub use connection::{SqliteConflict, SqliteConflictAction, SqliteConflictKind};
 pub use database::Sqlite;
 pub use error::SqliteError;
 pub use options::{
-    SqliteAutoVacuum, SqliteConnectOptions, SqliteJournalMode, SqliteLockingMode, SqliteSynchronous,
+    SqliteAutoVacuum, SqliteConnectOptions, SqliteJournalMode, SqliteLockingMode,
+    SqliteSynchronous, SqliteTempStore,
 };
 pub use query_result::SqliteQueryResult;
 pub use row::SqliteRow;
@@ -68,6 +76,7 @@ mod transaction;
 mod type_info;
 pub mod types;
 mod value;
+pub mod vfs;
 
 #[cfg(feature = "any")]
 pub mod any;
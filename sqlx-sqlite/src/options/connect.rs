@@ -1,11 +1,12 @@
-use crate::{SqliteConnectOptions, SqliteConnection};
+use crate::options::quote_literal;
+use crate::{Sqlite, SqliteConnectOptions, SqliteConnection};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
-use sqlx_core::connection::ConnectOptions;
+use sqlx_core::connection::{ConnectOptions, QueryEventHandler, QueryRewriter};
 use sqlx_core::error::Error;
-use sqlx_core::executor::Executor;
 use std::fmt::Write;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -32,14 +33,28 @@ impl ConnectOptions for SqliteConnectOptions {
             let mut conn = SqliteConnection::establish(self).await?;
 
             // Execute PRAGMAs
-            conn.execute(&*self.pragma_string()).await?;
+            conn.fetch_all_internal(&self.pragma_string()).await?;
 
-            if !self.collations.is_empty() {
+            // Attach databases, so that every connection sees the same schemas
+            if !self.attachments.is_empty() {
+                conn.fetch_all_internal(&self.attach_string()?).await?;
+            }
+
+            if !self.collations.is_empty() || !self.functions.is_empty() || !self.modules.is_empty()
+            {
                 let mut locked = conn.lock_handle().await?;
 
                 for collation in &self.collations {
                     collation.create(&mut locked.guard.handle)?;
                 }
+
+                for function in &self.functions {
+                    function.create(&mut locked.guard.handle)?;
+                }
+
+                for module in &self.modules {
+                    module.create(&mut locked.guard.handle)?;
+                }
             }
 
             Ok(conn)
@@ -55,6 +70,16 @@ impl ConnectOptions for SqliteConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn event_handler(mut self, handler: Arc<dyn QueryEventHandler>) -> Self {
+        self.log_settings.event_handler(handler);
+        self
+    }
+
+    fn query_rewriter(mut self, rewriter: Arc<dyn QueryRewriter<Sqlite>>) -> Self {
+        self.query_rewriter = Some(rewriter);
+        self
+    }
 }
 
 impl SqliteConnectOptions {
@@ -70,4 +95,27 @@ impl SqliteConnectOptions {
 
         string
     }
+
+    /// Collect all `ATTACH DATABASE` commands into a single string
+    pub(crate) fn attach_string(&self) -> Result<String, Error> {
+        let mut string = String::new();
+
+        for (alias, path) in &self.attachments {
+            let path = path.to_str().ok_or_else(|| {
+                Error::Configuration(
+                    format!("path of attached database {alias:?} must be valid UTF-8").into(),
+                )
+            })?;
+
+            write!(
+                string,
+                "ATTACH DATABASE {} AS \"{}\"; ",
+                quote_literal(path),
+                alias.replace('"', "\"\"")
+            )
+            .ok();
+        }
+
+        Ok(string)
+    }
 }
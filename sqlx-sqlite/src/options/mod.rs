@@ -6,8 +6,9 @@ mod journal_mode;
 mod locking_mode;
 mod parse;
 mod synchronous;
+mod temp_store;
 
-use crate::connection::LogSettings;
+use crate::connection::{LogSettings, QueryRewriter};
 pub use auto_vacuum::SqliteAutoVacuum;
 pub use journal_mode::SqliteJournalMode;
 pub use locking_mode::SqliteLockingMode;
@@ -15,9 +16,16 @@ use std::cmp::Ordering;
 use std::sync::Arc;
 use std::{borrow::Cow, time::Duration};
 pub use synchronous::SqliteSynchronous;
+pub use temp_store::SqliteTempStore;
 
 use crate::common::DebugFn;
 use crate::connection::collation::Collation;
+use crate::connection::function::Function;
+use crate::connection::vtab::Module;
+use crate::connection::{SqliteAggregate, SqliteVirtualTable, SqliteWindowFunction};
+use crate::encode::Encode;
+use crate::error::BoxDynError;
+use crate::{Sqlite, SqliteValue};
 use sqlx_core::IndexMap;
 
 /// Options and flags which can be used to configure a SQLite connection.
@@ -60,8 +68,12 @@ pub struct SqliteConnectOptions {
     pub(crate) create_if_missing: bool,
     pub(crate) shared_cache: bool,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) prepare_persistent: bool,
     pub(crate) busy_timeout: Duration,
+    pub(crate) busy_backoff: Option<(Duration, Duration)>,
+    pub(crate) statement_timeout: Option<Duration>,
     pub(crate) log_settings: LogSettings,
+    pub(crate) query_rewriter: Option<Arc<dyn QueryRewriter<Sqlite>>>,
     pub(crate) immutable: bool,
     pub(crate) vfs: Option<Cow<'static, str>>,
 
@@ -71,11 +83,16 @@ pub struct SqliteConnectOptions {
     /// be added to the map with a `None` value.
     /// <https://www.sqlite.org/loadext.html#loading_an_extension>
     pub(crate) extensions: IndexMap<Cow<'static, str>, Option<Cow<'static, str>>>,
+    pub(crate) load_extension_sql: bool,
+    /// Databases attached to every connection, as a map of <Alias : Path>.
+    pub(crate) attachments: IndexMap<Cow<'static, str>, Cow<'static, Path>>,
 
     pub(crate) command_channel_size: usize,
     pub(crate) row_channel_size: usize,
 
     pub(crate) collations: Vec<Collation>,
+    pub(crate) functions: Vec<Function>,
+    pub(crate) modules: Vec<Module>,
 
     pub(crate) serialized: bool,
     pub(crate) thread_name: Arc<DebugFn<dyn Fn(u64) -> String + Send + Sync + 'static>>,
@@ -178,6 +195,13 @@ impl SqliteConnectOptions {
 
         pragmas.insert("auto_vacuum".into(), None);
 
+        // Memory settings of the connection, which SQLite leaves to the compile-time defaults.
+        pragmas.insert("cache_size".into(), None);
+        pragmas.insert("mmap_size".into(), None);
+        pragmas.insert("temp_store".into(), None);
+
+        pragmas.insert("wal_autocheckpoint".into(), None);
+
         // Soft limit on the number of rows that `ANALYZE` touches per index.
         pragmas.insert("analysis_limit".into(), None);
 
@@ -188,13 +212,21 @@ impl SqliteConnectOptions {
             create_if_missing: false,
             shared_cache: false,
             statement_cache_capacity: 100,
+            prepare_persistent: true,
             busy_timeout: Duration::from_secs(5),
+            busy_backoff: None,
+            statement_timeout: None,
             log_settings: Default::default(),
+            query_rewriter: None,
             immutable: false,
             vfs: None,
             pragmas,
             extensions: Default::default(),
+            load_extension_sql: false,
+            attachments: Default::default(),
             collations: Default::default(),
+            functions: Default::default(),
+            modules: Default::default(),
             serialized: false,
             thread_name: Arc::new(DebugFn(|id| format!("sqlx-sqlite-worker-{}", id))),
             command_channel_size: 50,
@@ -211,6 +243,32 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Opens the database in memory instead of from a file, with the
+    /// [`SQLITE_OPEN_MEMORY` flag](https://www.sqlite.org/c3ref/open.html).
+    ///
+    /// The database only lives as long as the connection unless the [shared cache](Self::shared_cache)
+    /// is enabled as well, in which case all connections opened with the same [filename](Self::filename)
+    /// share the database until the last of them is closed. See [`Self::shared_memory()`].
+    ///
+    /// By default, this is disabled.
+    pub fn in_memory(mut self, on: bool) -> Self {
+        self.in_memory = on;
+        self
+    }
+
+    /// Opens the named in-memory database `name`, which is shared by all connections opened with
+    /// these options, e.g. by all connections of a pool, and by other options with the same name.
+    ///
+    /// This is equivalent to the connection URL `sqlite:file:<name>?mode=memory&cache=shared`.
+    /// The database is deleted when the last connection to it is closed, so a pool should keep at
+    /// least one connection open with [`min_connections`][sqlx_core::pool::PoolOptions::min_connections]
+    /// and without [`idle_timeout`][sqlx_core::pool::PoolOptions::idle_timeout] to retain it.
+    ///
+    /// See [In-Memory Databases](https://www.sqlite.org/inmemorydb.html#sharedmemdb) for details.
+    pub fn shared_memory(self, name: impl AsRef<Path>) -> Self {
+        self.filename(name).in_memory(true).shared_cache(true)
+    }
+
     /// Set the enforcement of [foreign key constraints](https://www.sqlite.org/pragma.html#pragma_foreign_keys).
     ///
     /// SQLx chooses to enable this by default so that foreign keys function as expected,
@@ -261,6 +319,37 @@ impl SqliteConnectOptions {
 
     /// Sets the [access mode](https://www.sqlite.org/c3ref/open.html) to open the database
     /// for read-only access.
+    ///
+    /// In [WAL mode](SqliteJournalMode::Wal), readers do not block the writer and vice versa,
+    /// but there can only be a single writer at a time. Instead of letting connections of one
+    /// pool wait for the write lock, an application can use a pool of read-only connections
+    /// and a separate pool with a single connection for writing:
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+    ///
+    /// let options = SqliteConnectOptions::new()
+    ///     .filename("data.db")
+    ///     .journal_mode(SqliteJournalMode::Wal);
+    ///
+    /// // the writer switches the database into WAL mode, which is a persistent setting
+    /// let writer = SqlitePoolOptions::new()
+    ///     .max_connections(1)
+    ///     .connect_with(options.clone().create_if_missing(true))
+    ///     .await?;
+    ///
+    /// let readers = SqlitePoolOptions::new()
+    ///     .max_connections(8)
+    ///     .connect_with(options.read_only(true))
+    ///     .await?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Statements which write to the database fail on read-only connections with
+    /// `SQLITE_READONLY` (result code `8`).
     pub fn read_only(mut self, read_only: bool) -> Self {
         self.read_only = read_only;
         self
@@ -281,11 +370,27 @@ impl SqliteConnectOptions {
     /// dropped.
     ///
     /// The default cache capacity is 100 statements.
+    ///
+    /// The capacity can be changed on an open connection with
+    /// [`SqliteConnection::set_statement_cache_capacity()`](crate::SqliteConnection::set_statement_cache_capacity).
     pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
         self.statement_cache_capacity = capacity;
         self
     }
 
+    /// Sets whether cached statements are prepared with the `SQLITE_PREPARE_PERSISTENT` flag,
+    /// which tells SQLite that they are retained for a long time. SQLite then allocates their
+    /// memory from the heap instead of the limited lookaside memory of the connection.
+    ///
+    /// The default is `true`. Statements which are not cached are never prepared as persistent.
+    ///
+    /// See [`sqlite3_prepare_v3()`](https://www.sqlite.org/c3ref/c_prepare_normalize.html#sqlitepreparepersistent)
+    /// for details.
+    pub fn prepare_persistent(mut self, persistent: bool) -> Self {
+        self.prepare_persistent = persistent;
+        self
+    }
+
     /// Sets a timeout value to wait when the database is locked, before
     /// returning a busy timeout error.
     ///
@@ -295,6 +400,44 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Retries statements which fail because the database is locked by another connection
+    /// with exponential backoff and jitter, instead of the fixed intervals of SQLite.
+    ///
+    /// The first retry waits up to `initial`, and every further retry waits up to twice as long
+    /// as the one before, but at most `max`. Each wait is randomly shortened by up to half, so
+    /// that concurrent writers which were blocked by the same lock do not retry in lockstep.
+    /// The [busy timeout](Self::busy_timeout) still limits the total time spent waiting.
+    ///
+    /// The waits block the worker thread of the connection, not the async runtime.
+    ///
+    /// By default, SQLite's builtin busy handler is used.
+    pub fn busy_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.busy_backoff = Some((initial, max));
+        self
+    }
+
+    /// Interrupts statements which run longer than `timeout`, failing them with
+    /// [`Error::Timeout`](crate::error::Error::Timeout), so that a runaway query does not block the
+    /// connection indefinitely. The connection remains usable.
+    ///
+    /// The time is measured from the start of the execution on the worker thread, including
+    /// the time spent waiting for the caller to consume the rows. Statements are checked
+    /// periodically while SQLite evaluates them, so they may run slightly longer, and waiting
+    /// for a locked database is limited by the [busy timeout](Self::busy_timeout) instead.
+    ///
+    /// Independently of this timeout, a statement is interrupted as soon as the stream of its
+    /// results is dropped.
+    ///
+    /// The timeout can be changed on an open connection with
+    /// [`SqliteConnection::set_statement_timeout()`](crate::SqliteConnection::set_statement_timeout),
+    /// and is overridden by the timeout of a single query set with `Query::timeout()`.
+    ///
+    /// By default, there is no timeout.
+    pub fn statement_timeout(mut self, timeout: Duration) -> Self {
+        self.statement_timeout = Some(timeout);
+        self
+    }
+
     /// Sets the [synchronous](https://www.sqlite.org/pragma.html#pragma_synchronous) setting for the database connection.
     ///
     /// The default synchronous settings is FULL. However, if durability is not a concern,
@@ -313,6 +456,59 @@ impl SqliteConnectOptions {
         self.pragma("auto_vacuum", auto_vacuum.as_str())
     }
 
+    /// Sets the suggested maximum number of database pages that SQLite holds in memory per
+    /// attached database.
+    ///
+    /// The default is 2000 KiB, see [`cache_size_kib()`](Self::cache_size_kib).
+    ///
+    /// See [`PRAGMA cache_size`](https://www.sqlite.org/pragma.html#pragma_cache_size) for details.
+    pub fn cache_size(self, pages: u32) -> Self {
+        self.pragma("cache_size", pages.to_string())
+    }
+
+    /// Sets the suggested maximum amount of memory in KiB that SQLite uses for the page cache
+    /// per attached database, regardless of the page size.
+    ///
+    /// See [`PRAGMA cache_size`](https://www.sqlite.org/pragma.html#pragma_cache_size) for details.
+    pub fn cache_size_kib(self, kib: u32) -> Self {
+        self.pragma("cache_size", format!("-{kib}"))
+    }
+
+    /// Sets the maximum number of bytes of the database file that SQLite accesses via
+    /// memory-mapped I/O, or disables memory-mapped I/O if `0`.
+    ///
+    /// The default is `0` unless SQLite was compiled otherwise. The size is silently limited to
+    /// the compile-time maximum, which is 2 GiB for the bundled SQLite.
+    ///
+    /// See [`PRAGMA mmap_size`](https://www.sqlite.org/pragma.html#pragma_mmap_size) and
+    /// [Memory-Mapped I/O](https://www.sqlite.org/mmap.html) for details.
+    pub fn mmap_size(self, bytes: u64) -> Self {
+        self.pragma("mmap_size", bytes.to_string())
+    }
+
+    /// Sets where temporary tables and indices are stored.
+    ///
+    /// The default temp_store setting is DEFAULT, which uses the compile-time default.
+    ///
+    /// See [`PRAGMA temp_store`](https://www.sqlite.org/pragma.html#pragma_temp_store) for details.
+    pub fn temp_store(self, temp_store: SqliteTempStore) -> Self {
+        self.pragma("temp_store", temp_store.as_str())
+    }
+
+    /// Sets the number of pages in the write-ahead log after which SQLite runs a passive
+    /// checkpoint automatically when a transaction commits, or disables automatic checkpoints
+    /// if `0`.
+    ///
+    /// The default is 1000 pages. Only applies in [WAL mode](SqliteJournalMode::Wal), see
+    /// [`SqliteConnection::wal_checkpoint()`](crate::SqliteConnection::wal_checkpoint) to
+    /// checkpoint explicitly.
+    ///
+    /// See [`PRAGMA wal_autocheckpoint`](https://www.sqlite.org/pragma.html#pragma_wal_autocheckpoint)
+    /// for details.
+    pub fn wal_autocheckpoint(self, pages: u32) -> Self {
+        self.pragma("wal_autocheckpoint", pages.to_string())
+    }
+
     /// Sets the [page_size](https://www.sqlite.org/pragma.html#pragma_page_size) setting for the database connection.
     ///
     /// The default page_size setting is 4096.
@@ -324,6 +520,20 @@ impl SqliteConnectOptions {
         self.pragma("page_size", page_size.to_string())
     }
 
+    /// Sets the key of an encrypted database for [SQLCipher](https://www.zetetic.net/sqlcipher/)
+    /// or the [SQLite Encryption Extension](https://www.sqlite.org/see).
+    ///
+    /// The key is executed as `PRAGMA key` before any other statement on every connection.
+    /// It is quoted as a string literal and derived into the encryption key by the extension;
+    /// to use a raw key, set the `key` pragma to a blob literal with [`pragma`][Self::pragma]
+    /// instead, e.g. `.pragma("key", "\"x'2DD2...'\"")`.
+    ///
+    /// Use [`SqliteConnection::rekey()`][crate::SqliteConnection::rekey] to change the key
+    /// of a database.
+    pub fn pragma_key(self, key: impl AsRef<str>) -> Self {
+        self.pragma("key", quote_literal(key.as_ref()))
+    }
+
     /// Sets custom initial pragma for the database connection.
     pub fn pragma<K, V>(mut self, key: K, value: V) -> Self
     where
@@ -359,6 +569,108 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Add a scalar SQL function implemented in Rust.
+    ///
+    /// The function is called with the values of its arguments and its result is encoded
+    /// like a bind parameter; returning an error fails the statement with its message.
+    /// `n_args` is the number of arguments the function accepts, or `-1` for any number.
+    ///
+    /// Set `deterministic` if the function always returns the same result for the same
+    /// arguments, which allows SQLite to use it in indexes and to optimize calls away.
+    ///
+    /// If a function with the same name and number of arguments already exists, it will be
+    /// replaced.
+    ///
+    /// See [`sqlite3_create_function()`](https://www.sqlite.org/c3ref/create_function.html) for details.
+    ///
+    /// ```rust,no_run
+    /// use sqlx::sqlite::SqliteConnectOptions;
+    /// use sqlx::Value;
+    ///
+    /// let options = SqliteConnectOptions::new().function("reverse", 1, true, |args| {
+    ///     let text: String = args[0].try_decode()?;
+    ///     Ok(text.chars().rev().collect::<String>())
+    /// });
+    /// ```
+    pub fn function<N, F, R>(mut self, name: N, n_args: i32, deterministic: bool, func: F) -> Self
+    where
+        N: Into<Arc<str>>,
+        F: Fn(&[SqliteValue]) -> Result<R, BoxDynError> + Send + Sync + 'static,
+        R: Encode<'static, Sqlite>,
+    {
+        self.functions
+            .push(Function::new(name, n_args, deterministic, func));
+        self
+    }
+
+    /// Add an aggregate SQL function implemented in Rust.
+    ///
+    /// `n_args` and `deterministic` have the same meaning as for [`function()`][Self::function].
+    /// If an aggregate or function with the same name and number of arguments already exists,
+    /// it will be replaced.
+    ///
+    /// See [`SqliteAggregate`] and
+    /// [`sqlite3_create_function()`](https://www.sqlite.org/c3ref/create_function.html) for details.
+    pub fn aggregate<N, A>(
+        mut self,
+        name: N,
+        n_args: i32,
+        deterministic: bool,
+        aggregate: A,
+    ) -> Self
+    where
+        N: Into<Arc<str>>,
+        A: SqliteAggregate,
+    {
+        self.functions
+            .push(Function::aggregate(name, n_args, deterministic, aggregate));
+        self
+    }
+
+    /// Add an aggregate window function implemented in Rust.
+    ///
+    /// A window function can also be used as an ordinary aggregate. `n_args` and
+    /// `deterministic` have the same meaning as for [`function()`][Self::function].
+    ///
+    /// Requires SQLite 3.25.0 or later. See [`SqliteWindowFunction`] and
+    /// [`sqlite3_create_window_function()`](https://www.sqlite.org/c3ref/create_function.html)
+    /// for details.
+    pub fn window_function<N, A>(
+        mut self,
+        name: N,
+        n_args: i32,
+        deterministic: bool,
+        window: A,
+    ) -> Self
+    where
+        N: Into<Arc<str>>,
+        A: SqliteWindowFunction,
+    {
+        self.functions
+            .push(Function::window(name, n_args, deterministic, window));
+        self
+    }
+
+    /// Add a read-only virtual table module implemented in Rust.
+    ///
+    /// The module can be queried directly as a table named `name`, or used to create virtual
+    /// tables with `CREATE VIRTUAL TABLE <table> USING <name>`, which store the declaration of
+    /// the table in the database; arguments of the statement are ignored. Inserting, updating
+    /// or deleting rows fails.
+    ///
+    /// If a module with the same name already exists, it will be replaced.
+    ///
+    /// See [`SqliteVirtualTable`] and [The Virtual Table Mechanism Of SQLite](https://www.sqlite.org/vtab.html)
+    /// for details.
+    pub fn module<N, T>(mut self, name: N, table: T) -> Self
+    where
+        N: Into<Arc<str>>,
+        T: SqliteVirtualTable,
+    {
+        self.modules.push(Module::new(name, table));
+        self
+    }
+
     /// Set to `true` to signal to SQLite that the database file is on read-only media.
     ///
     /// If enabled, SQLite assumes the database file _cannot_ be modified, even by higher
@@ -432,12 +744,39 @@ impl SqliteConnectOptions {
     /// Sets the [`vfs`](https://www.sqlite.org/vfs.html) parameter of the database connection.
     ///
     /// The default value is empty, and sqlite will use the default VFS object depending on the
-    /// operating system.
+    /// operating system. A VFS implemented in Rust must be [registered](crate::vfs::register)
+    /// before connecting.
     pub fn vfs(mut self, vfs_name: impl Into<Cow<'static, str>>) -> Self {
         self.vfs = Some(vfs_name.into());
         self
     }
 
+    /// Attach the database file at `path` as the schema `alias` when the database connection is
+    /// established, using [`ATTACH DATABASE`](https://www.sqlite.org/lang_attach.html).
+    ///
+    /// Since the database is attached to every connection, e.g. every connection of a pool, queries
+    /// can refer to its tables as `alias.table` regardless of which connection serves them.
+    /// Attaching another database with the same alias replaces the previous one.
+    ///
+    /// The path is interpreted like the [filename](Self::filename) of the main database, relative
+    /// to the current working directory; the attached database must already exist unless
+    /// [`create_if_missing`](Self::create_if_missing) is enabled.
+    /// ```rust,no_run
+    /// # use sqlx_core::error::Error;
+    /// # use std::str::FromStr;
+    /// # use sqlx_sqlite::SqliteConnectOptions;
+    /// # fn options() -> Result<SqliteConnectOptions, Error> {
+    /// let options = SqliteConnectOptions::from_str("sqlite://data.db")?
+    ///     .attach("archive.db", "archive");
+    /// # Ok(options)
+    /// # }
+    /// ```
+    pub fn attach(mut self, path: impl AsRef<Path>, alias: impl Into<Cow<'static, str>>) -> Self {
+        self.attachments
+            .insert(alias.into(), Cow::Owned(path.as_ref().to_owned()));
+        self
+    }
+
     /// Load an [extension](https://www.sqlite.org/loadext.html) at run-time when the database connection
     /// is established, using the default entry point.
     ///
@@ -476,6 +815,20 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Sets whether extensions can be loaded with the `load_extension()` SQL function.
+    ///
+    /// Extensions added with [`extension`][Self::extension] or loaded with
+    /// [`SqliteConnection::load_extension()`][crate::SqliteConnection::load_extension] do not
+    /// require this. The default is `false`, as it allows any SQL executed on the connection to
+    /// load arbitrary shared libraries.
+    ///
+    /// See [`sqlite3_enable_load_extension()`](https://www.sqlite.org/c3ref/enable_load_extension.html)
+    /// for details.
+    pub fn enable_load_extension(mut self, enable: bool) -> Self {
+        self.load_extension_sql = enable;
+        self
+    }
+
     /// Execute `PRAGMA optimize;` on the SQLite connection before closing.
     ///
     /// The SQLite manual recommends using this for long-lived databases.
@@ -491,7 +844,8 @@ impl SqliteConnectOptions {
     /// If not `None`, the `analysis_limit` here overrides the global `analysis_limit` setting,
     /// but only for the `PRAGMA optimize;` call.
     ///
-    /// Not enabled by default.
+    /// Not enabled by default, and ignored for [read-only](Self::read_only) and
+    /// [immutable](Self::immutable) connections, which cannot store the statistics.
     ///
     /// See [the SQLite manual](https://www.sqlite.org/lang_analyze.html#automatically_running_analyze) for details.
     pub fn optimize_on_close(
@@ -549,3 +903,8 @@ impl SqliteConnectOptions {
         self
     }
 }
+
+// quotes a value as an SQL string literal
+pub(crate) fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
@@ -155,6 +155,21 @@ fn test_parse_in_memory() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn test_parse_named_in_memory() -> Result<(), Error> {
+    let options: SqliteConnectOptions = "sqlite:file:shared?mode=memory&cache=shared".parse()?;
+    assert!(options.in_memory);
+    assert!(options.shared_cache);
+    assert_eq!(&*options.filename.to_string_lossy(), "file:shared");
+
+    let options: SqliteConnectOptions = "sqlite://file:shared?mode=memory".parse()?;
+    assert!(options.in_memory);
+    assert!(options.shared_cache);
+    assert_eq!(&*options.filename.to_string_lossy(), "file:shared");
+
+    Ok(())
+}
+
 #[test]
 fn test_parse_read_only() -> Result<(), Error> {
     let options: SqliteConnectOptions = "sqlite://a.db?mode=ro".parse()?;
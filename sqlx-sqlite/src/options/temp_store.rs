@@ -0,0 +1,46 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// Refer to [SQLite documentation] for the meaning of various temp_store settings.
+///
+/// [SQLite documentation]: https://www.sqlite.org/pragma.html#pragma_temp_store
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteTempStore {
+    Default,
+    File,
+    Memory,
+}
+
+impl SqliteTempStore {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            SqliteTempStore::Default => "DEFAULT",
+            SqliteTempStore::File => "FILE",
+            SqliteTempStore::Memory => "MEMORY",
+        }
+    }
+}
+
+impl Default for SqliteTempStore {
+    fn default() -> Self {
+        SqliteTempStore::Default
+    }
+}
+
+impl FromStr for SqliteTempStore {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "default" => SqliteTempStore::Default,
+            "file" => SqliteTempStore::File,
+            "memory" => SqliteTempStore::Memory,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {:?} for `temp_store`", s).into(),
+                ));
+            }
+        })
+    }
+}
@@ -0,0 +1,89 @@
+//! Registration of [VFS](https://www.sqlite.org/vfs.html) implementations.
+//!
+//! The VFS of a connection is selected by name with
+//! [`SqliteConnectOptions::vfs()`](crate::SqliteConnectOptions::vfs) or the `vfs` parameter of
+//! a connection URL. Besides the VFS built into SQLite, a VFS implemented in Rust, e.g. a shim
+//! which encrypts or instruments the I/O of another VFS, can be registered with [`register()`].
+
+use std::ffi::{CStr, CString};
+use std::ptr::null;
+
+use libsqlite3_sys::{
+    sqlite3_vfs, sqlite3_vfs_find, sqlite3_vfs_register, sqlite3_vfs_unregister, SQLITE_OK,
+};
+
+use crate::error::Error;
+use crate::SqliteError;
+
+/// Registers `vfs`, so that connections can select it by its `zName`.
+///
+/// If `make_default` is `true`, the VFS is used by all connections which do not select a VFS.
+/// Registering a VFS which is already registered only changes whether it is the default.
+///
+/// See [`sqlite3_vfs_register()`](https://www.sqlite.org/c3ref/vfs_find.html) for details.
+///
+/// # Safety
+///
+/// `vfs` must point to a valid `sqlite3_vfs` whose name and methods remain valid until it is
+/// [unregistered](unregister), and which can be used from any thread.
+pub unsafe fn register(vfs: *mut sqlite3_vfs, make_default: bool) -> Result<(), Error> {
+    let status = sqlite3_vfs_register(vfs, i32::from(make_default));
+
+    if status != SQLITE_OK {
+        return Err(SqliteError::from_code(status).into());
+    }
+
+    Ok(())
+}
+
+/// Unregisters `vfs`. If it was the default, another VFS becomes the default.
+///
+/// See [`sqlite3_vfs_unregister()`](https://www.sqlite.org/c3ref/vfs_find.html) for details.
+///
+/// # Safety
+///
+/// No open connection may use `vfs`.
+pub unsafe fn unregister(vfs: *mut sqlite3_vfs) -> Result<(), Error> {
+    let status = sqlite3_vfs_unregister(vfs);
+
+    if status != SQLITE_OK {
+        return Err(SqliteError::from_code(status).into());
+    }
+
+    Ok(())
+}
+
+/// Returns the VFS registered as `name`, or the default VFS if `name` is `None`.
+///
+/// The returned pointer can be used to forward calls from a shim to the VFS it wraps.
+pub fn find(name: Option<&str>) -> Option<*mut sqlite3_vfs> {
+    let name = match name {
+        Some(name) => Some(CString::new(name).ok()?),
+        None => None,
+    };
+
+    // SAFETY: SQLite synchronizes access to the list of registered VFS
+    let vfs = unsafe { sqlite3_vfs_find(name.as_ref().map_or(null(), |name| name.as_ptr())) };
+
+    (!vfs.is_null()).then_some(vfs)
+}
+
+/// Returns the names of all registered VFS, starting with the default.
+pub fn names() -> Vec<String> {
+    let mut names = Vec::new();
+
+    // SAFETY: the registered VFS and their names remain valid until they are unregistered
+    unsafe {
+        let mut vfs = sqlite3_vfs_find(null());
+
+        while !vfs.is_null() {
+            if !(*vfs).zName.is_null() {
+                names.push(CStr::from_ptr((*vfs).zName).to_string_lossy().into_owned());
+            }
+
+            vfs = (*vfs).pNext;
+        }
+    }
+
+    names
+}
@@ -1,11 +1,12 @@
 use sqlx::any::{AnyConnectOptions, AnyPoolOptions};
-use sqlx::Executor;
+use sqlx::pool::Fairness;
+use sqlx::{Connection, Executor};
 use std::sync::atomic::AtomicI32;
 use std::sync::{
     atomic::{AtomicUsize, Ordering},
     Arc,
 };
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[sqlx_macros::test]
 async fn pool_should_invoke_after_connect() -> anyhow::Result<()> {
@@ -216,3 +217,247 @@ async fn test_pool_callbacks() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn pool_should_bound_acquire_per_call() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(30))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    let start = Instant::now();
+    let res = pool.acquire_timeout(Duration::from_millis(100)).await;
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let res = pool
+        .try_acquire_until(Instant::now() + Duration::from_millis(100))
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+
+    // a deadline in the past fails immediately
+    let res = pool.try_acquire_until(Instant::now()).await;
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+
+    drop(conn);
+
+    let _conn = pool.acquire_timeout(Duration::from_secs(5)).await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_warm_up() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(5)
+        .min_connections(3)
+        .warm_up_on_connect(true)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    // the idle reaper may race to open connections too, so only check the lower bound
+    assert!(pool.size() >= 3);
+
+    // nothing is missing anymore
+    let report = pool.warm_up().await;
+    assert!(report.is_ok());
+    assert_eq!(report.opened, 0);
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(5)
+        .min_connections(3)
+        .connect_lazy(&dotenvy::var("DATABASE_URL")?)?;
+
+    let report = pool.warm_up().await;
+    assert!(report.is_ok(), "{:?}", report.errors);
+    assert!(pool.size() >= 3);
+
+    pool.close().await;
+
+    let report = pool.warm_up().await;
+    assert_eq!(report.into_result()?, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_ping_with_timeout() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .ping_timeout(Duration::from_secs(5))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    assert_eq!(
+        pool.options().get_ping_timeout(),
+        Some(Duration::from_secs(5))
+    );
+
+    let mut conn = pool.acquire().await?;
+    conn.ping_with_timeout(Duration::from_secs(5)).await?;
+    drop(conn);
+
+    // the idle connection passes the check and is reused
+    let mut conn = pool.acquire().await?;
+    conn.execute("SELECT 1").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_count_connection_uses() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let connects = Arc::new(AtomicUsize::new(0));
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .after_connect({
+            let connects = connects.clone();
+            move |_conn, meta| {
+                assert_eq!(meta.connect_count, 0);
+                connects.fetch_add(1, Ordering::SeqCst);
+
+                Box::pin(async move { Ok(()) })
+            }
+        })
+        // close connections after two uses
+        .before_acquire(|_conn, meta| Box::pin(async move { Ok(meta.connect_count < 2) }))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    for _ in 0..3 {
+        let mut conn = pool.acquire().await?;
+        conn.execute("SELECT 1").await?;
+    }
+
+    assert_eq!(connects.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_close_with_timeout() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    // a connection returned before the timeout is closed gracefully
+    let conn = pool.acquire().await?;
+
+    sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+        drop(conn);
+    });
+
+    assert_eq!(pool.close_with_timeout(Duration::from_secs(5)).await, 0);
+    assert!(pool.is_closed());
+    assert!(matches!(pool.acquire().await, Err(sqlx::Error::PoolClosed)));
+
+    // a connection held past the timeout is reported
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    let start = Instant::now();
+    assert_eq!(pool.close_with_timeout(Duration::from_millis(100)).await, 1);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    drop(conn);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_run_once_with_reconnect() -> anyhow::Result<()> {
+    use sqlx::pool::ReconnectPolicy;
+
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .reconnect_policy(ReconnectPolicy::new(2).backoff(Duration::from_millis(10)))
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let value: i32 = pool
+        .with_reconnect(|conn| Box::pin(sqlx::query_scalar("SELECT 1").fetch_one(conn)))
+        .await?;
+
+    assert_eq!(value, 1);
+
+    let attempts = AtomicUsize::new(0);
+
+    // an operation which lost its connection after it started is not run again
+    let res = pool
+        .with_reconnect(|_conn| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+
+            Box::pin(async move {
+                Err::<(), _>(sqlx::Error::Io(std::io::ErrorKind::ConnectionReset.into()))
+            })
+        })
+        .await;
+
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    assert!(matches!(res, Err(sqlx::Error::Io(_))));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_should_serve_waiters_in_order() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .fairness(Fairness::Fifo)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    assert_eq!(pool.options().get_fairness(), Fairness::Fifo);
+
+    let conn = pool.acquire().await?;
+    let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut waiters = Vec::new();
+
+    for i in 0..3 {
+        let pool = pool.clone();
+        let order = order.clone();
+
+        waiters.push(sqlx_core::rt::spawn(async move {
+            let _conn = pool.acquire().await?;
+            order.lock().unwrap().push(i);
+            sqlx_core::rt::sleep(Duration::from_millis(10)).await;
+            Ok::<_, sqlx::Error>(())
+        }));
+
+        // make sure each task is queued before the next one
+        sqlx_core::rt::sleep(Duration::from_millis(50)).await;
+    }
+
+    drop(conn);
+
+    for waiter in waiters {
+        waiter.await?;
+    }
+
+    assert_eq!(*order.lock().unwrap(), [0, 1, 2]);
+
+    Ok(())
+}
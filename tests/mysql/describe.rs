@@ -69,3 +69,38 @@ async fn uses_alias_name() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_column_flags() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE with_flags (
+    id INT UNSIGNED PRIMARY KEY AUTO_INCREMENT,
+    code INT(6) UNSIGNED ZEROFILL NOT NULL,
+    name VARCHAR(10) CHARACTER SET utf8mb4 UNIQUE,
+    data VARBINARY(10)
+);
+    "#,
+    )
+    .await?;
+
+    let d = conn.describe("SELECT * FROM with_flags").await?;
+
+    assert!(d.column(0).is_primary_key());
+    assert!(d.column(0).is_auto_increment());
+    assert!(d.column(0).type_info().is_unsigned());
+
+    assert!(d.column(1).type_info().is_zerofill());
+    assert_eq!(d.column(1).type_info().display_width(), Some(6));
+
+    assert!(d.column(2).is_unique_key());
+    assert!(!d.column(2).type_info().is_binary());
+    assert_eq!(d.column(2).type_info().display_width(), Some(40));
+
+    assert!(d.column(3).type_info().is_binary());
+    assert_eq!(d.column(3).type_info().char_set(), 63);
+
+    Ok(())
+}
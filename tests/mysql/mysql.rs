@@ -1,7 +1,12 @@
 use futures::TryStreamExt;
-use sqlx::mysql::{MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow};
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
-use sqlx_test::{new, setup_if_needed};
+use sqlx::mysql::{
+    MySql, MySqlConnectOptions, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow,
+};
+use sqlx::{
+    Column, ConnectOptions, Connection, Executor, IsolationLevel, Row, Statement,
+    TransactionOptions, TypeInfo,
+};
+use sqlx_test::{new, pool, setup_if_needed};
 use std::env;
 
 #[sqlx_macros::test]
@@ -477,3 +482,419 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_fetches_result_sets_of_multiple_statements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let results: Vec<_> = conn
+        .fetch_result_sets(
+            r#"
+CREATE TEMPORARY TABLE multi (id INTEGER PRIMARY KEY);
+INSERT INTO multi (id) VALUES (1), (2), (3);
+SELECT id FROM multi ORDER BY id;
+            "#,
+        )
+        .try_collect()
+        .await?;
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].rows().is_empty());
+    assert_eq!(results[1].rows_affected(), 3);
+
+    let ids = results[2]
+        .rows()
+        .iter()
+        .map(|row| row.try_get::<i32, _>(0))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_out_params_of_procedures() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("DROP PROCEDURE IF EXISTS sqlx_add_one")
+        .await?;
+    conn.execute(
+        r#"
+CREATE PROCEDURE sqlx_add_one(IN x INT, OUT y INT, INOUT z INT)
+BEGIN
+    SET y = x + 1;
+    SET z = z * 2;
+END
+        "#,
+    )
+    .await?;
+
+    let result = sqlx::query("CALL sqlx_add_one(?, ?, ?)")
+        .bind(41_i32)
+        .bind(None::<i32>)
+        .bind(5_i32)
+        .execute(&mut conn)
+        .await?;
+
+    let params = result.out_params().expect("expected OUT parameters");
+
+    assert_eq!(params.try_get::<i32, _>(0)?, 42);
+    assert_eq!(params.try_get::<i32, _>(1)?, 10);
+
+    conn.execute("DROP PROCEDURE sqlx_add_one").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_tracks_session_state() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = options.track_session_state(true).connect().await?;
+
+    assert!(!conn.session_state().is_changed());
+
+    conn.execute("SET @sqlx_session_var = 1").await?;
+    assert!(conn.session_state().is_changed());
+
+    conn.execute("SET SESSION time_zone = '+01:00'").await?;
+    assert_eq!(
+        conn.session_state().system_variable("time_zone"),
+        Some("+01:00")
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_with_cursor() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let rows: Vec<MySqlRow> = conn
+        .fetch_with_cursor(
+            sqlx::query("SELECT id FROM (SELECT 1 id UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4 UNION ALL SELECT 5) t WHERE id > ? ORDER BY id")
+                .bind(0_i32),
+            2,
+        )
+        .try_collect()
+        .await?;
+
+    let ids: Vec<i64> = rows.iter().map(|row| row.get(0)).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+
+    // the connection is usable after the cursor is exhausted
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_chunked() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    const IDS: &str = "SELECT id FROM (SELECT 1 id UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4 UNION ALL SELECT 5) t ORDER BY id";
+
+    let batches: Vec<Vec<MySqlRow>> = sqlx::query(IDS)
+        .fetch_chunked(&mut conn, 2)
+        .try_collect()
+        .await?;
+
+    let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+    assert_eq!(sizes, vec![2, 2, 1]);
+
+    let ids: Vec<i64> = batches.iter().flatten().map(|row| row.get(0)).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+
+    // rows can be mapped, and batches fetched through a pool
+    let pool = pool::<MySql>().await?;
+
+    let batches: Vec<Vec<(i64,)>> = sqlx::query_as(IDS)
+        .fetch_chunked(&pool, 3)
+        .try_collect()
+        .await?;
+
+    assert_eq!(batches, vec![vec![(1,), (2,), (3,)], vec![(4,), (5,)]]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resets_connections_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options.reset_on_release(true))
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    conn.execute("SET @sqlx_reset_var = 1").await?;
+    conn.execute("CREATE TEMPORARY TABLE sqlx_reset_tmp (id INT)")
+        .await?;
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+
+    let value: Option<i64> = sqlx::query_scalar("SELECT @sqlx_reset_var")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(value, None);
+
+    // the temporary table was dropped
+    assert!(conn.execute("SELECT * FROM sqlx_reset_tmp").await.is_err());
+
+    // the session settings were restored
+    let time_zone: String = sqlx::query_scalar("SELECT @@time_zone")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(time_zone, "+00:00");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_changes_user() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("DROP USER IF EXISTS 'sqlx_change_user'@'%'")
+        .await?;
+    conn.execute("CREATE USER 'sqlx_change_user'@'%' IDENTIFIED BY 'password'")
+        .await?;
+    conn.execute("SET @sqlx_change_user_var = 1").await?;
+
+    conn.change_user("sqlx_change_user", Some("password"), None)
+        .await?;
+
+    let user: String = sqlx::query_scalar("SELECT SUBSTRING_INDEX(CURRENT_USER(), '@', 1)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(user, "sqlx_change_user");
+
+    // the session was reset
+    let value: Option<i64> = sqlx::query_scalar("SELECT @sqlx_change_user_var")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_batches() -> anyhow::Result<()> {
+    use sqlx::mysql::MySqlArguments;
+    use sqlx::Arguments;
+
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE batch (id INTEGER PRIMARY KEY, name TEXT)")
+        .await?;
+
+    let arguments = (1..=5).map(|id| {
+        let mut arguments = MySqlArguments::default();
+        arguments.add(id);
+        arguments.add((id % 2 == 0).then(|| format!("name {id}")));
+        arguments
+    });
+
+    let result = conn
+        .execute_batch("INSERT INTO batch (id, name) VALUES (?, ?)", arguments)
+        .await?;
+
+    assert_eq!(result.rows_affected(), 5);
+
+    let rows: Vec<(i32, Option<String>)> = sqlx::query_as("SELECT id, name FROM batch ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0], (1, None));
+    assert_eq!(rows[1], (2, Some("name 2".to_owned())));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_routes_statements() -> anyhow::Result<()> {
+    use sqlx::mysql::MySqlRouter;
+
+    setup_if_needed();
+
+    let url = env::var("DATABASE_URL")?;
+    let primary = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect(&url)
+        .await?;
+    let replica = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect(&url)
+        .await?;
+
+    let router = MySqlRouter::new(primary.clone()).replica(replica.clone());
+
+    let primary_id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&primary)
+        .await?;
+    let replica_id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&replica)
+        .await?;
+
+    // reads are executed on the replica
+    let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&router)
+        .await?;
+    assert_eq!(id, replica_id);
+
+    // locking reads and other statements are executed on the primary
+    let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID() FROM DUAL FOR UPDATE")
+        .fetch_one(&router)
+        .await?;
+    assert_eq!(id, primary_id);
+
+    router.execute("DO 1").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_statements_after_query_timeout() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = options
+        .query_timeout(std::time::Duration::from_millis(200))
+        .connect()
+        .await?;
+
+    let started = std::time::Instant::now();
+
+    // `SLEEP()` returns 1 when interrupted
+    let interrupted: i64 = sqlx::query_scalar("SELECT SLEEP(5)")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(interrupted, 1);
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+    // the connection remains usable
+    conn.set_query_timeout(None);
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_statements_after_statement_timeout() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let started = std::time::Instant::now();
+
+    // `SLEEP()` returns 1 when interrupted
+    let interrupted: i64 = sqlx::query_scalar("SELECT SLEEP(5)")
+        .timeout(std::time::Duration::from_millis(200))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(interrupted, 1);
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+    // the timeout only applies to that statement
+    let slept: i64 = sqlx::query_scalar("SELECT SLEEP(0.5)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(slept, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_with_handle() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let handle = conn.cancel_handle();
+
+    let connection_id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(u64::from(handle.connection_id()), connection_id);
+
+    let cancel = sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(std::time::Duration::from_millis(200)).await;
+        handle.cancel().await
+    });
+
+    let interrupted: i64 = sqlx::query_scalar("SELECT SLEEP(5)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(interrupted, 1);
+
+    cancel.await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_begin_with_transaction_options() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let mut tx = conn
+        .begin_with(
+            TransactionOptions::new()
+                .isolation(IsolationLevel::Serializable)
+                .read_only(true),
+        )
+        .await?;
+
+    // ER_CANT_EXECUTE_IN_READ_ONLY_TRANSACTION
+    let res = tx
+        .execute("INSERT INTO tweet (text) VALUES ('read only')")
+        .await;
+    assert!(res.is_err());
+
+    tx.rollback().await?;
+
+    // deferrable transactions are a Postgres extension
+    assert!(conn
+        .begin_with(TransactionOptions::new().deferrable(true))
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_results_per_statement() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let results: Vec<_> = conn
+        .execute_many(
+            r#"
+CREATE TEMPORARY TABLE items (id INTEGER PRIMARY KEY AUTO_INCREMENT, name TEXT NOT NULL);
+INSERT INTO items (name) VALUES ('a'), ('b'), ('c');
+UPDATE items SET name = 'z' WHERE id > 1;
+DELETE FROM items WHERE id = 1;
+            "#,
+        )
+        .try_collect()
+        .await?;
+
+    let changes: Vec<u64> = results.iter().map(|r| r.rows_affected()).collect();
+    assert_eq!(changes, [0, 3, 2, 1]);
+
+    // the stream ends at the failing statement
+    let mut stream = conn.execute_many(
+        r#"
+UPDATE items SET name = 'y';
+SELECT * FROM _sqlx_does_not_exist;
+UPDATE items SET name = 'x';
+        "#,
+    );
+
+    assert_eq!(stream.try_next().await?.map(|r| r.rows_affected()), Some(2));
+    assert!(stream.try_next().await.is_err());
+
+    Ok(())
+}
@@ -133,6 +133,53 @@ mod chrono {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn test_type_chrono_session_time_zone() -> anyhow::Result<()> {
+        use sqlx::mysql::{MySqlConnectOptions, MySqlDatetimeMode};
+        use sqlx::ConnectOptions;
+
+        sqlx_test::setup_if_needed();
+
+        let options: MySqlConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+        let mut conn = options
+            .time_zone("+02:00")
+            .datetime_mode(MySqlDatetimeMode::Naive)
+            .connect()
+            .await?;
+
+        conn.execute(
+            r#"
+CREATE TEMPORARY TABLE tz (ts TIMESTAMP, dt DATETIME);
+INSERT INTO tz VALUES (FROM_UNIXTIME(86400), '1970-01-02 02:00:00');
+            "#,
+        )
+        .await?;
+
+        let row = sqlx::query("SELECT ts, dt FROM tz")
+            .fetch_one(&mut conn)
+            .await?;
+
+        // TIMESTAMP values are converted from the session time zone
+        let ts: DateTime<Utc> = row.try_get("ts")?;
+        assert_eq!(ts.timestamp(), 86400);
+
+        // DATETIME values are naive
+        assert!(row.try_get::<DateTime<Utc>, _>("dt").is_err());
+
+        let dt: NaiveDateTime = row.try_get("dt")?;
+        assert_eq!(dt, NaiveDate::from_ymd(1970, 1, 2).and_hms(2, 0, 0));
+
+        // date-times with a time zone cannot be bound in another session time zone
+        let result = sqlx::query("SELECT ?").bind(ts).fetch_one(&mut conn).await;
+        assert!(matches!(result, Err(sqlx::Error::Encode(_))));
+
+        // the rejected query was never sent, so the connection is still usable
+        let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+        assert_eq!(one, 1);
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "time")]
@@ -221,6 +268,10 @@ test_type!(bigdecimal<sqlx::types::BigDecimal>(
     "CAST(0.01234 AS DECIMAL(6, 5))" == "0.01234".parse::<sqlx::types::BigDecimal>().unwrap(),
     "CAST(12.34 AS DECIMAL(4, 2))" == "12.34".parse::<sqlx::types::BigDecimal>().unwrap(),
     "CAST(12345.6789 AS DECIMAL(9, 4))" == "12345.6789".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "CAST(12345678901234567890.123456789012345678901234567890 AS DECIMAL(50, 30))"
+        == "12345678901234567890.123456789012345678901234567890".parse::<sqlx::types::BigDecimal>().unwrap(),
+    "CAST(-9223372036854775808 AS SIGNED)" == sqlx::types::BigDecimal::from(i64::MIN),
+    "CAST(18446744073709551615 AS UNSIGNED)" == sqlx::types::BigDecimal::from(u64::MAX),
 ));
 
 #[cfg(feature = "decimal")]
@@ -232,6 +283,10 @@ test_type!(decimal<sqlx::types::Decimal>(MySql,
     "CAST(0.01234 AS DECIMAL(6, 5))" == sqlx::types::Decimal::from_str("0.01234").unwrap(),
     "CAST(12.34 AS DECIMAL(4, 2))" == sqlx::types::Decimal::from_str("12.34").unwrap(),
     "CAST(12345.6789 AS DECIMAL(9, 4))" == sqlx::types::Decimal::from_str("12345.6789").unwrap(),
+    "CAST(1234567890.123456789012345678 AS DECIMAL(28, 18))"
+        == sqlx::types::Decimal::from_str("1234567890.123456789012345678").unwrap(),
+    "CAST(-9223372036854775808 AS SIGNED)" == sqlx::types::Decimal::from(i64::MIN),
+    "CAST(18446744073709551615 AS UNSIGNED)" == sqlx::types::Decimal::from(u64::MAX),
 ));
 
 #[cfg(feature = "json")]
@@ -278,6 +333,31 @@ mod json_tests {
     ));
 }
 
+#[sqlx_macros::test]
+async fn test_checked_integer_conversions() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let sql = "SELECT CAST(200 AS UNSIGNED), CAST(18446744073709551615 AS UNSIGNED), -1";
+
+    // BINARY
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+
+    assert_eq!(row.try_get::<i16, _>(0)?, 200);
+    assert!(row.try_get::<i8, _>(0).is_err());
+    assert!(row.try_get::<i64, _>(1).is_err());
+    assert!(row.try_get::<u64, _>(2).is_err());
+
+    // TEXT
+    let row = conn.fetch_one(sql).await?;
+
+    assert_eq!(row.try_get::<i16, _>(0)?, 200);
+    assert!(row.try_get::<i8, _>(0).is_err());
+    assert!(row.try_get::<i64, _>(1).is_err());
+    assert!(row.try_get::<u64, _>(2).is_err());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_bits() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -4,7 +4,10 @@ use sqlx::postgres::{
     PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
     PgPoolOptions, PgRow, PgSeverity, Postgres,
 };
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
+use sqlx::{
+    Column, Connection, Executor, IsolationLevel, QueryBuilder, Row, Statement, TransactionOptions,
+    TypeInfo,
+};
 use sqlx_test::{new, pool, setup_if_needed};
 use std::env;
 use std::sync::Arc;
@@ -452,6 +455,39 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_begin_with_transaction_options() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut tx = conn
+        .begin_with(
+            TransactionOptions::new()
+                .isolation(IsolationLevel::Serializable)
+                .read_only(true),
+        )
+        .await?;
+
+    let isolation: String = sqlx::query_scalar("SHOW transaction_isolation")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(isolation, "serializable");
+
+    let read_only: String = sqlx::query_scalar("SHOW transaction_read_only")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(read_only, "on");
+
+    // options cannot be applied to a savepoint
+    assert!(tx
+        .begin_with(TransactionOptions::new().read_only(false))
+        .await
+        .is_err());
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -1018,6 +1054,60 @@ async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_cancels_statements_after_timeout() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let started = std::time::Instant::now();
+
+    let res = sqlx::query("SELECT pg_sleep(5)")
+        .timeout(Duration::from_millis(200))
+        .execute(&mut conn)
+        .await;
+
+    let err = res.unwrap_err();
+    let err = err.into_database_error().unwrap();
+    // query_canceled
+    assert_eq!(err.code().as_deref(), Some("57014"));
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    // the connection remains usable
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pg_listener_generic() -> anyhow::Result<()> {
+    use sqlx::{Listener, Notification};
+
+    async fn recv_one(listener: &mut impl Listener) -> anyhow::Result<(String, String)> {
+        let notification = listener.recv().await?;
+
+        Ok((
+            notification.channel().to_owned(),
+            notification.payload().to_owned(),
+        ))
+    }
+
+    let pool = pool::<Postgres>().await?;
+    let mut notify_conn = new::<Postgres>().await?;
+
+    let mut listener = PgListener::connect_with(&pool).await?;
+    Listener::listen(&mut listener, "generic_channel").await?;
+
+    notify_conn
+        .execute("NOTIFY generic_channel, 'payload'")
+        .await?;
+
+    let (channel, payload) = recv_one(&mut listener).await?;
+    assert_eq!(channel, "generic_channel");
+    assert_eq!(payload, "payload");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_supports_domain_types_in_composite_domain_types() -> anyhow::Result<()> {
     // Only supported in Postgres 11+
@@ -1819,3 +1909,167 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_pipelines_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE pipelined (id INT8 PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let mut pipeline = conn.pipeline();
+    pipeline
+        .push(
+            sqlx::query("INSERT INTO pipelined (id, name) VALUES ($1, $2), ($3, $4)")
+                .bind(1_i64)
+                .bind("a")
+                .bind(2_i64)
+                .bind("b"),
+        )
+        .push(
+            sqlx::query("UPDATE pipelined SET name = $1 WHERE id = $2")
+                .bind("c")
+                .bind(2_i64),
+        )
+        .push("SELECT name FROM pipelined ORDER BY id");
+    assert_eq!(pipeline.len(), 3);
+
+    let results = pipeline.fetch_all().await?;
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_empty());
+    assert!(results[1].is_empty());
+
+    let names: Vec<String> = results[2].iter().map(|row| row.get(0)).collect();
+    assert_eq!(names, ["a", "c"]);
+
+    let mut pipeline = conn.pipeline();
+    pipeline
+        .push(sqlx::query("DELETE FROM pipelined WHERE id = $1").bind(1_i64))
+        .push(sqlx::query("UPDATE pipelined SET name = name || $1").bind("!"));
+
+    let rows_affected: Vec<u64> = pipeline
+        .execute()
+        .await?
+        .iter()
+        .map(|result| result.rows_affected())
+        .collect();
+    assert_eq!(rows_affected, [1, 1]);
+
+    // a failing query rolls back the whole pipeline
+    let mut pipeline = conn.pipeline();
+    pipeline
+        .push(sqlx::query("DELETE FROM pipelined"))
+        .push(sqlx::query("INSERT INTO pipelined (id, name) VALUES ($1, NULL)").bind(3_i64));
+
+    assert!(pipeline.execute().await.is_err());
+
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM pipelined ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(names, ["c!"]);
+
+    // the connection remains usable
+    assert!(conn.pipeline().execute().await?.is_empty());
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_results_per_statement() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let results: Vec<_> = conn
+        .execute_many(
+            r#"
+CREATE TEMPORARY TABLE items (id SERIAL PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO items (name) VALUES ('a'), ('b'), ('c');
+UPDATE items SET name = 'z' WHERE id > 1;
+DELETE FROM items WHERE id = 1;
+            "#,
+        )
+        .try_collect()
+        .await?;
+
+    let changes: Vec<u64> = results.iter().map(|r| r.rows_affected()).collect();
+    assert_eq!(changes, [0, 3, 2, 1]);
+
+    // the stream ends at the failing statement
+    let mut stream = conn.execute_many(
+        r#"
+UPDATE items SET name = 'y';
+SELECT * FROM _sqlx_does_not_exist;
+UPDATE items SET name = 'x';
+        "#,
+    );
+
+    assert_eq!(stream.try_next().await?.map(|r| r.rows_affected()), Some(2));
+    assert!(stream.try_next().await.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_splits_push_values_with_by_bind_limit() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE _sqlx_bulk_insert (id INTEGER, name TEXT)")
+        .await?;
+
+    // 140,000 parameters need three statements
+    let mut qb: QueryBuilder<Postgres> = QueryBuilder::new("INSERT INTO _sqlx_bulk_insert ");
+
+    let result = qb
+        .push_values_with(&mut conn, 0..70_000, |mut b, id| {
+            b.push_bind(id).push_bind(id.to_string());
+        })
+        .await?;
+
+    assert_eq!(result.rows_affected(), 70_000);
+    assert_eq!(qb.sql(), "INSERT INTO _sqlx_bulk_insert ");
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_bulk_insert")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 70_000);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_bind_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let statement = conn.prepare("SELECT $1::int4 + 1").await?;
+
+    let err = statement
+        .query()
+        .bind_checked("one")
+        .err()
+        .expect("a string is not compatible with INT4");
+
+    assert!(matches!(err, sqlx::Error::Encode(_)));
+    assert!(err.to_string().contains("parameter 1"));
+
+    let err = statement
+        .query()
+        .bind_checked(1_i32)?
+        .bind_checked(2_i32)
+        .err()
+        .expect("the statement has only one parameter");
+
+    assert!(err.to_string().contains("too many values bound"));
+
+    let row = statement
+        .query()
+        .bind_checked(1_i32)?
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.get::<i32, _>(0), 2);
+
+    Ok(())
+}
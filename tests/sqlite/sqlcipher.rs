@@ -201,3 +201,40 @@ async fn it_allows_to_rekey_the_db() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_quotes_keys_and_rekeys() -> anyhow::Result<()> {
+    let (url, _dir) = new_db_url().await?;
+
+    let mut conn = SqliteConnectOptions::from_str(&url)?
+        .pragma_key("the 'password'")
+        .create_if_missing(true)
+        .connect()
+        .await?;
+
+    fill_db(&mut conn).await?;
+
+    conn.rekey("new 'password'").await?;
+
+    // the old key is no longer valid
+    let mut conn = SqliteConnectOptions::from_str(&url)?
+        .pragma_key("the 'password'")
+        .connect()
+        .await?;
+
+    assert!(query("SELECT * FROM Company;")
+        .fetch_all(&mut conn)
+        .await
+        .is_err());
+
+    let mut conn = SqliteConnectOptions::from_str(&url)?
+        .pragma_key("new 'password'")
+        .connect()
+        .await?;
+
+    let result = query("SELECT * FROM Company;").fetch_all(&mut conn).await?;
+
+    assert_eq!(result.len(), 2);
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use rand_xoshiro::Xoshiro256PlusPlus;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
 use sqlx::{
     query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor, Row,
-    SqliteConnection, SqlitePool, Statement, TypeInfo,
+    SqliteConnection, SqlitePool, Statement, TypeInfo, Value,
 };
 use sqlx_test::new;
 use std::sync::Arc;
@@ -189,6 +189,25 @@ async fn it_fetches_in_loop() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fetches_chunked() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let batches: Vec<Vec<(i64,)>> = sqlx::query_as(
+        "SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 UNION ALL SELECT 4 UNION ALL SELECT 5",
+    )
+    .fetch_chunked(&mut conn, 2)
+    .try_collect()
+    .await?;
+
+    assert_eq!(
+        batches,
+        vec![vec![(1,), (2,)], vec![(3,), (4,)], vec![(5,)]]
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_executes_with_pool() -> anyhow::Result<()> {
     let pool: SqlitePool = SqlitePoolOptions::new()
@@ -220,6 +239,40 @@ async fn it_opens_with_extension() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(sqlite_ipaddr)]
+#[sqlx_macros::test]
+async fn it_loads_extensions_at_run_time() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // the SQL function is disabled by default
+    assert!(conn
+        .execute("SELECT load_extension('ipaddr')")
+        .await
+        .is_err());
+
+    conn.load_extension("ipaddr", None).await?;
+    conn.execute("SELECT ipmasklen('192.168.16.12/24');")
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(sqlite_ipaddr)]
+#[sqlx_macros::test]
+async fn it_enables_load_extension_sql_function() -> anyhow::Result<()> {
+    use std::str::FromStr;
+
+    let opts =
+        SqliteConnectOptions::from_str(&dotenvy::var("DATABASE_URL")?)?.enable_load_extension(true);
+
+    let mut conn = SqliteConnection::connect_with(&opts).await?;
+    conn.execute("SELECT load_extension('ipaddr')").await?;
+    conn.execute("SELECT ipmasklen('192.168.16.12/24');")
+        .await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_opens_in_memory() -> anyhow::Result<()> {
     // If the filename is ":memory:", then a private, temporary in-memory database
@@ -249,192 +302,1248 @@ async fn it_fails_to_parse() -> anyhow::Result<()> {
 
     let err = res.unwrap_err().to_string();
 
-    assert_eq!(
-        "error returned from database: (code: 1) near \"SEELCT\": syntax error",
-        err
+    assert_eq!(
+        "error returned from database: (code: 1) near \"SEELCT\": syntax error",
+        err
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_handles_empty_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+    let done = conn.execute("").await?;
+
+    assert_eq!(done.rows_affected(), 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+fn it_binds_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let v: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(10_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(v, 10);
+
+    let v: (i32, i32) = sqlx::query_as("SELECT ?1, ?")
+        .bind(10_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(v.0, 10);
+    assert_eq!(v.1, 10);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+fn it_binds_dollar_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let v: (i32, i32) = sqlx::query_as("SELECT $1, $2")
+        .bind(10_i32)
+        .bind(11_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(v.0, 10);
+    assert_eq!(v.1, 11);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let _ = conn
+        .execute(
+            r#"
+CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)
+            "#,
+        )
+        .await?;
+
+    for index in 1..=10_i32 {
+        let done = sqlx::query("INSERT INTO users (id) VALUES (?)")
+            .bind(index * 2)
+            .execute(&mut conn)
+            .await?;
+
+        assert_eq!(done.rows_affected(), 1);
+    }
+
+    let sum: i32 = sqlx::query_as("SELECT id FROM users")
+        .fetch(&mut conn)
+        .try_fold(0_i32, |acc, (x,): (i32,)| async move { Ok(acc + x) })
+        .await?;
+
+    assert_eq!(sum, 110);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_execute_multiple_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let done = conn
+        .execute(
+            r#"
+CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, other INTEGER);
+INSERT INTO users DEFAULT VALUES;
+            "#,
+        )
+        .await?;
+
+    assert_eq!(done.rows_affected(), 1);
+
+    for index in 2..5_i32 {
+        let (id, other): (i32, i32) = sqlx::query_as(
+            r#"
+INSERT INTO users (other) VALUES (?);
+SELECT id, other FROM users WHERE id = last_insert_rowid();
+            "#,
+        )
+        .bind(index)
+        .fetch_one(&mut conn)
+        .await?;
+
+        assert_eq!(id, index);
+        assert_eq!(other, index);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_results_per_statement() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let results: Vec<_> = conn
+        .execute_many(
+            r#"
+CREATE TEMPORARY TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO items (name) VALUES ('a'), ('b'), ('c');
+UPDATE items SET name = 'z' WHERE id > 1;
+DELETE FROM items WHERE id = 1;
+            "#,
+        )
+        .try_collect()
+        .await?;
+
+    let changes: Vec<u64> = results.iter().map(|r| r.rows_affected()).collect();
+    assert_eq!(changes, [0, 3, 2, 1]);
+    assert_eq!(results[1].last_insert_rowid(), 3);
+
+    // arguments are bound to the statements in order
+    let results: Vec<_> = sqlx::query(
+        r#"
+INSERT INTO items (name) VALUES (?);
+SELECT name FROM items WHERE id > ? ORDER BY id;
+        "#,
+    )
+    .bind("d")
+    .bind(2_i32)
+    .fetch_many(&mut conn)
+    .try_collect()
+    .await?;
+
+    assert_eq!(results.len(), 4);
+    assert!(matches!(&results[0], sqlx::Either::Left(r) if r.rows_affected() == 1));
+    assert!(matches!(&results[1], sqlx::Either::Right(row) if row.get::<String, _>(0) == "z"));
+    assert!(matches!(&results[2], sqlx::Either::Right(row) if row.get::<String, _>(0) == "d"));
+    assert!(matches!(&results[3], sqlx::Either::Left(r) if r.rows_affected() == 0));
+
+    // execution stops at the first failing statement
+    let err = conn
+        .execute("INSERT INTO items (name) VALUES ('e'); INSERT INTO items (name) VALUES (NULL); INSERT INTO items (name) VALUES ('f')")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("NOT NULL"), "{err}");
+
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM items ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(names, ["z", "z", "d", "e"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_interleaves_reads_and_writes() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut cursor = conn.fetch(
+        "
+CREATE TABLE IF NOT EXISTS _sqlx_test (
+    id INT PRIMARY KEY,
+    text TEXT NOT NULL
+);
+
+SELECT 'Hello World' as _1;
+
+INSERT INTO _sqlx_test (text) VALUES ('this is a test');
+
+SELECT id, text FROM _sqlx_test;
+    ",
+    );
+
+    let row = cursor.try_next().await?.unwrap();
+
+    assert!("Hello World" == row.try_get::<&str, _>("_1")?);
+
+    let row = cursor.try_next().await?.unwrap();
+
+    let id: i64 = row.try_get("id")?;
+    let text: &str = row.try_get("text")?;
+
+    assert_eq!(0, id);
+    assert_eq!("this is a test", text);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_collations() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // also tests `.lock_handle()`
+    conn.lock_handle()
+        .await?
+        .create_collation("test_collation", |l, r| l.cmp(r).reverse())?;
+
+    let _ = conn
+        .execute(
+            r#"
+CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL COLLATE test_collation)
+            "#,
+        )
+        .await?;
+
+    sqlx::query("INSERT INTO users (name) VALUES (?)")
+        .bind("a")
+        .execute(&mut conn)
+        .await?;
+    sqlx::query("INSERT INTO users (name) VALUES (?)")
+        .bind("b")
+        .execute(&mut conn)
+        .await?;
+
+    let row: SqliteRow = conn
+        .fetch_one("SELECT name FROM users ORDER BY name ASC")
+        .await?;
+    let name: &str = row.try_get(0)?;
+
+    assert_eq!(name, "b");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_collations_in_unique_indexes() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.create_collation("case_fold", |l, r| l.to_lowercase().cmp(&r.to_lowercase()))
+        .await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE tags (name TEXT NOT NULL UNIQUE COLLATE case_fold)
+        "#,
+    )
+    .await?;
+
+    conn.execute("INSERT INTO tags (name) VALUES ('Rust')")
+        .await?;
+
+    let result = conn
+        .execute("INSERT INTO tags (name) VALUES ('rUST')")
+        .await;
+
+    assert!(result
+        .unwrap_err()
+        .into_database_error()
+        .unwrap()
+        .is_unique_violation());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_functions() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.create_function("add_one", 1, true, |args| {
+        let value: Option<i64> = args[0].try_decode()?;
+        Ok(value.map(|value| value + 1))
+    })
+    .await?;
+
+    conn.create_function("fail", 0, false, |_| -> Result<i64, _> {
+        Err("function failed".into())
+    })
+    .await?;
+
+    let value: i64 = sqlx::query_scalar("SELECT add_one(?)")
+        .bind(41_i64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 42);
+
+    let value: Option<i64> = sqlx::query_scalar("SELECT add_one(NULL)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, None);
+
+    let err = conn.execute("SELECT fail()").await.unwrap_err();
+    assert!(err.to_string().contains("function failed"));
+
+    // registered on every connection through the options
+    let mut conn = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .function("reverse", 1, true, |args| {
+            let text: String = args[0].try_decode()?;
+            Ok(text.chars().rev().collect::<String>())
+        })
+        .connect()
+        .await?;
+
+    let value: String = sqlx::query_scalar("SELECT reverse('abc')")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, "cba");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_aggregates() -> anyhow::Result<()> {
+    use sqlx::error::BoxDynError;
+    use sqlx::sqlite::{SqliteAggregate, SqliteValue};
+
+    struct Product;
+
+    impl SqliteAggregate for Product {
+        type State = i64;
+        type Output = i64;
+
+        fn init(&self) -> i64 {
+            1
+        }
+
+        fn step(&self, product: &mut i64, args: &[SqliteValue]) -> Result<(), BoxDynError> {
+            *product *= args[0].try_decode::<i64>()?;
+            Ok(())
+        }
+
+        fn finalize(&self, product: i64) -> Result<i64, BoxDynError> {
+            Ok(product)
+        }
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.create_aggregate("product", 1, true, Product).await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE numbers (category TEXT NOT NULL, value INTEGER NOT NULL);
+INSERT INTO numbers VALUES ('a', 2), ('a', 3), ('b', 4), ('b', 5), ('b', 6);
+        "#,
+    )
+    .await?;
+
+    let products: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT category, product(value) FROM numbers GROUP BY category ORDER BY category",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(products, vec![("a".into(), 6), ("b".into(), 120)]);
+
+    // the aggregate of no rows is the initial state
+    let product: i64 = sqlx::query_scalar("SELECT product(value) FROM numbers WHERE value > 10")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(product, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_window_functions() -> anyhow::Result<()> {
+    use sqlx::error::BoxDynError;
+    use sqlx::sqlite::{SqliteAggregate, SqliteValue, SqliteWindowFunction};
+
+    struct Total;
+
+    impl SqliteAggregate for Total {
+        type State = i64;
+        type Output = i64;
+
+        fn init(&self) -> i64 {
+            0
+        }
+
+        fn step(&self, total: &mut i64, args: &[SqliteValue]) -> Result<(), BoxDynError> {
+            *total += args[0].try_decode::<i64>()?;
+            Ok(())
+        }
+
+        fn finalize(&self, total: i64) -> Result<i64, BoxDynError> {
+            Ok(total)
+        }
+    }
+
+    impl SqliteWindowFunction for Total {
+        fn inverse(&self, total: &mut i64, args: &[SqliteValue]) -> Result<(), BoxDynError> {
+            *total -= args[0].try_decode::<i64>()?;
+            Ok(())
+        }
+
+        fn value(&self, total: &i64) -> Result<i64, BoxDynError> {
+            Ok(*total)
+        }
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.create_window_function("total_of", 1, true, Total)
+        .await?;
+
+    let totals: Vec<i64> = sqlx::query_scalar(
+        r#"
+WITH numbers (value) AS (VALUES (1), (2), (3), (4))
+SELECT total_of(value) OVER (ORDER BY value ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)
+FROM numbers
+        "#,
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(totals, vec![1, 3, 5, 7]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_backs_up_databases() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+    let mut backup = SqliteConnection::connect(":memory:").await?;
+
+    let mut steps = Vec::new();
+
+    conn.backup_to(&mut backup, 1, |progress| steps.push(progress))
+        .await?;
+
+    // one step per page
+    let last = steps.last().unwrap();
+    assert_eq!(last.remaining, 0);
+    assert_eq!(steps.len(), last.page_count as usize);
+
+    let expected: Vec<String> = sqlx::query_scalar("SELECT name FROM sqlite_master ORDER BY name")
+        .fetch_all(&mut conn)
+        .await?;
+
+    let actual: Vec<String> = sqlx::query_scalar("SELECT name FROM sqlite_master ORDER BY name")
+        .fetch_all(&mut backup)
+        .await?;
+
+    assert!(!actual.is_empty());
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_serializes_and_deserializes_databases() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    conn.execute(
+        r#"
+CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO users (name) VALUES ('alice'), ('bob');
+        "#,
+    )
+    .await?;
+
+    let data = conn.serialize().await?;
+
+    // the header of a database file
+    assert!(data.starts_with(b"SQLite format 3\0"));
+
+    let mut copy = SqliteConnection::connect(":memory:").await?;
+    copy.deserialize(&data, false).await?;
+
+    copy.execute("INSERT INTO users (name) VALUES ('carol')")
+        .await?;
+
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM users ORDER BY id")
+        .fetch_all(&mut copy)
+        .await?;
+
+    assert_eq!(names, ["alice", "bob", "carol"]);
+
+    // read-only copies cannot be written to
+    let mut copy = SqliteConnection::connect(":memory:").await?;
+    copy.deserialize(&data, true).await?;
+
+    assert!(copy
+        .execute("INSERT INTO users (name) VALUES ('dave')")
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_updates() -> anyhow::Result<()> {
+    use futures::StreamExt;
+    use sqlx::sqlite::{SqliteOperation, SqliteUpdate};
+
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let mut updates = conn.updates().await?;
+
+    conn.execute(
+        r#"
+INSERT INTO users (id, name) VALUES (1, 'alice');
+UPDATE users SET name = 'bob' WHERE id = 1;
+DELETE FROM users WHERE id = 1;
+        "#,
+    )
+    .await?;
+
+    for operation in [
+        SqliteOperation::Insert,
+        SqliteOperation::Update,
+        SqliteOperation::Delete,
+    ] {
+        assert_eq!(
+            updates.next().await,
+            Some(SqliteUpdate {
+                operation,
+                database: "main".into(),
+                table: "users".into(),
+                rowid: 1,
+            })
+        );
+    }
+
+    // removing the hook ends the stream
+    conn.lock_handle().await?.remove_update_hook();
+
+    assert_eq!(updates.next().await, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_commit_and_rollback_hooks() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let allow_commit = Arc::new(AtomicBool::new(false));
+    let commits = Arc::new(AtomicUsize::new(0));
+    let rollbacks = Arc::new(AtomicUsize::new(0));
+
+    {
+        let mut handle = conn.lock_handle().await?;
+
+        let (allow_commit, commits) = (allow_commit.clone(), commits.clone());
+        handle.set_commit_hook(move || {
+            commits.fetch_add(1, Ordering::SeqCst);
+            allow_commit.load(Ordering::SeqCst)
+        });
+
+        let rollbacks = rollbacks.clone();
+        handle.set_rollback_hook(move || {
+            rollbacks.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+
+    // the commit is vetoed and rolled back
+    let mut tx = conn.begin().await?;
+    tx.execute("INSERT INTO users (name) VALUES ('alice')")
+        .await?;
+    assert!(tx.commit().await.is_err());
+
+    assert_eq!(commits.load(Ordering::SeqCst), 1);
+    assert_eq!(rollbacks.load(Ordering::SeqCst), 1);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    allow_commit.store(true, Ordering::SeqCst);
+
+    conn.execute("INSERT INTO users (name) VALUES ('bob')")
+        .await?;
+
+    assert_eq!(commits.load(Ordering::SeqCst), 2);
+    assert_eq!(rollbacks.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checkpoints_the_wal() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteCheckpointMode, SqliteJournalMode};
+
+    let dir = tempdir::TempDir::new("sqlx-sqlite-checkpoint")?;
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(dir.path().join("checkpoint.db"))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .wal_autocheckpoint(0)
+        .connect()
+        .await?;
+
+    conn.execute(
+        r#"
+CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO users (name) VALUES ('alice'), ('bob');
+        "#,
+    )
+    .await?;
+
+    let checkpoint = conn.wal_checkpoint(SqliteCheckpointMode::Passive).await?;
+
+    assert!(!checkpoint.busy);
+    assert!(checkpoint.log_frames > 0);
+    assert_eq!(checkpoint.checkpointed_frames, checkpoint.log_frames);
+
+    // the log is empty after truncating it
+    let checkpoint = conn.wal_checkpoint(SqliteCheckpointMode::Truncate).await?;
+
+    assert!(!checkpoint.busy);
+    assert_eq!(checkpoint.log_frames, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_busy_handlers() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    let dir = tempdir::TempDir::new("sqlx-sqlite-busy")?;
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("busy.db"))
+        .create_if_missing(true);
+
+    let mut writer = options.connect().await?;
+    writer
+        .execute("CREATE TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut conn = options
+        .clone()
+        .busy_timeout(Duration::from_millis(50))
+        .busy_backoff(Duration::from_millis(1), Duration::from_millis(10))
+        .connect()
+        .await?;
+
+    // hold the write lock on the database
+    writer.execute("BEGIN IMMEDIATE").await?;
+
+    // the backoff gives up after the busy timeout
+    let err = conn
+        .execute("INSERT INTO users DEFAULT VALUES")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("locked"), "{err}");
+
+    let calls = Arc::new(AtomicU32::new(0));
+    let calls_ = calls.clone();
+
+    conn.lock_handle().await?.set_busy_handler(move |count| {
+        calls_.store(count + 1, Ordering::SeqCst);
+        count < 2
+    });
+
+    assert!(conn
+        .execute("INSERT INTO users DEFAULT VALUES")
+        .await
+        .is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    writer.execute("COMMIT").await?;
+
+    conn.execute("INSERT INTO users DEFAULT VALUES").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_shares_named_in_memory_databases() -> anyhow::Result<()> {
+    let pool = SqlitePoolOptions::new()
+        .min_connections(2)
+        .max_connections(2)
+        .connect_with(SqliteConnectOptions::new().shared_memory("it_shares_named_in_memory"))
+        .await?;
+
+    let mut a = pool.acquire().await?;
+    let mut b = pool.acquire().await?;
+
+    a.execute("CREATE TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+    a.execute("INSERT INTO users DEFAULT VALUES").await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut *b)
+        .await?;
+    assert_eq!(count, 1);
+
+    // connections opened from a connection URL share the same database
+    let mut conn =
+        SqliteConnection::connect("sqlite:file:it_shares_named_in_memory?mode=memory&cache=shared")
+            .await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_attaches_databases_to_pool_connections() -> anyhow::Result<()> {
+    let dir = tempdir::TempDir::new("sqlx-sqlite-attach")?;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect_with(
+            SqliteConnectOptions::new()
+                .filename(dir.path().join("main.db"))
+                .create_if_missing(true)
+                .attach(dir.path().join("archive.db"), "archive"),
+        )
+        .await?;
+
+    let mut a = pool.acquire().await?;
+    let mut b = pool.acquire().await?;
+
+    a.execute(
+        r#"
+CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+CREATE TABLE archive.users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO users (name) VALUES ('alice');
+INSERT INTO archive.users (name) VALUES ('bob');
+        "#,
+    )
+    .await?;
+
+    let names: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM main.users UNION ALL SELECT name FROM archive.users ORDER BY name",
+    )
+    .fetch_all(&mut *b)
+    .await?;
+    assert_eq!(names, ["alice", "bob"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_uses_read_only_pools_with_a_single_writer() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteJournalMode;
+
+    let dir = tempdir::TempDir::new("sqlx-sqlite-read-only")?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("data.db"))
+        .journal_mode(SqliteJournalMode::Wal)
+        .optimize_on_close(true, None);
+
+    let writer = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options.clone().create_if_missing(true))
+        .await?;
+    let read_only = options.read_only(true);
+    let readers = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect_with(read_only.clone())
+        .await?;
+
+    writer
+        .execute("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let mut reader = readers.acquire().await?;
+    let mut tx = reader.begin().await?;
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(count, 0);
+
+    // the writer is not blocked by the open read transaction
+    writer
+        .execute("INSERT INTO items (name) VALUES ('a')")
+        .await?;
+    tx.rollback().await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items")
+        .fetch_one(&mut *reader)
+        .await?;
+    assert_eq!(count, 1);
+
+    let err = reader
+        .execute("INSERT INTO items (name) VALUES ('b')")
+        .await
+        .unwrap_err();
+    let err = err
+        .into_database_error()
+        .expect("expected a database error");
+    assert_eq!(err.code().as_deref(), Some("8"));
+    drop(reader);
+
+    // read-only connections skip `PRAGMA optimize`, which would fail
+    read_only.connect().await?.close().await?;
+
+    readers.close().await;
+    writer.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_virtual_tables() -> anyhow::Result<()> {
+    use sqlx::error::BoxDynError;
+    use sqlx::sqlite::{SqliteVirtualCursor, SqliteVirtualTable, SqliteVirtualValue};
+
+    struct Fruits(Vec<&'static str>);
+
+    impl SqliteVirtualTable for Fruits {
+        type Cursor = FruitsCursor;
+
+        fn schema(&self) -> String {
+            "CREATE TABLE x(name TEXT, length INTEGER)".into()
+        }
+
+        fn open(&self) -> Result<FruitsCursor, BoxDynError> {
+            Ok(FruitsCursor {
+                fruits: self.0.clone(),
+                index: None,
+            })
+        }
+    }
+
+    struct FruitsCursor {
+        fruits: Vec<&'static str>,
+        index: Option<usize>,
+    }
+
+    impl SqliteVirtualCursor for FruitsCursor {
+        fn next(&mut self) -> Result<bool, BoxDynError> {
+            let index = self.index.map_or(0, |index| index + 1);
+            self.index = Some(index);
+            Ok(index < self.fruits.len())
+        }
+
+        fn column(
+            &self,
+            index: usize,
+            value: &mut SqliteVirtualValue<'_>,
+        ) -> Result<(), BoxDynError> {
+            let fruit = self.fruits[self.index.unwrap()];
+
+            match index {
+                0 => value.set(fruit),
+                1 => value.set(fruit.len() as i64),
+                _ => return Err("no such column".into()),
+            }
+
+            Ok(())
+        }
+
+        fn rowid(&self) -> i64 {
+            self.index.unwrap() as i64
+        }
+    }
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .module("fruits", Fruits(vec!["apple", "banana", "cherry"]))
+        .connect()
+        .await?;
+
+    // eponymous virtual table
+    let rows: Vec<(String, i64)> =
+        sqlx::query_as("SELECT name, length FROM fruits WHERE length > 5 ORDER BY name")
+            .fetch_all(&mut conn)
+            .await?;
+    assert_eq!(rows, [("banana".to_owned(), 6), ("cherry".to_owned(), 6)]);
+
+    // joins scan the table once per row of the outer table
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM fruits a, fruits b")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 9);
+
+    conn.create_module("empty", Fruits(vec![])).await?;
+    conn.execute("CREATE VIRTUAL TABLE no_fruits USING empty")
+        .await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM no_fruits")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    assert!(conn
+        .execute("INSERT INTO no_fruits (name) VALUES ('durian')")
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_registers_vfs() -> anyhow::Result<()> {
+    use sqlx::sqlite::vfs;
+
+    // a shim which forwards all calls to the default VFS
+    let default = vfs::find(None).expect("no default VFS");
+    let shim = Box::leak(Box::new(unsafe { std::ptr::read(default) }));
+    shim.zName = b"sqlx-shim\0".as_ptr().cast();
+    shim.pNext = std::ptr::null_mut();
+
+    unsafe { vfs::register(shim, false)? };
+
+    assert!(vfs::find(Some("sqlx-shim")).is_some());
+    assert!(vfs::names().iter().any(|name| name == "sqlx-shim"));
+    assert_ne!(vfs::names()[0], "sqlx-shim");
+
+    let dir = tempdir::TempDir::new("sqlx-sqlite-vfs")?;
+    let mut conn = SqliteConnectOptions::new()
+        .filename(dir.path().join("vfs.db"))
+        .create_if_missing(true)
+        .vfs("sqlx-shim")
+        .connect()
+        .await?;
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    conn.close().await?;
+
+    // an unknown VFS fails to connect
+    assert!(SqliteConnectOptions::new()
+        .filename(dir.path().join("vfs.db"))
+        .vfs("sqlx-unknown")
+        .connect()
+        .await
+        .is_err());
+
+    unsafe { vfs::unregister(shim)? };
+    assert!(vfs::find(Some("sqlx-shim")).is_none());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_interrupts_cancelled_and_timed_out_statements() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    // counting to a billion takes far longer than the test
+    const RUNAWAY: &str = "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 1000000000) SELECT COUNT(*) FROM c";
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .statement_timeout(Duration::from_millis(100))
+        .connect()
+        .await?;
+
+    let started = Instant::now();
+    let err = conn
+        .fetch_one(RUNAWAY)
+        .await
+        .err()
+        .expect("expected a timeout");
+    assert!(matches!(err, sqlx::Error::Timeout), "{err}");
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    // the connection remains usable
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    // statements can also be interrupted from another thread
+    conn.set_statement_timeout(None).await?;
+    let interrupt = conn.interrupt_handle();
+    let done = Arc::new(AtomicBool::new(false));
+    let timer = std::thread::spawn({
+        let done = Arc::clone(&done);
+        move || {
+            // interrupting before the statement starts has no effect
+            while !done.load(Ordering::Acquire) {
+                std::thread::sleep(Duration::from_millis(50));
+                interrupt.interrupt();
+            }
+        }
+    });
+
+    let err = conn
+        .fetch_one(RUNAWAY)
+        .await
+        .err()
+        .expect("expected an interrupt");
+    done.store(true, Ordering::Release);
+    timer.join().unwrap();
+
+    let err = err
+        .into_database_error()
+        .expect("expected a database error");
+    assert_eq!(err.code().as_deref(), Some("9"));
+
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    // a timeout can also be set for a single query
+    let started = Instant::now();
+    let err = sqlx::query(RUNAWAY)
+        .timeout(Duration::from_millis(100))
+        .fetch_one(&mut conn)
+        .await
+        .err()
+        .expect("expected a timeout");
+    assert!(matches!(err, sqlx::Error::Timeout), "{err}");
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    // dropping the query interrupts it
+    let mut conn = new::<Sqlite>().await?;
+
+    assert!(
+        sqlx_core::rt::timeout(Duration::from_millis(100), conn.fetch_one(RUNAWAY))
+            .await
+            .is_err()
     );
 
+    let started = Instant::now();
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+    assert!(started.elapsed() < Duration::from_secs(10));
+
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_handles_empty_queries() -> anyhow::Result<()> {
+async fn it_supports_authorizers() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteAuthorization, SqliteAuthorizerAction};
+
     let mut conn = new::<Sqlite>().await?;
-    let done = conn.execute("").await?;
 
-    assert_eq!(done.rows_affected(), 0);
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, password TEXT);
+INSERT INTO users (name, password) VALUES ('alice', 'secret');
+        "#,
+    )
+    .await?;
 
-    Ok(())
-}
+    conn.lock_handle().await?.set_authorizer(|request| {
+        match (request.action, request.arg1, request.arg2) {
+            (SqliteAuthorizerAction::Read, Some("users"), Some("password")) => {
+                SqliteAuthorization::Ignore
+            }
+            (SqliteAuthorizerAction::Delete, Some("users"), _) => SqliteAuthorization::Deny,
+            _ => SqliteAuthorization::Allow,
+        }
+    });
 
-#[sqlx_macros::test]
-fn it_binds_parameters() -> anyhow::Result<()> {
-    let mut conn = new::<Sqlite>().await?;
+    let (name, password): (String, Option<String>) =
+        sqlx::query_as("SELECT name, password FROM users")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(name, "alice");
+    assert_eq!(password, None);
 
-    let v: i32 = sqlx::query_scalar("SELECT ?")
-        .bind(10_i32)
-        .fetch_one(&mut conn)
-        .await?;
+    let err = conn.execute("DELETE FROM users").await.unwrap_err();
+    assert_eq!(err.as_database_error().unwrap().code().unwrap(), "23");
 
-    assert_eq!(v, 10);
+    conn.lock_handle().await?.remove_authorizer();
 
-    let v: (i32, i32) = sqlx::query_as("SELECT ?1, ?")
-        .bind(10_i32)
+    let password: Option<String> = sqlx::query_scalar("SELECT password FROM users")
         .fetch_one(&mut conn)
         .await?;
+    assert_eq!(password.as_deref(), Some("secret"));
 
-    assert_eq!(v.0, 10);
-    assert_eq!(v.1, 10);
+    conn.execute("DELETE FROM users").await?;
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-fn it_binds_dollar_parameters() -> anyhow::Result<()> {
-    let mut conn = new::<Sqlite>().await?;
+async fn it_streams_blobs() -> anyhow::Result<()> {
+    use futures::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, SeekFrom};
 
-    let v: (i32, i32) = sqlx::query_as("SELECT $1, $2")
-        .bind(10_i32)
-        .bind(11_i32)
-        .fetch_one(&mut conn)
+    let mut conn = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .connect()
         .await?;
 
-    assert_eq!(v.0, 10);
-    assert_eq!(v.1, 11);
-
-    Ok(())
-}
+    conn.execute(
+        r#"
+CREATE TABLE files (id INTEGER PRIMARY KEY, data BLOB NOT NULL);
+INSERT INTO files (id, data) VALUES (1, zeroblob(10)), (2, x'0102');
+        "#,
+    )
+    .await?;
 
-#[sqlx_macros::test]
-async fn it_executes_queries() -> anyhow::Result<()> {
-    let mut conn = new::<Sqlite>().await?;
+    {
+        let mut blob = conn.blob_open("files", "data", 1).await?;
+        assert_eq!(blob.len(), 10);
 
-    let _ = conn
-        .execute(
-            r#"
-CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)
-            "#,
-        )
-        .await?;
+        blob.write_all(b"hello").await?;
+        blob.seek(SeekFrom::End(-5)).await?;
+        blob.write_all(b"world").await?;
 
-    for index in 1..=10_i32 {
-        let done = sqlx::query("INSERT INTO users (id) VALUES (?)")
-            .bind(index * 2)
-            .execute(&mut conn)
-            .await?;
+        // the size of a blob cannot change
+        let err = blob.write_all(b"!").await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
 
-        assert_eq!(done.rows_affected(), 1);
+        blob.reopen(2)?;
+        let mut data = Vec::new();
+        blob.read_to_end(&mut data).await?;
+        assert_eq!(data, [1, 2]);
     }
 
-    let sum: i32 = sqlx::query_as("SELECT id FROM users")
-        .fetch(&mut conn)
-        .try_fold(0_i32, |acc, (x,): (i32,)| async move { Ok(acc + x) })
+    let data: Vec<u8> = sqlx::query_scalar("SELECT data FROM files WHERE id = 1")
+        .fetch_one(&mut conn)
         .await?;
+    assert_eq!(data, b"helloworld");
 
-    assert_eq!(sum, 110);
+    let mut blob = conn.blob_open_read_only("files", "data", 1).await?;
+    blob.seek(SeekFrom::Start(5)).await?;
+    let mut data = String::new();
+    blob.read_to_string(&mut data).await?;
+    assert_eq!(data, "world");
+    assert!(blob.write_all(b"hello").await.is_err());
+    drop(blob);
+
+    assert!(conn.blob_open("files", "data", 3).await.is_err());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_execute_multiple_statements() -> anyhow::Result<()> {
-    let mut conn = new::<Sqlite>().await?;
+async fn it_applies_typed_pragmas() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteTempStore;
 
-    let done = conn
-        .execute(
-            r#"
-CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, other INTEGER);
-INSERT INTO users DEFAULT VALUES;
-            "#,
-        )
+    let mut conn = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .cache_size_kib(4096)
+        .temp_store(SqliteTempStore::Memory)
+        .foreign_keys(false)
+        .connect()
         .await?;
 
-    assert_eq!(done.rows_affected(), 1);
+    let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(cache_size, -4096);
 
-    for index in 2..5_i32 {
-        let (id, other): (i32, i32) = sqlx::query_as(
-            r#"
-INSERT INTO users (other) VALUES (?);
-SELECT id, other FROM users WHERE id = last_insert_rowid();
-            "#,
-        )
-        .bind(index)
+    // 2 = MEMORY
+    let temp_store: i64 = sqlx::query_scalar("PRAGMA temp_store")
         .fetch_one(&mut conn)
         .await?;
+    assert_eq!(temp_store, 2);
 
-        assert_eq!(id, index);
-        assert_eq!(other, index);
-    }
+    let foreign_keys: bool = sqlx::query_scalar("PRAGMA foreign_keys")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(!foreign_keys);
 
     Ok(())
 }
 
+#[cfg(feature = "sqlite-session")]
 #[sqlx_macros::test]
-async fn it_interleaves_reads_and_writes() -> anyhow::Result<()> {
-    let mut conn = new::<Sqlite>().await?;
-
-    let mut cursor = conn.fetch(
-        "
-CREATE TABLE IF NOT EXISTS _sqlx_test (
-    id INT PRIMARY KEY,
-    text TEXT NOT NULL
-);
-
-SELECT 'Hello World' as _1;
-
-INSERT INTO _sqlx_test (text) VALUES ('this is a test');
+async fn it_applies_session_changesets() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteConflictAction, SqliteConflictKind, SqliteOperation};
 
-SELECT id, text FROM _sqlx_test;
-    ",
-    );
-
-    let row = cursor.try_next().await?.unwrap();
-
-    assert!("Hello World" == row.try_get::<&str, _>("_1")?);
+    const SCHEMA: &str = r#"
+CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO items (id, name) VALUES (1, 'one'), (2, 'two');
+    "#;
 
-    let row = cursor.try_next().await?.unwrap();
+    let mut source = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .connect()
+        .await?;
+    let mut replica = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .connect()
+        .await?;
 
-    let id: i64 = row.try_get("id")?;
-    let text: &str = row.try_get("text")?;
+    source.execute(SCHEMA).await?;
+    replica.execute(SCHEMA).await?;
 
-    assert_eq!(0, id);
-    assert_eq!("this is a test", text);
+    source.start_session(Some(&["items"])).await?;
+    source
+        .execute(
+            "UPDATE items SET name = 'uno' WHERE id = 1; INSERT INTO items VALUES (3, 'three')",
+        )
+        .await?;
+    let changeset = source.changeset().await?;
+    source.end_session().await?;
+    assert!(!changeset.is_empty());
 
-    Ok(())
-}
+    // the row inserted by the changeset already exists on the replica
+    replica
+        .execute("INSERT INTO items (id, name) VALUES (3, 'tres')")
+        .await?;
 
-#[sqlx_macros::test]
-async fn it_supports_collations() -> anyhow::Result<()> {
-    let mut conn = new::<Sqlite>().await?;
+    let mut conflicts = Vec::new();
+    replica
+        .apply_changeset(&changeset, |conflict| {
+            conflicts.push(conflict.clone());
+            SqliteConflictAction::Replace
+        })
+        .await?;
 
-    // also tests `.lock_handle()`
-    conn.lock_handle()
-        .await?
-        .create_collation("test_collation", |l, r| l.cmp(r).reverse())?;
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, SqliteConflictKind::Conflict);
+    assert_eq!(conflicts[0].table, "items");
+    assert_eq!(conflicts[0].operation, SqliteOperation::Insert);
 
-    let _ = conn
-        .execute(
-            r#"
-CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL COLLATE test_collation)
-            "#,
-        )
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM items ORDER BY id")
+        .fetch_all(&mut replica)
         .await?;
+    assert_eq!(names, ["uno", "two", "three"]);
 
-    sqlx::query("INSERT INTO users (name) VALUES (?)")
-        .bind("a")
-        .execute(&mut conn)
-        .await?;
-    sqlx::query("INSERT INTO users (name) VALUES (?)")
-        .bind("b")
-        .execute(&mut conn)
+    // aborting rolls back all changes of the changeset
+    replica
+        .execute("DELETE FROM items; INSERT INTO items (id, name) VALUES (1, 'eins')")
         .await?;
+    assert!(replica
+        .apply_changeset(&changeset, |_| SqliteConflictAction::Abort)
+        .await
+        .is_err());
 
-    let row: SqliteRow = conn
-        .fetch_one("SELECT name FROM users ORDER BY name ASC")
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM items ORDER BY id")
+        .fetch_all(&mut replica)
         .await?;
-    let name: &str = row.try_get(0)?;
+    assert_eq!(names, ["eins"]);
 
-    assert_eq!(name, "b");
+    assert!(source.changeset().await.is_err());
 
     Ok(())
 }
@@ -490,6 +1599,48 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_controls_the_statement_cache() -> anyhow::Result<()> {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .prepare_persistent(false)
+        .connect()
+        .await?;
+
+    for query in ["SELECT ? AS val", "SELECT ? + 1 AS val"] {
+        let val: i32 = sqlx::query_scalar(query)
+            .bind(1)
+            .fetch_one(&mut conn)
+            .await?;
+        assert!(val > 0);
+    }
+    // the cache size is updated once the worker finished the statement
+    conn.ping().await?;
+    assert_eq!(2, conn.cached_statements_size());
+
+    assert!(conn.invalidate_statement("SELECT ? AS val").await?);
+    assert!(!conn.invalidate_statement("SELECT ? AS val").await?);
+
+    // shrinking the cache removes the least recently used statements
+    conn.set_statement_cache_capacity(1).await?;
+
+    for query in ["SELECT ? AS val", "SELECT ? + 2 AS val"] {
+        let val: i32 = sqlx::query_scalar(query)
+            .bind(1)
+            .fetch_one(&mut conn)
+            .await?;
+        assert!(val > 0);
+    }
+    conn.ping().await?;
+    assert_eq!(1, conn.cached_statements_size());
+
+    assert!(!conn.invalidate_statement("SELECT ? + 1 AS val").await?);
+    assert!(!conn.invalidate_statement("SELECT ? AS val").await?);
+    assert!(conn.invalidate_statement("SELECT ? + 2 AS val").await?);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -794,3 +1945,203 @@ async fn test_multiple_set_progress_handler_calls_drop_old_handler() -> anyhow::
     assert_eq!(1, Arc::strong_count(&ref_counted_object));
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_reports_query_events() -> anyhow::Result<()> {
+    let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let events_ = Arc::clone(&events);
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .event_handler(Arc::new(move |event: &sqlx::QueryEvent<'_>| {
+            events_.lock().unwrap().push((
+                event.sql.to_owned(),
+                event.arguments,
+                event.rows_affected,
+                event.rows_returned,
+                event.error.map(str::to_owned),
+            ));
+        }))
+        .connect()
+        .await?;
+
+    events.lock().unwrap().clear();
+
+    conn.execute("CREATE TABLE events (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    sqlx::query("INSERT INTO events (id) VALUES (?), (?)")
+        .bind(1_i32)
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query("SELECT id FROM events")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert!(conn.execute("SELECT * FROM missing").await.is_err());
+
+    // the worker reports the failed query before it executes the next one
+    conn.execute("SELECT 1").await?;
+
+    let events = events.lock().unwrap();
+
+    assert_eq!(events.len(), 5);
+    assert_eq!(
+        events[1],
+        (
+            "INSERT INTO events (id) VALUES (?), (?)".to_owned(),
+            2,
+            2,
+            0,
+            None
+        )
+    );
+    assert_eq!(
+        events[2],
+        ("SELECT id FROM events".to_owned(), 0, 0, 2, None)
+    );
+    assert_eq!(events[3].0, "SELECT * FROM missing");
+    assert!(events[3]
+        .4
+        .as_deref()
+        .map_or(false, |e| e.contains("no such table")));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_shares_a_connection_between_tasks() -> anyhow::Result<()> {
+    use futures::future::BoxFuture;
+
+    let shared = sqlx::SharedConnection::<Sqlite>::new(new::<Sqlite>().await?);
+
+    shared
+        .run(
+            |conn: &mut SqliteConnection| -> BoxFuture<'_, Result<_, sqlx::Error>> {
+                Box::pin(async move {
+                    conn.execute("CREATE TEMPORARY TABLE shared (id INTEGER)")
+                        .await
+                })
+            },
+        )
+        .await??;
+
+    let tasks =
+        (0..10).map(|i: i32| {
+            let shared = shared.clone();
+
+            sqlx_core::rt::spawn(async move {
+                shared
+                .run(move |conn: &mut SqliteConnection| -> BoxFuture<'_, Result<_, sqlx::Error>> {
+                    Box::pin(async move {
+                        sqlx::query("INSERT INTO shared (id) VALUES (?)")
+                            .bind(i)
+                            .execute(conn)
+                            .await
+                    })
+                })
+                .await
+            })
+        });
+
+    for res in futures::future::join_all(tasks).await {
+        res??;
+    }
+
+    // the temporary table is only visible because every task used the same connection
+    let count: i64 = shared
+        .run(
+            |conn: &mut SqliteConnection| -> BoxFuture<'_, Result<i64, sqlx::Error>> {
+                Box::pin(async move {
+                    sqlx::query_scalar("SELECT COUNT(*) FROM shared")
+                        .fetch_one(conn)
+                        .await
+                })
+            },
+        )
+        .await??;
+
+    assert_eq!(count, 10);
+    assert_eq!(shared.queued(), 0);
+
+    let other = shared.clone();
+    shared.close().await?;
+
+    assert!(matches!(
+        other
+            .run(|_: &mut SqliteConnection| -> BoxFuture<'_, ()> { Box::pin(async {}) })
+            .await,
+        Err(sqlx::Error::WorkerCrashed)
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rewrites_queries() -> anyhow::Result<()> {
+    use sqlx::sqlite::{SqliteArguments, SqliteCheckpointMode};
+    use sqlx::{Arguments, QueryRewriter};
+    use std::borrow::Cow;
+
+    struct AddOne;
+
+    impl QueryRewriter<Sqlite> for AddOne {
+        fn rewrite<'q>(
+            &self,
+            sql: &mut Cow<'q, str>,
+            arguments: Option<&mut SqliteArguments<'q>>,
+        ) -> Result<(), sqlx::Error> {
+            if sql.starts_with("DELETE") || sql.starts_with("PRAGMA") {
+                return Err(sqlx::Error::Protocol("connection is read-only".into()));
+            }
+
+            if let Some(arguments) = arguments {
+                *sql = format!("{} + ?", sql).into();
+                arguments.add(1_i32);
+            }
+
+            Ok(())
+        }
+    }
+
+    let mut conn = SqliteConnectOptions::new()
+        .filename(":memory:")
+        .query_rewriter(Arc::new(AddOne))
+        .connect()
+        .await?;
+
+    let value: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(41_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 42);
+
+    // also applies to prepared statements
+    let statement = conn.prepare("SELECT ?").await?;
+
+    let value: i32 = statement
+        .query_scalar()
+        .bind(41_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 42);
+
+    // queries without arguments are passed through
+    let value: i32 = conn.fetch_one("SELECT 1").await?.get(0);
+    assert_eq!(value, 1);
+
+    conn.execute("CREATE TABLE rewritten (id INTEGER)").await?;
+
+    let err = conn.execute("DELETE FROM rewritten").await.unwrap_err();
+    assert!(err.to_string().contains("connection is read-only"));
+
+    // statements issued by the driver itself, such as the PRAGMAs run while connecting,
+    // are not rewritten
+    conn.wal_checkpoint(SqliteCheckpointMode::Passive).await?;
+
+    Ok(())
+}